@@ -45,6 +45,7 @@ ping = Ping
 send-ping = Odeslat ping
 share = Sdílet
 share-file = Sdílet soubor
+share-folder = Sdílet složku
 share-clipboard = Odeslat schránku
 share-text = Sdílet text
 send-text = Odeslat text
@@ -52,6 +53,7 @@ sms = SMS
 sms-messages = SMS zprávy
 messages = Zprávy
 media = Média
+call-history = Historie hovorů
 media-controls = Ovládání médií
 notifications = Oznámení
 device-must-be-connected = Pro použití akcí musí být zařízení připojeno
@@ -95,12 +97,23 @@ settings-sms-section = SMS zprávy
 settings-sms-notifications = Zobrazit oznámení
 settings-sms-show-sender = Zobrazit odesílatele
 settings-sms-show-content = Zobrazit obsah
+settings-phone-region = Oblast telefonních čísel
+settings-phone-region-description = Kód země pro čísla zapsaná bez předvolby. Ponechte prázdné pro použití systémového národního prostředí.
 
 # Call Notifications settings
 settings-call-section = Hovory
 settings-call-notifications = Zobrazit oznámení
 settings-call-show-name = Zobrazit jméno volajícího
 settings-call-show-number = Zobrazit telefonní číslo
+settings-call-history = Uchovávat historii hovorů
+settings-call-history-description = Zaznamenávat příchozí, přijaté a zmeškané hovory na tomto počítači
+settings-call-pause-media = Pozastavit média během hovorů
+settings-call-pause-media-description = Pozastavit hudbu a videa na tomto počítači, když telefon zvoní
+settings-call-lower-volume = Místo pozastavení ztlumit hlasitost
+settings-call-resume-after-ring = Obnovit, když telefon přestane zvonit
+settings-call-resume-after-ring-description = Telefon nehlásí přijaté hovory, takže se média obnoví i během hovoru, který jste přijali
+call-media-paused = Média pozastavena kvůli hovoru
+resume-media = Obnovit
 
 # SMS Notification text
 sms-notification-title = Nová SMS
@@ -113,9 +126,19 @@ incoming-call-from = Příchozí hovor od { $name }
 missed-call = Zmeškaný hovor
 missed-call-from = Zmeškaný hovor od { $name }
 
+# Call history
+calls-title = Hovory - { $device }
+call-incoming = Příchozí
+call-missed = Zmeškaný
+send-sms = Poslat SMS
+no-calls = Zatím žádné hovory
+no-calls-hint = Zde se zobrazí hovory na váš telefon
+
 # File Notifications settings
 settings-file-section = Přenosy souborů
 settings-file-notifications = Zobrazit oznámení
+settings-share-folders-as-zip = Odesílat složky jako archivy zip
+settings-share-folders-as-zip-description = Sdílené složky dorazí do telefonu jako jeden archiv místo jednotlivých souborů
 
 # File Notification text
 file-received = Soubor přijat
@@ -137,6 +160,11 @@ load-more-conversations = Načíst další konverzace
 syncing = Synchronizování s telefonem...
 syncing-conversations = Synchronizování konverzací...
 hold-to-copy = Podržte pro zkopírování
+search-messages = Hledat ve zprávách
+search-no-results = Žádné odpovídající zprávy
+export-messages = Exportovat zprávy
+export-conversation = Exportovat konverzaci
+export-as = Exportovat jako:
 
 # New message
 new-message = Nová zpráva
@@ -146,6 +174,22 @@ recipient-placeholder = Jméno nebo telefonní číslo
 type-message = Napište zprávu...
 send = Odeslat
 sending = Odesílání...
+message-queued = Ve frontě
+message-not-delivered = Nedoručeno
+
+# Contacts
+contacts = Kontakty
+contacts-title = Kontakty - { $device }
+search-contacts = Hledat kontakty
+no-contacts = Žádné kontakty
+no-contacts-hint = Synchronizujte kontakty z telefonu, aby se zde zobrazily
+search-no-contacts = Žádné odpovídající kontakty
+open-conversation = Otevřít konverzaci
+phone-kind-mobile = Mobil
+phone-kind-home = Domů
+phone-kind-work = Práce
+phone-kind-other = Jiné
+sync-contacts = Synchronizovat kontakty z telefonu
 
 # Media controls
 loading-media = Načítání informací o médiích...
@@ -154,6 +198,11 @@ start-playing = Spusťte na telefonu hudbu
 media-not-available = Ovládání médií není k dispozici
 enable-mpris = Ujistěte se, že je zapnutý MPRIS plugin
 player = Přehrávač:
+media-shuffle-on = Náhodné pořadí je zapnuté
+media-shuffle-off = Náhodné pořadí je vypnuté
+media-repeat-off = Opakování je vypnuté
+media-repeat-all = Opakuje se vše
+media-repeat-one = Opakuje se jedna skladba
 volume = Hlasitost
 no-track-playing = Nic se nepřehrává
 
@@ -170,10 +219,36 @@ ping-sent = Ping odeslán!
 ping-failed = Odeslání pingu selhalo
 share-complete = Sdílení dokončeno
 share-failed = Sdílení selhalo
+share-batch-sharing = { $count ->
+    [one] Sdílí se 1 položka...
+    [few] Sdílí se { $count } položky...
+   *[other] Sdílí se { $count } položek...
+}
+share-batch-complete = { $count ->
+    [one] Sdílen 1 soubor
+    [few] Sdíleny { $count } soubory
+   *[other] Sdíleno { $count } souborů
+}
+share-batch-partial = Sdíleno { $shared } z { $total } souborů
+share-batch-failed = { $count ->
+    [one] Nepodařilo se sdílet 1 soubor: { $error }
+    [few] Nepodařilo se sdílet { $count } soubory: { $error }
+   *[other] Nepodařilo se sdílet { $count } souborů: { $error }
+}
 clipboard-sent = Schránka odeslána!
 clipboard-failed = Odeslání schránky selhalo
 sms-sent = Zpráva odeslána!
 sms-failed = Odeslání zprávy selhalo
+contacts-sync-requested = Synchronizace kontaktů vyžádána
+contacts-sync-failed = Synchronizace kontaktů selhala
+sms-export-complete = Exportováno do { $path }
+sms-export-failed = Export se nezdařil: { $error }
+sms-export-loading = Načítání zpráv pro export...
+sms-export-incomplete = { $count ->
+    [one] Exportováno do { $path }, ale 1 konverzaci se nepodařilo načíst celou
+    [few] Exportováno do { $path }, ale { $count } konverzace se nepodařilo načíst celé
+   *[other] Exportováno do { $path }, ale { $count } konverzací se nepodařilo načíst celých
+}
 pairing-accepted = Párování přijato
 pairing-rejected = Párování odmítnuto
 unpaired = Párování se zařízením zrušeno
@@ -182,6 +257,47 @@ unpaired = Párování se zařízením zrušeno
 attachment = Příloha
 loading-attachment = Načítání přílohy...
 attachment-failed = Nepodařilo se načíst přílohu
+attach-files = Přiložit soubory
+attachment-unsupported = { $name }: tento typ souboru nelze odeslat přes MMS
+attachment-too-large = { $name } je větší než { $max_mb } MB
+attachment-unreadable = Soubor { $name } nelze přečíst
+attachment-too-many = Nejvýše { $max } příloh na zprávu
+
+# Scheduled messages
+send-later = Odeslat později
+schedule-in-hour = Za hodinu
+schedule-tomorrow-morning = Zítra v 08:00
+schedule-time-placeholder = HH:MM
+schedule = Naplánovat
+schedule-invalid-time = Zadejte čas ve tvaru HH:MM
+sms-scheduled = Naplánováno na { $time }
+scheduled-sms-sent = Naplánovaná zpráva odeslána
+scheduled-sms-failed = Naplánovanou zprávu se nepodařilo odeslat
+scheduled-not-sent = Neodesláno
+scheduled-for = Odešle se { $time }
+edit = Upravit
+cancel-scheduled = Zrušit
+save = Uložit
+
+# Drafts
+draft-prefix = Koncept:
+
+# SIM selection
+send-from = Odeslat z
+sim-label = SIM { $slot }
+
+# Conversation flags
+pin-conversation = Připnout konverzaci
+unpin-conversation = Odepnout konverzaci
+mute-conversation = Ztlumit oznámení
+unmute-conversation = Zrušit ztlumení oznámení
+archive-conversation = Archivovat konverzaci
+unarchive-conversation = Obnovit z archivu
+conversation-archived = Konverzace archivována
+conversation-muted = Oznámení této konverzace ztlumena
+archived-conversations = Archivované ({ $count })
+show-reaction-text = Zobrazit reakce jako text
+show-reaction-badges = Zobrazit reakce jako odznaky
 
 # Reaction-thread merging UI
 merge-toggle-on-tooltip = Odpovědi na reakce jsou seskupeny do příslušné konverzace. Kliknutím je zobrazíte jako samostatná vlákna.
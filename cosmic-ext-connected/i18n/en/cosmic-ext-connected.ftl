@@ -45,6 +45,7 @@ ping = Ping
 send-ping = Send Ping
 share = Share
 share-file = Share File
+share-folder = Share Folder
 share-clipboard = Send Clipboard
 share-text = Share Text
 send-text = Send Text
@@ -52,6 +53,7 @@ sms = SMS
 sms-messages = SMS Messages
 messages = Messages
 media = Media
+call-history = Call History
 media-controls = Media Controls
 notifications = Notifications
 device-must-be-connected = Device must be connected to use actions
@@ -95,12 +97,23 @@ settings-sms-section = SMS messages
 settings-sms-notifications = Show notifications
 settings-sms-show-sender = Show sender
 settings-sms-show-content = Show content
+settings-phone-region = Phone number region
+settings-phone-region-description = Country code used for numbers written without one. Leave empty to use the system locale.
 
 # Call Notifications settings
 settings-call-section = Calls
 settings-call-notifications = Show notifications
 settings-call-show-name = Show caller name
 settings-call-show-number = Show phone number
+settings-call-history = Keep call history
+settings-call-history-description = Record incoming, answered and missed calls on this computer
+settings-call-pause-media = Pause media during calls
+settings-call-pause-media-description = Pause music and videos on this computer while the phone rings
+settings-call-lower-volume = Lower the volume instead of pausing
+settings-call-resume-after-ring = Resume when the phone stops ringing
+settings-call-resume-after-ring-description = The phone doesn't report answered calls, so media also resumes during a call you picked up
+call-media-paused = Media paused for a call
+resume-media = Resume
 
 # SMS Notification text
 sms-notification-title = New SMS
@@ -113,9 +126,19 @@ incoming-call-from = Incoming call from { $name }
 missed-call = Missed Call
 missed-call-from = Missed call from { $name }
 
+# Call history
+calls-title = Calls - { $device }
+call-incoming = Incoming
+call-missed = Missed
+send-sms = Send SMS
+no-calls = No calls yet
+no-calls-hint = Calls to your phone will be listed here
+
 # File Notifications settings
 settings-file-section = File transfers
 settings-file-notifications = Show notifications
+settings-share-folders-as-zip = Send folders as zip archives
+settings-share-folders-as-zip-description = Shared folders arrive on the phone as one archive instead of their individual files
 
 # File Notification text
 file-received = File Received
@@ -137,6 +160,11 @@ load-more-conversations = Load more conversations
 syncing = Syncing with phone...
 syncing-conversations = Syncing conversations...
 hold-to-copy = Hold to copy
+search-messages = Search messages
+search-no-results = No matching messages
+export-messages = Export messages
+export-conversation = Export conversation
+export-as = Export as:
 
 # New message
 new-message = New Message
@@ -146,6 +174,22 @@ recipient-placeholder = Name or phone number
 type-message = Type a message...
 send = Send
 sending = Sending...
+message-queued = Queued
+message-not-delivered = Not delivered
+
+# Contacts
+contacts = Contacts
+contacts-title = Contacts - { $device }
+search-contacts = Search contacts
+no-contacts = No contacts
+no-contacts-hint = Sync contacts from your phone to see them here
+search-no-contacts = No matching contacts
+open-conversation = Open conversation
+phone-kind-mobile = Mobile
+phone-kind-home = Home
+phone-kind-work = Work
+phone-kind-other = Other
+sync-contacts = Sync contacts from phone
 
 # Media controls
 loading-media = Loading media info...
@@ -154,6 +198,11 @@ start-playing = Start playing music on your phone
 media-not-available = Media controls not available
 enable-mpris = Make sure the MPRIS plugin is enabled
 player = Player:
+media-shuffle-on = Shuffle is on
+media-shuffle-off = Shuffle is off
+media-repeat-off = Repeat is off
+media-repeat-all = Repeating all
+media-repeat-one = Repeating one track
 volume = Volume
 no-track-playing = No track playing
 
@@ -170,10 +219,32 @@ ping-sent = Ping sent!
 ping-failed = Failed to send ping
 share-complete = Share complete
 share-failed = Failed to share
+share-batch-sharing = { $count ->
+    [one] Sharing 1 item...
+   *[other] Sharing { $count } items...
+}
+share-batch-complete = { $count ->
+    [one] Shared 1 file
+   *[other] Shared { $count } files
+}
+share-batch-partial = Shared { $shared } of { $total } files
+share-batch-failed = { $count ->
+    [one] Failed to share 1 file: { $error }
+   *[other] Failed to share { $count } files: { $error }
+}
 clipboard-sent = Clipboard sent!
 clipboard-failed = Failed to send clipboard
 sms-sent = Message sent!
 sms-failed = Failed to send message
+contacts-sync-requested = Contact sync requested
+contacts-sync-failed = Contact sync failed
+sms-export-complete = Exported to { $path }
+sms-export-failed = Export failed: { $error }
+sms-export-loading = Loading messages for export...
+sms-export-incomplete = { $count ->
+    [one] Exported to { $path }, but 1 conversation could not be loaded in full
+   *[other] Exported to { $path }, but { $count } conversations could not be loaded in full
+}
 pairing-accepted = Pairing accepted
 pairing-rejected = Pairing rejected
 unpaired = Unpaired from device
@@ -182,6 +253,47 @@ unpaired = Unpaired from device
 attachment = Attachment
 loading-attachment = Loading attachment...
 attachment-failed = Failed to load attachment
+attach-files = Attach files
+attachment-unsupported = { $name }: this file type can't be sent by MMS
+attachment-too-large = { $name } is larger than { $max_mb } MB
+attachment-unreadable = Couldn't read { $name }
+attachment-too-many = At most { $max } attachments per message
+
+# Scheduled messages
+send-later = Send later
+schedule-in-hour = In 1 hour
+schedule-tomorrow-morning = Tomorrow 08:00
+schedule-time-placeholder = HH:MM
+schedule = Schedule
+schedule-invalid-time = Enter a time as HH:MM
+sms-scheduled = Scheduled for { $time }
+scheduled-sms-sent = Scheduled message sent
+scheduled-sms-failed = Scheduled message could not be sent
+scheduled-not-sent = Not sent
+scheduled-for = Sends { $time }
+edit = Edit
+cancel-scheduled = Cancel
+save = Save
+
+# Drafts
+draft-prefix = Draft:
+
+# SIM selection
+send-from = Send from
+sim-label = SIM { $slot }
+
+# Conversation flags
+pin-conversation = Pin conversation
+unpin-conversation = Unpin conversation
+mute-conversation = Mute notifications
+unmute-conversation = Unmute notifications
+archive-conversation = Archive conversation
+unarchive-conversation = Unarchive conversation
+conversation-archived = Conversation archived
+conversation-muted = Notifications muted for this conversation
+archived-conversations = Archived ({ $count })
+show-reaction-text = Show reactions as text
+show-reaction-badges = Show reactions as badges
 
 # Reaction-thread merging UI
 merge-toggle-on-tooltip = Reaction replies are grouped into their conversation. Click to show as separate threads.
@@ -45,6 +45,7 @@ ping = Ping
 send-ping = Skicka ping
 share = Dela
 share-file = Dela fil
+share-folder = Dela mapp
 share-clipboard = Skicka urklipp
 share-text = Dela text
 send-text = Skicka text
@@ -52,6 +53,7 @@ sms = SMS
 sms-messages = SMS Meddelanden
 messages = Meddelanden
 media = Media
+call-history = Samtalshistorik
 media-controls = Mediekontroller
 notifications = Aviseringar
 device-must-be-connected = Enheten måste vara ansluten för att använda åtgärder
@@ -97,12 +99,23 @@ learn-more = Learn more
 # Inställningar för SMS-aviseringar
 settings-sms-notifications = SMS-aviseringar
 settings-sms-show-content = Visa meddelandeinnehåll
+settings-phone-region = Region för telefonnummer
+settings-phone-region-description = Landskod för nummer som skrivits utan landsnummer. Lämna tomt för att använda systemets språkinställning.
 settings-sms-show-sender = Visa avsändarnamn
 
 # Inställningar för samtalsaviseringar
 settings-call-notifications = Samtalsaviseringar
 settings-call-show-number = Visa telefonnummer
 settings-call-show-name = Visa uppringarens namn
+settings-call-history = Spara samtalshistorik
+settings-call-history-description = Registrera inkommande, besvarade och missade samtal på den här datorn
+settings-call-pause-media = Pausa media under samtal
+settings-call-pause-media-description = Pausa musik och video på den här datorn medan telefonen ringer
+settings-call-lower-volume = Sänk volymen i stället för att pausa
+settings-call-resume-after-ring = Återuppta när telefonen slutar ringa
+settings-call-resume-after-ring-description = Telefonen rapporterar inte besvarade samtal, så media återupptas även under ett samtal du har svarat på
+call-media-paused = Media pausade för ett samtal
+resume-media = Återuppta
 
 # SMS Aviseringstext
 sms-notification-title = Nytt SMS
@@ -115,8 +128,18 @@ incoming-call-from = Inkommande samtal från { $name }
 missed-call = Missat samtal
 missed-call-from = Missat samtal från { $name }
 
+# Samtalshistorik
+calls-title = Samtal - { $device }
+call-incoming = Inkommande
+call-missed = Missat
+send-sms = Skicka SMS
+no-calls = Inga samtal än
+no-calls-hint = Samtal till din telefon visas här
+
 # Inställningar för filaviseringar
 settings-file-notifications = Filaviseringar
+settings-share-folders-as-zip = Skicka mappar som zip-arkiv
+settings-share-folders-as-zip-description = Delade mappar kommer fram till telefonen som ett arkiv i stället för som enskilda filer
 
 # Filmeddelandetext
 file-received = Fil mottagen
@@ -138,6 +161,11 @@ load-more-conversations = Ladda mer konversationer
 syncing = Synkroniserar med telefonen...
 syncing-conversations = Synkroniserar konversationer...
 hold-to-copy = Håll nedtryckt för att kopiera
+search-messages = Sök i meddelanden
+search-no-results = Inga matchande meddelanden
+export-messages = Exportera meddelanden
+export-conversation = Exportera konversation
+export-as = Exportera som:
 
 # Nytt meddelande
 new-message = Nytt meddelande
@@ -147,6 +175,22 @@ recipient-placeholder = Namn eller telefonnummer
 type-message = Skriv ett meddelande...
 send = Skicka
 sending = Skickar...
+message-queued = I kö
+message-not-delivered = Inte levererat
+
+# Kontakter
+contacts = Kontakter
+contacts-title = Kontakter - { $device }
+search-contacts = Sök kontakter
+no-contacts = Inga kontakter
+no-contacts-hint = Synkronisera kontakter från telefonen för att se dem här
+search-no-contacts = Inga matchande kontakter
+open-conversation = Öppna konversation
+phone-kind-mobile = Mobil
+phone-kind-home = Hem
+phone-kind-work = Arbete
+phone-kind-other = Annat
+sync-contacts = Synkronisera kontakter från telefonen
 
 # Mediekontroller
 loading-media = Laddar medieinformation...
@@ -155,6 +199,11 @@ start-playing = Börja spela musik på din telefon
 media-not-available = Mediekontroller är inte tillgängliga
 enable-mpris = Se till att MPRIS-pluginet är aktiverat
 player = Spelare:
+media-shuffle-on = Blanda är på
+media-shuffle-off = Blanda är av
+media-repeat-off = Upprepning är av
+media-repeat-all = Upprepar alla
+media-repeat-one = Upprepar ett spår
 volume = Volym
 no-track-playing = Inget spår spelas
 
@@ -171,10 +220,32 @@ ping-sent = Ping skickat!
 ping-failed = Misslyckades med att skicka ping
 share-complete = Delning är slutförd
 share-failed = Misslyckades att dela
+share-batch-sharing = { $count ->
+    [one] Delar 1 objekt...
+   *[other] Delar { $count } objekt...
+}
+share-batch-complete = { $count ->
+    [one] Delade 1 fil
+   *[other] Delade { $count } filer
+}
+share-batch-partial = Delade { $shared } av { $total } filer
+share-batch-failed = { $count ->
+    [one] Misslyckades att dela 1 fil: { $error }
+   *[other] Misslyckades att dela { $count } filer: { $error }
+}
 clipboard-sent = Urklipp skickat!
 clipboard-failed = Misslyckades med att skicka urklipp
 sms-sent = Meddelande skickat!
 sms-failed = Misslyckades med att skicka meddelande
+contacts-sync-requested = Kontaktsynkronisering begärd
+contacts-sync-failed = Kontaktsynkroniseringen misslyckades
+sms-export-complete = Exporterat till { $path }
+sms-export-failed = Exporten misslyckades: { $error }
+sms-export-loading = Läser in meddelanden för export...
+sms-export-incomplete = { $count ->
+    [one] Exporterat till { $path }, men 1 konversation kunde inte läsas in helt
+   *[other] Exporterat till { $path }, men { $count } konversationer kunde inte läsas in helt
+}
 pairing-accepted = Parning accepterad
 pairing-rejected = Parning avvisad
 unpaired = Avparad från enhet
@@ -183,6 +254,47 @@ unpaired = Avparad från enhet
 attachment = Bilaga
 loading-attachment = Laddar bilaga...
 attachment-failed = Misslyckades att ladda bilaga
+attach-files = Bifoga filer
+attachment-unsupported = { $name }: den här filtypen kan inte skickas via MMS
+attachment-too-large = { $name } är större än { $max_mb } MB
+attachment-unreadable = Kunde inte läsa { $name }
+attachment-too-many = Högst { $max } bilagor per meddelande
+
+# Scheduled messages
+send-later = Skicka senare
+schedule-in-hour = Om 1 timme
+schedule-tomorrow-morning = I morgon 08:00
+schedule-time-placeholder = HH:MM
+schedule = Schemalägg
+schedule-invalid-time = Ange en tid som HH:MM
+sms-scheduled = Schemalagt till { $time }
+scheduled-sms-sent = Schemalagt meddelande skickat
+scheduled-sms-failed = Det schemalagda meddelandet kunde inte skickas
+scheduled-not-sent = Inte skickat
+scheduled-for = Skickas { $time }
+edit = Redigera
+cancel-scheduled = Avbryt
+save = Spara
+
+# Drafts
+draft-prefix = Utkast:
+
+# SIM selection
+send-from = Skicka från
+sim-label = SIM { $slot }
+
+# Conversation flags
+pin-conversation = Fäst konversation
+unpin-conversation = Lossa konversation
+mute-conversation = Tysta aviseringar
+unmute-conversation = Slå på aviseringar
+archive-conversation = Arkivera konversation
+unarchive-conversation = Avarkivera konversation
+conversation-archived = Konversationen arkiverad
+conversation-muted = Aviseringar tystade för den här konversationen
+archived-conversations = Arkiverade ({ $count })
+show-reaction-text = Visa reaktioner som text
+show-reaction-badges = Visa reaktioner som märken
 
 # Användargränssnitt för sammanslagning av reaktionstrådar
 merge-toggle-on-tooltip = Reaktionstrådarna är sammanslagna. Klicka för att visa separat.
@@ -1,5 +1,9 @@
 //! Main application state and logic for the Connected applet.
 
+use crate::calls::{
+    load_call_log_async, pause_media_for_call_async, record_call_async,
+    resume_media_after_call_async, view_call_history, CallHistoryParams, CallLog,
+};
 use crate::config::Config;
 use crate::constants::{
     dbus::{PENDING_REFRESH_TICK_SECS, SIGNAL_REFRESH_DEBOUNCE_SECS},
@@ -8,19 +12,27 @@ use crate::constants::{
 };
 use crate::device::{
     accept_pairing_async, dismiss_notification_async, fetch_devices_async, find_my_phone_async,
-    reject_pairing_async, request_pair_async, send_clipboard_async, send_ping_async,
-    share_file_async, share_text_async, unpair_async,
+    pick_share_paths_async, reject_pairing_async, request_pair_async, send_clipboard_async,
+    send_ping_async, share_paths_async, share_text_async, unpair_async, ShareSummary,
 };
 use crate::fl;
 use crate::media::{
     fetch_media_info_async, media_action_async, view_media_controls, MediaAction,
-    MediaControlsParams,
+    MediaControlsParams, PlayerCapabilities,
 };
+use crate::notifications::{remember_call_toast, ringing_call_toast, take_call_toast};
+use crate::sms::archive::SmsArchive;
+use crate::sms::attach::PickedAttachment;
+use crate::sms::drafts::{load_drafts_async, SmsDrafts};
+use crate::sms::export::ExportFormat;
+use crate::sms::flags::{load_flags_async, ConversationFlag, ConversationFlagStore};
+use crate::sms::scheduled::{load_scheduled_async, SchedulePreset, ScheduledMessage, SendClaim};
 use crate::sms::{
     fetch_conversations_async, prefetch_conversations_async, SmsConversationStore, SmsViewMode,
 };
 use crate::subscriptions::{
-    call_notification_subscription, dbus_signal_subscription, sms_notification_subscription,
+    call_notification_subscription, dbus_signal_subscription, media_signal_subscription,
+    sms_notification_subscription,
 };
 use crate::ui;
 use crate::views::send_to::{view_send_to, view_share_text, SendToParams, ShareTextParams};
@@ -34,7 +46,8 @@ use cosmic::widget;
 use cosmic::{Application, Element};
 use kdeconnect_dbus::{
     contacts::ContactLookup,
-    plugins::{ConversationSummary, NotificationInfo, SmsMessage},
+    phone_key,
+    plugins::{ConversationSummary, MprisRemoteProperty, NotificationInfo, SmsMessage},
 };
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -89,8 +102,14 @@ pub enum Message {
     // Share actions
     /// Initiate file sharing (opens file picker)
     ShareFile(String),
-    /// File was selected from picker
-    FileSelected(Option<PathBuf>),
+    /// Initiate folder sharing (opens folder picker)
+    ShareFolder(String),
+    /// Files or folders were selected from the picker (empty if cancelled)
+    FilesSelected(Vec<PathBuf>),
+    /// Files or folders were dropped onto a device (device_id, paths)
+    DropFiles(String, Vec<PathBuf>),
+    /// A batch of files finished sharing
+    ShareBatchComplete(ShareSummary),
     /// Initiate text sharing
     ShareText(String, String),
     /// Share operation completed
@@ -142,6 +161,8 @@ pub enum Message {
     OpenUrl(String),
     /// Toggle a specific setting
     ToggleSetting(SettingKey),
+    /// Edit the phone-number region (ISO country code; empty = from locale)
+    SetPhoneRegion(String),
     /// Expand/collapse a collapsible device group (Offline)
     ToggleDeviceGroup(GroupKind),
     /// Set the notification timeout duration (seconds)
@@ -161,8 +182,48 @@ pub enum Message {
     ConversationsLoaded(Vec<ConversationSummary>),
     /// Contacts loaded asynchronously for a device
     ContactsLoaded(String, ContactLookup),
+    /// Reload contacts if the vCard directory changed
+    CheckContactsChanged,
+    /// The daemon announced newly written vCards for the SMS device
+    ContactsCacheSynchronized,
+    /// Ask the phone to sync its contacts to the desktop
+    SyncContacts,
+    /// Contact sync request finished
+    SyncContactsResult(Result<(), String>),
     /// User clicked "Load More" button in conversation list
     LoadMoreConversations,
+    /// Update the conversation-list search query
+    SmsSearchInput(String),
+    /// Open a conversation from a search hit, scrolled to the matched message
+    OpenSearchResult { thread_id: i64, uid: Option<i32> },
+    /// On-disk SMS archive read for a device
+    SmsArchiveLoaded(String, SmsArchive),
+    /// Flush timer tick: write unsaved archive changes to disk
+    SmsArchiveFlush,
+    /// Archive write finished
+    SmsArchiveSaved(Result<(), String>),
+    /// Compose drafts loaded from disk at startup
+    SmsDraftsLoaded(SmsDrafts),
+    /// Flush timer tick: write changed drafts to disk
+    SmsDraftsFlush,
+    /// Drafts write finished
+    SmsDraftsSaved(Result<SmsDrafts, String>),
+    /// Show/hide the export format picker
+    ToggleSmsExportMenu,
+    /// Export the open conversation (or all conversations) in a format
+    ExportSms(ExportFormat),
+    /// Full history loaded for an export: format, conversations to export
+    /// (addresses and thread ids), the loaded messages and the threads that
+    /// didn't arrive in full
+    SmsExportThreadsLoaded(
+        ExportFormat,
+        Vec<(Vec<String>, Vec<i64>)>,
+        Vec<SmsMessage>,
+        Vec<i64>,
+    ),
+    /// Export finished; `None` if the save dialog was cancelled. Carries the
+    /// number of conversations that couldn't be loaded in full.
+    SmsExportComplete(Result<Option<PathBuf>, String>, usize),
     /// SMS-related error occurred
     SmsError(String),
     /// Update SMS compose text input
@@ -171,8 +232,63 @@ pub enum Message {
     SendSms,
     /// SMS send operation completed
     SmsSendResult(Result<String, String>),
+    /// Resend a message the phone reported as failed (by uid)
+    RetrySms(i32),
+    /// Resend of a failed message completed (uid, result)
+    RetrySmsResult(i32, Result<(), String>),
+    /// Open the file picker to attach files to the message being composed
+    PickSmsAttachments,
+    /// Files chosen in the picker, validated
+    SmsAttachmentsPicked(Vec<PickedAttachment>),
+    /// Remove a pending attachment by index
+    RemoveSmsAttachment(usize),
+    /// Toggle a local pin/mute/archive flag on the open conversation
+    ToggleConversationFlag(ConversationFlag),
+    /// Expand/collapse the "Archived" section of the conversation list
+    ToggleArchivedSection,
+    /// Conversation flags loaded from disk at startup
+    ConversationFlagsLoaded(ConversationFlagStore),
+    /// Conversation flags write finished
+    ConversationFlagsSaved(Result<ConversationFlagStore, String>),
+    /// Pick the SIM (subscription ID) to send from
+    SelectSim(i64),
+    /// Show/hide the "Send later" picker
+    ToggleSchedulePicker,
+    /// Update the custom `HH:MM` time in the "Send later" picker
+    ScheduleTimeInput(String),
+    /// Schedule the message being composed instead of sending it now
+    ScheduleSms(SchedulePreset),
+    /// Cancel a scheduled message by ID
+    CancelScheduledSms(i64),
+    /// Try again to send a scheduled message that ran out of attempts
+    RetryScheduledSms(i64),
+    /// Start editing a scheduled message's text
+    EditScheduledSms(i64),
+    /// Update the text of the scheduled message being edited
+    ScheduledEditInput(String),
+    /// Save the edit to the scheduled message
+    SaveScheduledEdit,
+    /// Scheduled messages loaded from disk at startup
+    ScheduledSmsLoaded(Vec<ScheduledMessage>),
+    /// Check timer tick: send scheduled messages that are due
+    ScheduledSmsTick,
+    /// Claim on due scheduled messages written; the result is the file's
+    /// list, in which messages carrying this claim are ours to send
+    ScheduledSmsClaimed(SendClaim, Result<Vec<ScheduledMessage>, String>),
+    /// Scheduled message send completed (message ID, result)
+    ScheduledSmsSent(i64, Result<(), String>),
+    /// Scheduled-message file write finished, with the file's list
+    ScheduledSmsSaved(Result<Vec<ScheduledMessage>, String>),
     /// Open new message compose view
     OpenNewMessage,
+    /// Start a new message to a contact's number (name, phone)
+    MessageContact(String, String),
+    /// Open the contacts browser from the conversation list
+    OpenContactsView,
+    /// Close the contacts browser and return to the conversation list
+    CloseContactsView,
+    /// Update the contacts browser search query
+    ContactsSearchInput(String),
     /// Close new message view
     CloseNewMessage,
     /// Update new message recipient input
@@ -232,10 +348,20 @@ pub enum Message {
     MediaSetVolume(i32),
     /// Select a different player
     MediaSelectPlayer(String),
+    /// Toggle shuffle on the selected player
+    MediaToggleShuffle,
+    /// Switch the selected player to the next repeat mode
+    MediaCycleLoop,
     /// Media control action completed
     MediaActionResult(Result<String, String>),
     /// Refresh media info (for auto-refresh)
     MediaRefresh,
+    /// Seek bar dragged to a position (milliseconds)
+    MediaSeekPreview(i32),
+    /// Seek bar released; seek to the previewed position
+    MediaSeekRelease,
+    /// Advance the interpolated playback position
+    MediaPositionTick(std::time::Instant),
 
     // SMS Notifications
     /// New SMS received via D-Bus signal (device_id, message)
@@ -244,12 +370,34 @@ pub enum Message {
     // Call Notifications
     /// Incoming or missed call received via D-Bus signal
     CallNotification {
+        device_id: String,
         device_name: String,
         event: String,
         phone_number: String,
         contact_name: String,
     },
 
+    /// Call written to the device's call log (device_id, updated log)
+    CallRecorded(String, Result<CallLog, String>),
+    /// Whether local media is paused for a call, after pausing or resuming
+    CallMediaPaused(bool),
+    /// Resume the local media paused for a call
+    ResumeCallMedia,
+
+    // Call history
+    /// Open the call history view for a device
+    OpenCallHistory(String),
+    /// Close the call history view and return to the device page
+    CloseCallHistory,
+    /// Call log loaded from disk (device_id, log)
+    CallLogLoaded(String, CallLog),
+    /// Start an SMS to a number from the call history
+    CallHistorySendSms {
+        device_id: String,
+        name: String,
+        phone_number: String,
+    },
+
     // File Notifications
     /// File received via D-Bus signal
     FileReceived {
@@ -289,8 +437,14 @@ pub enum SettingKey {
     CallNotifications,
     CallShowNumber,
     CallShowName,
+    CallHistory,
+    CallPauseMedia,
+    CallLowerVolume,
+    CallResumeAfterRing,
     FileNotifications,
+    ShareFoldersAsZip,
     MergeReactionThreads,
+    ShowRawReactions,
 }
 
 /// The device-list groups, in display order
@@ -331,6 +485,10 @@ pub struct MediaInfo {
     pub artist: String,
     /// Track album.
     pub album: String,
+    /// Album art URL as reported by the daemon.
+    pub album_art_url: String,
+    /// Album art copied into the applet's cache, if the track has any.
+    pub album_art: Option<PathBuf>,
     /// Whether playback is active.
     pub is_playing: bool,
     /// Current volume (0-100).
@@ -339,10 +497,56 @@ pub struct MediaInfo {
     pub position: i64,
     /// Track length in milliseconds.
     pub length: i64,
-    /// Can go to next track.
-    pub can_next: bool,
-    /// Can go to previous track.
-    pub can_previous: bool,
+    /// When `position` was read from the device.
+    pub position_updated: std::time::Instant,
+    /// Whether the player accepts seeking.
+    pub can_seek: bool,
+    /// What the selected player supports, including shuffle and repeat.
+    pub capabilities: PlayerCapabilities,
+}
+
+impl MediaInfo {
+    /// Playback position at `now`, advanced from the last reading while
+    /// playing so the seek bar moves between refreshes.
+    pub fn position_at(&self, now: std::time::Instant) -> i64 {
+        if !self.is_playing {
+            return self.position;
+        }
+        let elapsed = now
+            .saturating_duration_since(self.position_updated)
+            .as_millis() as i64;
+        let position = self.position + elapsed;
+        if self.length > 0 {
+            position.min(self.length)
+        } else {
+            position
+        }
+    }
+
+    /// Apply a property read from the daemon.
+    pub fn apply(&mut self, property: MprisRemoteProperty, now: std::time::Instant) {
+        match property {
+            MprisRemoteProperty::PlayerList(players) => self.players = players,
+            MprisRemoteProperty::Player(player) => self.current_player = player,
+            MprisRemoteProperty::IsPlaying(is_playing) => {
+                // Settle the interpolated position before the clock stops or starts
+                self.position = self.position_at(now);
+                self.position_updated = now;
+                self.is_playing = is_playing;
+            }
+            MprisRemoteProperty::Volume(volume) => self.volume = volume,
+            MprisRemoteProperty::Length(length) => self.length = length as i64,
+            MprisRemoteProperty::Position(position) => {
+                self.position = position as i64;
+                self.position_updated = now;
+            }
+            MprisRemoteProperty::Title(title) => self.title = title,
+            MprisRemoteProperty::Artist(artist) => self.artist = artist,
+            MprisRemoteProperty::Album(album) => self.album = album,
+            MprisRemoteProperty::AlbumArtUrl(url) => self.album_art_url = url,
+            MprisRemoteProperty::CanSeek(can_seek) => self.can_seek = can_seek,
+        }
+    }
 }
 
 /// View mode for the applet popup.
@@ -363,12 +567,16 @@ pub enum ViewMode {
     MessageThread,
     /// New message compose view
     NewMessage,
+    /// Contacts browser for the SMS device
+    Contacts,
     /// Settings view
     Settings,
     /// About sub-page
     About,
     /// Media player controls
     MediaControls,
+    /// Call history for a device
+    CallHistory,
 }
 
 /// Loading state for SMS operations with phase tracking.
@@ -437,6 +645,21 @@ pub struct ConnectApplet {
     media_loading: bool,
     /// User's explicit player selection (overrides D-Bus value until view is closed)
     media_selected_player: Option<String>,
+    /// Position the seek bar is being dragged to, until it is released
+    media_seek_preview: Option<i64>,
+    /// Clock for interpolating the playback position, advanced by a timer
+    media_now: std::time::Instant,
+
+    // Call history state
+    /// Device ID for the call history view
+    call_log_device_id: Option<String>,
+    /// Device name for the call history header
+    call_log_device_name: Option<String>,
+    /// Log shown in the call history view; `None` while loading
+    call_log: Option<CallLog>,
+    /// Local media is paused for a call and waits for a missed call or the
+    /// user to resume it
+    call_media_paused: bool,
 
     // SendTo submenu state
     /// Device ID for SendTo view
@@ -461,6 +684,37 @@ impl ConnectApplet {
         )
     }
 
+    /// Share picked or dropped paths with a device as one batch.
+    fn share_paths(
+        &mut self,
+        device_id: String,
+        paths: Vec<PathBuf>,
+    ) -> cosmic::app::Task<Message> {
+        let Some(conn) = &self.dbus_connection else {
+            return cosmic::app::Task::none();
+        };
+        if paths.is_empty() {
+            return cosmic::app::Task::none();
+        }
+        self.status_message = Some(fl!("share-batch-sharing", count = paths.len()));
+        cosmic::app::Task::perform(
+            share_paths_async(
+                conn.clone(),
+                device_id,
+                paths,
+                self.config.share_folders_as_zip,
+            ),
+            |summary| cosmic::Action::App(Message::ShareBatchComplete(summary)),
+        )
+    }
+
+    /// Whether the daemon currently reports `device_id` as reachable.
+    fn is_device_reachable(&self, device_id: &str) -> bool {
+        self.devices
+            .iter()
+            .any(|d| d.id == device_id && d.is_reachable)
+    }
+
     /// Apply an `SmsReply` returned by `SmsConversationStore::update()`.
     /// Caller batches the returned task with the store's task.
     fn handle_sms_reply(&mut self, reply: crate::sms::SmsReply) -> cosmic::app::Task<Message> {
@@ -498,6 +752,7 @@ impl Application for ConnectApplet {
     fn init(core: Core, _flags: Self::Flags) -> (Self, cosmic::app::Task<Self::Message>) {
         // Load config from disk or use defaults
         let config = Config::load();
+        kdeconnect_dbus::phone::set_default_region(config.phone_region());
 
         let app = ConnectApplet {
             core,
@@ -522,6 +777,12 @@ impl Application for ConnectApplet {
             media_info: None,
             media_loading: false,
             media_selected_player: None,
+            media_seek_preview: None,
+            media_now: std::time::Instant::now(),
+            call_log_device_id: None,
+            call_log_device_name: None,
+            call_log: None,
+            call_media_paused: false,
             // SendTo state
             sendto_device_id: None,
             sendto_device_type: None,
@@ -530,14 +791,29 @@ impl Application for ConnectApplet {
         };
 
         // Connect to D-Bus on startup
-        let task = cosmic::app::Task::perform(async { Connection::session().await }, |result| {
-            cosmic::Action::App(match result {
-                Ok(conn) => Message::DbusConnected(Arc::new(Mutex::new(conn))),
-                Err(e) => Message::DbusConnectionFailed(e.to_string()),
-            })
+        let connect_task =
+            cosmic::app::Task::perform(async { Connection::session().await }, |result| {
+                cosmic::Action::App(match result {
+                    Ok(conn) => Message::DbusConnected(Arc::new(Mutex::new(conn))),
+                    Err(e) => Message::DbusConnectionFailed(e.to_string()),
+                })
+            });
+        // Scheduled messages must be sent even if the SMS view is never opened
+        let scheduled_task = cosmic::app::Task::perform(load_scheduled_async(), |loaded| {
+            cosmic::Action::App(Message::ScheduledSmsLoaded(loaded))
+        });
+        let drafts_task = cosmic::app::Task::perform(load_drafts_async(), |drafts| {
+            cosmic::Action::App(Message::SmsDraftsLoaded(drafts))
+        });
+        // Mutes apply to notifications, so load these up front too
+        let flags_task = cosmic::app::Task::perform(load_flags_async(), |flags| {
+            cosmic::Action::App(Message::ConversationFlagsLoaded(flags))
         });
 
-        (app, task)
+        (
+            app,
+            cosmic::app::Task::batch([connect_task, scheduled_task, drafts_task, flags_task]),
+        )
     }
 
     fn on_close_requested(&self, id: window::Id) -> Option<Message> {
@@ -568,6 +844,8 @@ impl Application for ConnectApplet {
                 if self.popup == Some(id) {
                     self.popup = None;
                 }
+                // Don't leave the last few keystrokes to the flush timer
+                return self.sms.flush_drafts();
             }
             Message::DbusConnected(conn) => {
                 tracing::info!("D-Bus connection established");
@@ -713,31 +991,45 @@ impl Application for ConnectApplet {
             // Share
             Message::ShareFile(device_id) => {
                 self.pending_share_device = Some(device_id);
-                return cosmic::task::future(async {
-                    use cosmic::dialog::file_chooser;
-                    let result = file_chooser::open::Dialog::new()
-                        .title("Share File")
-                        .open_file()
-                        .await;
-                    match result {
-                        Ok(response) => Message::FileSelected(response.url().to_file_path().ok()),
-                        Err(_) => Message::FileSelected(None),
-                    }
+                return cosmic::task::future(async move {
+                    Message::FilesSelected(pick_share_paths_async(fl!("share-file"), false).await)
                 });
             }
-            Message::FileSelected(path) => {
-                if let (Some(conn), Some(device_id)) =
-                    (&self.dbus_connection, self.pending_share_device.take())
-                {
-                    if let Some(path) = path {
-                        self.status_message = Some("Sharing file...".to_string());
-                        return cosmic::app::Task::perform(
-                            share_file_async(conn.clone(), device_id, path),
-                            |result| cosmic::Action::App(Message::ShareComplete(result)),
-                        );
-                    }
+            Message::ShareFolder(device_id) => {
+                self.pending_share_device = Some(device_id);
+                return cosmic::task::future(async move {
+                    Message::FilesSelected(pick_share_paths_async(fl!("share-folder"), true).await)
+                });
+            }
+            Message::FilesSelected(paths) => {
+                if let Some(device_id) = self.pending_share_device.take() {
+                    return self.share_paths(device_id, paths);
                 }
             }
+            Message::DropFiles(device_id, paths) => {
+                return self.share_paths(device_id, paths);
+            }
+            Message::ShareBatchComplete(summary) => {
+                let status = match (summary.shared, summary.failed) {
+                    (shared, 0) => fl!("share-batch-complete", count = shared),
+                    (0, failed) => fl!(
+                        "share-batch-failed",
+                        count = failed,
+                        error = summary.last_error.unwrap_or_default()
+                    ),
+                    (shared, failed) => fl!(
+                        "share-batch-partial",
+                        shared = shared,
+                        total = shared + failed
+                    ),
+                };
+                tracing::info!(
+                    "Share batch finished: {} shared, {} failed",
+                    summary.shared,
+                    summary.failed
+                );
+                return self.set_transient_status(status);
+            }
             Message::ShareTextInput(text) => {
                 self.share_text_input = text;
             }
@@ -991,13 +1283,31 @@ impl Application for ConnectApplet {
                         self.config.call_notification_show_name =
                             !self.config.call_notification_show_name;
                     }
+                    SettingKey::CallHistory => {
+                        self.config.call_history = !self.config.call_history;
+                    }
+                    SettingKey::CallPauseMedia => {
+                        self.config.call_pause_media = !self.config.call_pause_media;
+                    }
+                    SettingKey::CallLowerVolume => {
+                        self.config.call_lower_volume = !self.config.call_lower_volume;
+                    }
+                    SettingKey::CallResumeAfterRing => {
+                        self.config.call_resume_after_ring = !self.config.call_resume_after_ring;
+                    }
                     SettingKey::FileNotifications => {
                         self.config.file_notifications = !self.config.file_notifications;
                     }
+                    SettingKey::ShareFoldersAsZip => {
+                        self.config.share_folders_as_zip = !self.config.share_folders_as_zip;
+                    }
                     SettingKey::MergeReactionThreads => {
                         self.config.merge_reaction_threads = !self.config.merge_reaction_threads;
                         self.sms.rederive_conversations(&self.config);
                     }
+                    SettingKey::ShowRawReactions => {
+                        self.config.show_raw_reactions = !self.config.show_raw_reactions;
+                    }
                 }
                 tracing::debug!("Settings updated");
                 // Save config to disk
@@ -1005,6 +1315,34 @@ impl Application for ConnectApplet {
                     tracing::error!(?err, "Failed to save config");
                 }
             }
+            Message::SetPhoneRegion(input) => {
+                self.config.phone_region = input
+                    .chars()
+                    .filter(char::is_ascii_alphabetic)
+                    .take(2)
+                    .collect::<String>()
+                    .to_ascii_uppercase();
+                if let Err(err) = self.config.save() {
+                    tracing::error!(?err, "Failed to save config");
+                }
+                let region = self.config.phone_region();
+                if region == kdeconnect_dbus::phone::default_region() {
+                    return cosmic::app::Task::none();
+                }
+                kdeconnect_dbus::phone::set_default_region(region);
+                // Merge keys and contact keys both depend on the region
+                self.sms.rederive_conversations(&self.config);
+                self.sms.set_contacts(ContactLookup::default());
+                if let Some(device_id) = self.sms.sms_device_id.clone() {
+                    return cosmic::app::Task::perform(
+                        async move {
+                            let contacts = ContactLookup::load_for_device(&device_id).await;
+                            Message::ContactsLoaded(device_id, contacts)
+                        },
+                        cosmic::Action::App,
+                    );
+                }
+            }
             Message::ToggleDeviceGroup(kind) => {
                 if kind == GroupKind::Offline {
                     self.config.group_offline_expanded = !self.config.group_offline_expanded;
@@ -1029,14 +1367,29 @@ impl Application for ConnectApplet {
                     let same_device = self.sms.sms_device_id.as_ref() == Some(&device_id);
                     let has_cache = same_device && !self.sms.conversations.is_empty();
 
+                    // Offline devices show the archive only; there's no daemon
+                    // data to subscribe to until the phone comes back.
+                    let live = self.is_device_reachable(&device_id);
+
+                    // Swap archives before sms_device_id moves on, so the old
+                    // device's unsaved changes are written under its own ID.
+                    let archive_task = if same_device {
+                        cosmic::app::Task::none()
+                    } else {
+                        self.sms.open_archive(&device_id)
+                    };
+
                     self.view_mode = ViewMode::ConversationList;
                     self.sms.sms_device_id = Some(device_id.clone());
                     self.sms.sms_device_name = device_name;
 
                     // Per-device caches; otherwise the prior device's raw entries bleed into the new list when its subscription re-derives.
                     if !same_device {
-                        self.sms.contacts = ContactLookup::default();
+                        self.sms.set_contacts(ContactLookup::default());
                         self.sms.raw_conversations.clear();
+                        self.sms.locally_read.clear();
+                        self.sms.refresh_known_sims();
+                        self.sms.contacts_signal_seen = false;
                     }
 
                     // Load contacts if not already loaded for this device
@@ -1054,6 +1407,7 @@ impl Application for ConnectApplet {
                     } else {
                         cosmic::app::Task::none()
                     };
+                    let contacts_task = cosmic::app::Task::batch([contacts_task, archive_task]);
 
                     // Check if we have prefetched conversations for this device
                     let has_prefetch = self
@@ -1065,8 +1419,8 @@ impl Application for ConnectApplet {
                     if has_cache {
                         // Use in-memory cached conversations, enable subscription for background refresh
                         self.sms.sms_loading_state = SmsLoadingState::Idle; // Show cached data immediately
-                        self.sms.conversation_sync_active = true; // Show sync indicator
-                        self.sms.conversation_list_subscription_active = true; // Enable subscription
+                        self.sms.conversation_sync_active = live; // Show sync indicator
+                        self.sms.conversation_list_subscription_active = live; // Enable subscription
                         tracing::info!(
                             "Using cached {} conversations for device: {}, starting subscription-based sync",
                             self.sms.conversations.len(),
@@ -1090,8 +1444,8 @@ impl Application for ConnectApplet {
                             self.sms.rederive_conversations(&self.config);
                             self.sms.conversations_displayed = 10;
                             self.sms.sms_loading_state = SmsLoadingState::Idle;
-                            self.sms.conversation_sync_active = true;
-                            self.sms.conversation_list_subscription_active = true;
+                            self.sms.conversation_sync_active = live;
+                            self.sms.conversation_list_subscription_active = live;
                             tracing::info!(
                                 "Using prefetched {} conversations for device: {}, starting subscription-based sync",
                                 self.sms.conversations.len(),
@@ -1101,11 +1455,12 @@ impl Application for ConnectApplet {
                         return contacts_task;
                     } else {
                         // No cache or different device - subscription-based loading
-                        // Conversations will arrive incrementally via signals
+                        // Conversations will arrive incrementally via signals;
+                        // the archive fills the list in the meantime
                         self.sms.sms_loading_state =
                             SmsLoadingState::LoadingConversations(LoadingPhase::Connecting);
-                        self.sms.conversation_sync_active = true;
-                        self.sms.conversation_list_subscription_active = true; // Enable subscription
+                        self.sms.conversation_sync_active = live;
+                        self.sms.conversation_list_subscription_active = live; // Enable subscription
                         self.sms.conversations.clear();
                         self.sms.conversations_displayed = 10;
                         tracing::info!(
@@ -1123,6 +1478,7 @@ impl Application for ConnectApplet {
                 // Keep sms_device_id, sms_device_name, conversations, contacts
                 // for when user returns to SMS view
                 self.sms.messages.clear();
+                self.sms.refresh_known_sims();
                 self.sms.current_thread_id = None;
                 self.sms.current_thread_addresses = None;
                 self.sms.current_merged_thread_ids.clear();
@@ -1132,6 +1488,9 @@ impl Application for ConnectApplet {
                 self.sms.sms_compose_text = widget::text_editor::Content::new();
                 self.sms.sms_sending = false;
                 self.sms.sms_sending_body = None;
+                self.sms.search_query.clear();
+                self.sms.export_menu_open = false;
+                self.sms.close_schedule_ui();
             }
             Message::OpenConversation(thread_id) => {
                 // Guard: need D-Bus connection and device ID for the subscription
@@ -1163,9 +1522,17 @@ impl Application for ConnectApplet {
                             .insert((device_id, thread_id), now_ms);
                     }
 
+                    self.sms.mark_read_locally(&merged_thread_ids, &self.config);
                     self.sms.current_thread_id = Some(thread_id);
                     self.sms.current_thread_addresses = addresses;
                     self.sms.current_merged_thread_ids = merged_thread_ids;
+                    self.sms.restore_thread_draft();
+                    self.sms.reset_selected_sim();
+                    self.sms.search_target_uid = None;
+                    self.sms.search_highlight_uid = None;
+                    self.sms.export_menu_open = false;
+                    self.sms.pending_attachments.clear();
+                    self.sms.close_schedule_ui();
                     self.view_mode = ViewMode::MessageThread;
 
                     // Reset pagination state
@@ -1174,9 +1541,36 @@ impl Application for ConnectApplet {
                     self.sms.scroll_offset_before_load = None;
                     self.sms.content_height_before_load = None;
 
-                    // Clear known message IDs for fresh deduplication
-                    self.sms.known_message_ids.clear();
-                    self.sms.messages.clear();
+                    // Seed from the archive so the thread renders immediately
+                    // (and offline); live messages dedup against these UIDs.
+                    self.sms.messages = self
+                        .sms
+                        .archive
+                        .messages_for(&self.sms.current_merged_thread_ids);
+                    self.sms.refresh_known_sims();
+                    self.sms.known_message_ids = self.sms.messages.iter().map(|m| m.uid).collect();
+
+                    let live = self
+                        .sms
+                        .sms_device_id
+                        .as_deref()
+                        .is_some_and(|id| self.is_device_reachable(id));
+                    if !live {
+                        // Nothing to subscribe to - the archive is all we have.
+                        self.sms.conversation_load_active = false;
+                        self.sms.initial_load_complete = true;
+                        self.sms.messages_has_more = false;
+                        self.sms.sms_loading_state = SmsLoadingState::Idle;
+                        self.sms.message_sync_active = false;
+                        tracing::info!(
+                            "Opening conversation thread: {} (offline, archive only)",
+                            thread_id
+                        );
+                        return scrollable::snap_to(
+                            widget::Id::new("message-thread"),
+                            scrollable::RelativeOffset::END,
+                        );
+                    }
 
                     // Set up subscription-based loading state
                     // The subscription will fire the D-Bus request after setting up match rules
@@ -1195,12 +1589,25 @@ impl Application for ConnectApplet {
                     // Subscription will fire D-Bus request and handle incoming signals
                 }
             }
+            Message::OpenSearchResult { thread_id, uid } => {
+                let task = self.update(Message::OpenConversation(thread_id));
+                // Set after OpenConversation, which resets any previous target.
+                self.sms.search_target_uid = uid;
+                return task;
+            }
             Message::CloseConversation => {
+                // Anything that arrived while the thread was open has been seen.
+                let read_thread_ids = self.sms.current_merged_thread_ids.clone();
+                self.sms.mark_read_locally(&read_thread_ids, &self.config);
                 self.view_mode = ViewMode::ConversationList;
+                self.sms.export_menu_open = false;
+                self.sms.pending_attachments.clear();
+                self.sms.close_schedule_ui();
                 self.sms.current_thread_id = None;
                 self.sms.current_thread_addresses = None;
                 self.sms.current_merged_thread_ids.clear();
                 self.sms.messages.clear();
+                self.sms.refresh_known_sims();
                 self.sms.sms_compose_text = widget::text_editor::Content::new();
                 self.sms.sms_sending = false;
                 self.sms.sms_sending_body = None;
@@ -1237,19 +1644,50 @@ impl Application for ConnectApplet {
             // New message
             Message::OpenNewMessage => {
                 self.view_mode = ViewMode::NewMessage;
-                self.sms.new_message_recipients.clear();
+                self.sms.restore_new_message_draft();
+                self.sms.reset_selected_sim();
                 self.sms.new_message_recipient_input.clear();
-                self.sms.new_message_body = widget::text_editor::Content::new();
                 self.sms.new_message_sending = false;
+                self.sms.pending_attachments.clear();
+                self.sms.close_schedule_ui();
                 self.sms.contact_suggestions.clear();
                 return widget::text_input::focus(widget::Id::new("new-message-recipient"));
             }
+            Message::MessageContact(name, phone) => {
+                let task = self.update(Message::OpenNewMessage);
+                self.sms.start_message_to(name, phone);
+                return task;
+            }
+            Message::OpenContactsView => {
+                self.view_mode = ViewMode::Contacts;
+                self.sms.contacts_query.clear();
+                self.sms.refresh_contact_sections();
+            }
+            Message::CloseContactsView => {
+                self.view_mode = ViewMode::ConversationList;
+            }
             Message::CloseNewMessage => {
                 self.view_mode = ViewMode::ConversationList;
                 self.sms.new_message_recipients.clear();
                 self.sms.new_message_recipient_input.clear();
                 self.sms.new_message_body = widget::text_editor::Content::new();
                 self.sms.new_message_sending = false;
+                self.sms.pending_attachments.clear();
+                self.sms.close_schedule_ui();
+            }
+
+            // Scheduled messages go out on whichever reachable device they
+            // belong to, independent of which view is open.
+            Message::ScheduledSmsTick => {
+                if self.dbus_connection.is_some() {
+                    let reachable: Vec<String> = self
+                        .devices
+                        .iter()
+                        .filter(|d| d.is_reachable && d.is_paired)
+                        .map(|d| d.id.clone())
+                        .collect();
+                    return self.sms.claim_due_scheduled(&reachable);
+                }
             }
 
             // Attachment messages
@@ -1268,6 +1706,7 @@ impl Application for ConnectApplet {
                 self.media_info = None;
                 self.media_loading = true;
                 self.media_selected_player = None;
+                self.media_seek_preview = None;
                 self.view_mode = ViewMode::MediaControls;
 
                 if let Some(conn) = &self.dbus_connection {
@@ -1284,9 +1723,11 @@ impl Application for ConnectApplet {
                 self.media_info = None;
                 self.media_loading = false;
                 self.media_selected_player = None;
+                self.media_seek_preview = None;
             }
             Message::MediaInfoLoaded(info) => {
                 self.media_loading = false;
+                self.media_now = std::time::Instant::now();
                 // Preserve user's explicit player selection if set
                 self.media_info = match (info, &self.media_selected_player) {
                     (Some(mut media_info), Some(selected)) => {
@@ -1387,6 +1828,54 @@ impl Application for ConnectApplet {
                     );
                 }
             }
+            Message::MediaToggleShuffle => {
+                if let (Some(conn), Some(device_id), Some(info)) = (
+                    &self.dbus_connection,
+                    &self.media_device_id,
+                    self.media_info.as_mut(),
+                ) {
+                    let caps = &mut info.capabilities;
+                    if let (Some(service), Some(shuffle)) = (caps.service.clone(), caps.shuffle) {
+                        // Update local state immediately for responsive UI
+                        caps.shuffle = Some(!shuffle);
+                        return cosmic::app::Task::perform(
+                            media_action_async(
+                                conn.clone(),
+                                device_id.clone(),
+                                MediaAction::SetShuffle {
+                                    service,
+                                    shuffle: !shuffle,
+                                },
+                                None,
+                            ),
+                            cosmic::Action::App,
+                        );
+                    }
+                }
+            }
+            Message::MediaCycleLoop => {
+                if let (Some(conn), Some(device_id), Some(info)) = (
+                    &self.dbus_connection,
+                    &self.media_device_id,
+                    self.media_info.as_mut(),
+                ) {
+                    let caps = &mut info.capabilities;
+                    if let (Some(service), Some(status)) = (caps.service.clone(), caps.loop_status)
+                    {
+                        let status = status.next();
+                        caps.loop_status = Some(status);
+                        return cosmic::app::Task::perform(
+                            media_action_async(
+                                conn.clone(),
+                                device_id.clone(),
+                                MediaAction::SetLoopStatus { service, status },
+                                None,
+                            ),
+                            cosmic::Action::App,
+                        );
+                    }
+                }
+            }
             Message::MediaActionResult(result) => {
                 let clear_task = if let Err(err) = result {
                     self.set_transient_status(format!("Media error: {}", err))
@@ -1420,57 +1909,138 @@ impl Application for ConnectApplet {
                     }
                 }
             }
-
+            Message::MediaSeekPreview(position) => {
+                if self.media_info.as_ref().is_some_and(|info| info.can_seek) {
+                    self.media_seek_preview = Some(position as i64);
+                }
+            }
+            Message::MediaSeekRelease => {
+                if let (Some(position), Some(conn), Some(device_id)) = (
+                    self.media_seek_preview.take(),
+                    &self.dbus_connection,
+                    &self.media_device_id,
+                ) {
+                    // Jump locally so the bar doesn't snap back before the refresh
+                    let now = std::time::Instant::now();
+                    self.media_now = now;
+                    if let Some(ref mut info) = self.media_info {
+                        info.position = position;
+                        info.position_updated = now;
+                    }
+                    let ensure_player = self.media_selected_player.clone();
+                    return cosmic::app::Task::perform(
+                        media_action_async(
+                            conn.clone(),
+                            device_id.clone(),
+                            MediaAction::SetPosition(position as i32),
+                            ensure_player,
+                        ),
+                        cosmic::Action::App,
+                    );
+                }
+            }
+            Message::MediaPositionTick(now) => {
+                self.media_now = now;
+            }
             // SMS Notifications
 
             // Call Notifications
             Message::CallNotification {
+                device_id,
                 device_name,
                 event,
                 phone_number,
                 contact_name,
             } => {
-                // Build notification based on event type and privacy settings
-                let (summary, icon, urgency, timeout_ms) = match event.as_str() {
-                    "callReceived" => {
-                        let text = if self.config.call_notification_show_name
-                            && !contact_name.is_empty()
-                            && contact_name != phone_number
-                        {
-                            fl!("incoming-call-from", name = contact_name.clone())
-                        } else if self.config.call_notification_show_number {
-                            fl!("incoming-call-from", name = phone_number.clone())
-                        } else {
-                            fl!("incoming-call")
-                        };
-                        (
-                            text,
-                            "call-start-symbolic",
-                            notify_rust::Urgency::Critical,
-                            CALL_RING_TIMEOUT_MS,
-                        )
-                    }
-                    "missedCall" => {
-                        let text = if self.config.call_notification_show_name
-                            && !contact_name.is_empty()
-                            && contact_name != phone_number
-                        {
-                            fl!("missed-call-from", name = contact_name.clone())
-                        } else if self.config.call_notification_show_number {
-                            fl!("missed-call-from", name = phone_number.clone())
-                        } else {
-                            fl!("missed-call")
-                        };
-                        (
-                            text,
-                            "call-missed-symbolic",
-                            notify_rust::Urgency::Normal,
-                            NORMAL_NOTIFICATION_TIMEOUT_MS,
+                let call_key = format!("{}:{}", device_id, phone_key(&phone_number));
+                let record_task = if self.config.call_history {
+                    cosmic::app::Task::perform(
+                        record_call_async(
+                            device_id.clone(),
+                            event.clone(),
+                            phone_number.clone(),
+                            contact_name.clone(),
+                        ),
+                        move |result| cosmic::Action::App(Message::CallRecorded(device_id, result)),
+                    )
+                } else {
+                    cosmic::app::Task::none()
+                };
+
+                // Pause local media while the phone rings. The daemon reports
+                // nothing when a call is answered or hung up, so media resumes
+                // on a missed call or when the user resumes it, unless the
+                // timed resume after the ring window is turned on. Resuming
+                // doesn't check the setting, so turning it off mid-ring still
+                // restores what was paused.
+                let media_task = match (&self.dbus_connection, event.as_str()) {
+                    (Some(conn), "callReceived") if self.config.call_pause_media => {
+                        let conn = conn.clone();
+                        let lower_volume = self.config.call_lower_volume;
+                        let resume_after_ring = self.config.call_resume_after_ring;
+                        cosmic::app::Task::perform(
+                            async move {
+                                let paused =
+                                    pause_media_for_call_async(conn.clone(), lower_volume).await;
+                                if !(paused && resume_after_ring) {
+                                    return paused;
+                                }
+                                tokio::time::sleep(std::time::Duration::from_millis(u64::from(
+                                    CALL_RING_TIMEOUT_MS,
+                                )))
+                                .await;
+                                resume_media_after_call_async(conn, true).await
+                            },
+                            |paused| cosmic::Action::App(Message::CallMediaPaused(paused)),
                         )
                     }
+                    (Some(conn), "missedCall") => cosmic::app::Task::perform(
+                        resume_media_after_call_async(conn.clone(), false),
+                        |paused| cosmic::Action::App(Message::CallMediaPaused(paused)),
+                    ),
+                    _ => cosmic::app::Task::none(),
+                };
+                let call_task = cosmic::app::Task::batch([record_task, media_task]);
+                if !self.config.call_notifications {
+                    return call_task;
+                }
+
+                // Name shown in the toast, subject to the privacy settings
+                let caller = if self.config.call_notification_show_name
+                    && !contact_name.is_empty()
+                    && contact_name != phone_number
+                {
+                    Some(contact_name.clone())
+                } else if self.config.call_notification_show_number {
+                    Some(phone_number.clone())
+                } else {
+                    None
+                };
+
+                // Build notification based on event type
+                let ringing = event == "callReceived";
+                let (summary, icon, urgency, timeout_ms) = match event.as_str() {
+                    "callReceived" => (
+                        match caller {
+                            Some(name) => fl!("incoming-call-from", name = name),
+                            None => fl!("incoming-call"),
+                        },
+                        "call-start-symbolic",
+                        notify_rust::Urgency::Critical,
+                        CALL_RING_TIMEOUT_MS,
+                    ),
+                    "missedCall" => (
+                        match caller {
+                            Some(name) => fl!("missed-call-from", name = name),
+                            None => fl!("missed-call"),
+                        },
+                        "call-missed-symbolic",
+                        notify_rust::Urgency::Normal,
+                        NORMAL_NOTIFICATION_TIMEOUT_MS,
+                    ),
                     _ => {
-                        tracing::debug!("Unknown call event type: {}", event);
-                        return cosmic::app::Task::none();
+                        tracing::debug!("No notification for call event type: {}", event);
+                        return call_task;
                     }
                 };
 
@@ -1481,9 +2051,17 @@ impl Application for ConnectApplet {
                     device_name
                 );
 
-                // Show notification
-                return cosmic::app::Task::perform(
+                // The ringing toast's id is shared across applet processes
+                // because the missed-call event may be delivered to a different one.
+                let notify_task = cosmic::app::Task::perform(
                     async move {
+                        // A repeated ring updates the toast in place; a missed
+                        // call replaces it.
+                        let replaces = if ringing {
+                            ringing_call_toast(&call_key)
+                        } else {
+                            take_call_toast(&call_key)
+                        };
                         let mut notification = notify_rust::Notification::new();
                         notification
                             .summary(&summary)
@@ -1492,14 +2070,79 @@ impl Application for ConnectApplet {
                             .appname("Connected")
                             .urgency(urgency)
                             .timeout(notify_rust::Timeout::Milliseconds(timeout_ms));
+                        if let Some(id) = replaces {
+                            notification.id(id);
+                        }
                         match tokio::task::spawn_blocking(move || notification.show()).await {
-                            Ok(Ok(_handle)) => tracing::debug!("Call notification shown"),
+                            Ok(Ok(handle)) => {
+                                if ringing {
+                                    remember_call_toast(&call_key, handle.id());
+                                }
+                                tracing::debug!("Call notification shown");
+                            }
                             Ok(Err(e)) => tracing::warn!("Failed to show call notification: {}", e),
                             Err(e) => tracing::warn!("Call notification task panicked: {}", e),
                         }
                     },
                     |_| cosmic::Action::App(Message::RefreshDevices),
                 );
+                return cosmic::app::Task::batch([call_task, notify_task]);
+            }
+            Message::CallRecorded(device_id, result) => match result {
+                Ok(log) => {
+                    if self.call_log_device_id.as_deref() == Some(device_id.as_str()) {
+                        self.call_log = Some(log);
+                    }
+                }
+                Err(err) => tracing::warn!("Failed to save call log: {}", err),
+            },
+            Message::CallMediaPaused(paused) => {
+                self.call_media_paused = paused;
+            }
+            Message::ResumeCallMedia => {
+                self.call_media_paused = false;
+                if let Some(conn) = self.dbus_connection.clone() {
+                    return cosmic::app::Task::perform(
+                        resume_media_after_call_async(conn, false),
+                        |paused| cosmic::Action::App(Message::CallMediaPaused(paused)),
+                    );
+                }
+            }
+
+            // Call history
+            Message::OpenCallHistory(device_id) => {
+                self.call_log_device_name = self
+                    .devices
+                    .iter()
+                    .find(|d| d.id == device_id)
+                    .map(|d| d.name.clone());
+                self.call_log_device_id = Some(device_id.clone());
+                self.call_log = None;
+                self.view_mode = ViewMode::CallHistory;
+                return cosmic::app::Task::perform(
+                    load_call_log_async(device_id.clone()),
+                    move |log| cosmic::Action::App(Message::CallLogLoaded(device_id, log)),
+                );
+            }
+            Message::CloseCallHistory => {
+                self.view_mode = ViewMode::DevicePage;
+                self.call_log_device_id = None;
+                self.call_log_device_name = None;
+                self.call_log = None;
+            }
+            Message::CallLogLoaded(device_id, log) => {
+                if self.call_log_device_id.as_deref() == Some(device_id.as_str()) {
+                    self.call_log = Some(log);
+                }
+            }
+            Message::CallHistorySendSms {
+                device_id,
+                name,
+                phone_number,
+            } => {
+                let open_task = self.update(Message::OpenSmsView(device_id));
+                let compose_task = self.update(Message::MessageContact(name, phone_number));
+                return cosmic::app::Task::batch([open_task, compose_task]);
             }
 
             // File Notifications
@@ -1558,10 +2201,26 @@ impl Application for ConnectApplet {
             Message::SmsPrefetchReady(_, _)
             | Message::ConversationsLoaded(_)
             | Message::ContactsLoaded(_, _)
+            | Message::CheckContactsChanged
+            | Message::ContactsCacheSynchronized
+            | Message::SyncContacts
+            | Message::SyncContactsResult(_)
             | Message::ConversationReceived { .. }
             | Message::ConversationSyncStarted { .. }
             | Message::ConversationSyncComplete { .. }
             | Message::LoadMoreConversations
+            | Message::SmsSearchInput(_)
+            | Message::ContactsSearchInput(_)
+            | Message::SmsArchiveLoaded(_, _)
+            | Message::SmsArchiveFlush
+            | Message::SmsArchiveSaved(_)
+            | Message::SmsDraftsLoaded(_)
+            | Message::SmsDraftsFlush
+            | Message::SmsDraftsSaved(_)
+            | Message::ToggleSmsExportMenu
+            | Message::ExportSms(_)
+            | Message::SmsExportThreadsLoaded(..)
+            | Message::SmsExportComplete(..)
             | Message::OlderMessagesLoaded(..)
             | Message::MessageThreadScrolled(_)
             | Message::BubblePressStarted { .. }
@@ -1576,6 +2235,28 @@ impl Application for ConnectApplet {
             | Message::SmsComposeAction(_)
             | Message::SendSms
             | Message::SmsSendResult(_)
+            | Message::RetrySms(_)
+            | Message::RetrySmsResult(..)
+            | Message::PickSmsAttachments
+            | Message::SmsAttachmentsPicked(_)
+            | Message::RemoveSmsAttachment(_)
+            | Message::ToggleConversationFlag(_)
+            | Message::ToggleArchivedSection
+            | Message::ConversationFlagsLoaded(_)
+            | Message::ConversationFlagsSaved(_)
+            | Message::SelectSim(_)
+            | Message::ToggleSchedulePicker
+            | Message::ScheduleTimeInput(_)
+            | Message::ScheduleSms(_)
+            | Message::CancelScheduledSms(_)
+            | Message::RetryScheduledSms(_)
+            | Message::EditScheduledSms(_)
+            | Message::ScheduledEditInput(_)
+            | Message::SaveScheduledEdit
+            | Message::ScheduledSmsLoaded(_)
+            | Message::ScheduledSmsClaimed(..)
+            | Message::ScheduledSmsSent(..)
+            | Message::ScheduledSmsSaved(_)
             | Message::NewMessageRecipientInput(_)
             | Message::NewMessageBodyAction(_)
             | Message::AddManualRecipient
@@ -1608,11 +2289,28 @@ impl Application for ConnectApplet {
             "io.github.nwxnw.cosmic-ext-connected-disconnected-symbolic"
         };
 
-        self.core
+        let button = self
+            .core
             .applet
             .icon_button(icon_name)
-            .on_press(Message::TogglePopup)
-            .into()
+            .on_press(Message::TogglePopup);
+
+        // Unread SMS count next to the icon
+        let unread = self.sms.total_unread();
+        if unread == 0 {
+            return button.into();
+        }
+        let label = if unread > 99 {
+            "99+".to_string()
+        } else {
+            unread.to_string()
+        };
+        widget::mouse_area(
+            cosmic::iced::widget::row![button, self.core.applet.text(label)]
+                .align_y(Alignment::Center),
+        )
+        .on_press(Message::TogglePopup)
+        .into()
     }
 
     fn view_window(&self, _id: window::Id) -> Element<'_, Self::Message> {
@@ -1662,10 +2360,25 @@ impl Application for ConnectApplet {
                 &self.config,
                 self.status_message.as_deref(),
             ),
+            ViewMode::Contacts => self.sms.view(
+                SmsViewMode::Contacts,
+                &self.config,
+                self.status_message.as_deref(),
+            ),
+            ViewMode::CallHistory => view_call_history(CallHistoryParams {
+                device_id: self.call_log_device_id.as_deref().unwrap_or_default(),
+                device_name: self.call_log_device_name.as_deref(),
+                call_log: self.call_log.as_ref(),
+            }),
             ViewMode::MediaControls => view_media_controls(MediaControlsParams {
                 device_name: self.media_device_name.as_deref(),
                 media_info: self.media_info.as_ref(),
                 media_loading: self.media_loading,
+                position: self.media_seek_preview.or_else(|| {
+                    self.media_info
+                        .as_ref()
+                        .map(|info| info.position_at(self.media_now))
+                }),
             }),
             ViewMode::SendTo => view_send_to(SendToParams {
                 device_type: self.sendto_device_type.as_deref().unwrap_or("device"),
@@ -1688,6 +2401,7 @@ impl Application for ConnectApplet {
                             &self.devices,
                             &self.config,
                             self.status_message.as_deref(),
+                            self.call_media_paused,
                         )
                     }
                 } else {
@@ -1695,6 +2409,7 @@ impl Application for ConnectApplet {
                         &self.devices,
                         &self.config,
                         self.status_message.as_deref(),
+                        self.call_media_paused,
                     )
                 }
             }
@@ -1717,6 +2432,7 @@ impl Application for ConnectApplet {
                         &self.devices,
                         &self.config,
                         self.status_message.as_deref(),
+                        self.call_media_paused,
                     )
                 }
             }
@@ -1745,14 +2461,28 @@ impl Application for ConnectApplet {
                 .map(|_| Message::CheckPendingRefresh),
         ];
 
-        // Add media refresh timer when in media view
+        // Add media signal subscription and fallback refresh timer when in media view
         if self.view_mode == ViewMode::MediaControls {
             subscriptions.push(
                 cosmic::iced::time::every(std::time::Duration::from_secs(
-                    refresh::MEDIA_INTERVAL_SECS,
+                    refresh::MEDIA_FALLBACK_INTERVAL_SECS,
                 ))
                 .map(|_| Message::MediaRefresh),
             );
+            if let Some(device_id) = self.media_device_id.clone() {
+                subscriptions.push(Subscription::run_with(
+                    ("media_signals", device_id),
+                    |(_, device_id)| media_signal_subscription(device_id.clone()),
+                ));
+            }
+            if self.media_info.as_ref().is_some_and(|info| info.is_playing) {
+                subscriptions.push(
+                    cosmic::iced::time::every(std::time::Duration::from_millis(
+                        refresh::MEDIA_POSITION_TICK_MS,
+                    ))
+                    .map(Message::MediaPositionTick),
+                );
+            }
         }
 
         // Add SMS notification subscription when enabled and devices are connected
@@ -1762,8 +2492,11 @@ impl Application for ConnectApplet {
             subscriptions.push(Subscription::run(sms_notification_subscription));
         }
 
-        // Add call notification subscription when enabled and devices are connected
-        if self.config.call_notifications
+        // Add call subscription when call notifications, history or media
+        // pausing are enabled and devices are connected
+        if (self.config.call_notifications
+            || self.config.call_history
+            || self.config.call_pause_media)
             && self.devices.iter().any(|d| d.is_reachable && d.is_paired)
         {
             subscriptions.push(Subscription::run(call_notification_subscription));
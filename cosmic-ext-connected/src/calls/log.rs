@@ -0,0 +1,198 @@
+//! Per-device call log.
+//!
+//! KDE Connect only signals calls as they happen; it keeps no history.
+//! Each `callReceived` signal is recorded here and written to
+//! `$XDG_DATA_HOME/<APP_ID>/calls/<device-id>.json`, newest first. A ring
+//! followed by a "missedCall" event for the same number updates the ring's
+//! entry rather than adding a second one. The daemon reports nothing when a
+//! call is answered, so answered calls stay "incoming".
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::APP_ID;
+use crate::constants::calls::{CALL_EVENT_MERGE_WINDOW_MS, MAX_CALL_LOG_ENTRIES};
+use crate::storage::{file_name_for, load_json, update_json_async};
+use kdeconnect_dbus::phone_key;
+
+/// How a call ended up, as far as the phone reported it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallKind {
+    /// Rang; no miss reported (yet).
+    Incoming,
+    /// Rang out unanswered.
+    Missed,
+}
+
+impl CallKind {
+    /// Kind for a telephony event name, `None` for events that aren't calls.
+    fn from_event(event: &str) -> Option<Self> {
+        match event {
+            "callReceived" => Some(Self::Incoming),
+            "missedCall" => Some(Self::Missed),
+            _ => None,
+        }
+    }
+}
+
+/// One call in the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallEntry {
+    pub phone_number: String,
+    /// Name the phone resolved for the caller; empty if unknown.
+    #[serde(default)]
+    pub contact_name: String,
+    /// Unix millis when the call was first seen.
+    pub timestamp: i64,
+    pub kind: CallKind,
+}
+
+impl CallEntry {
+    /// Contact name if the phone sent a real one, else the number.
+    pub fn display_name(&self) -> &str {
+        let name = self.contact_name.trim();
+        if name.is_empty() || name == self.phone_number {
+            &self.phone_number
+        } else {
+            name
+        }
+    }
+}
+
+/// A device's call log, newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallLog {
+    #[serde(default)]
+    pub entries: Vec<CallEntry>,
+}
+
+impl CallLog {
+    /// Record a telephony event at `now_ms`. Returns whether the log
+    /// changed; unknown events and repeated signals for the same call are
+    /// ignored.
+    pub fn record(
+        &mut self,
+        event: &str,
+        phone_number: &str,
+        contact_name: &str,
+        now_ms: i64,
+    ) -> bool {
+        let Some(kind) = CallKind::from_event(event) else {
+            return false;
+        };
+        let key = phone_key(phone_number);
+        let is_recent = |e: &CallEntry, kind: CallKind| {
+            e.kind == kind
+                && phone_key(&e.phone_number) == key
+                && now_ms - e.timestamp <= CALL_EVENT_MERGE_WINDOW_MS
+        };
+        // Every applet process records the same signals into the file
+        let missed_already = self.entries.iter().any(|e| is_recent(e, CallKind::Missed));
+        let recent_ring = self
+            .entries
+            .iter_mut()
+            .find(|e| is_recent(e, CallKind::Incoming));
+        match (kind, recent_ring) {
+            // Still the same ring
+            (CallKind::Incoming, Some(_)) => false,
+            // The same miss
+            (CallKind::Missed, None) if missed_already => false,
+            (_, Some(entry)) => {
+                entry.kind = kind;
+                if entry.contact_name.is_empty() {
+                    entry.contact_name = contact_name.to_string();
+                }
+                true
+            }
+            (_, None) => {
+                self.entries.insert(
+                    0,
+                    CallEntry {
+                        phone_number: phone_number.to_string(),
+                        contact_name: contact_name.to_string(),
+                        timestamp: now_ms,
+                        kind,
+                    },
+                );
+                self.entries.truncate(MAX_CALL_LOG_ENTRIES);
+                true
+            }
+        }
+    }
+}
+
+fn call_log_path(device_id: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| {
+        dir.join(APP_ID)
+            .join("calls")
+            .join(format!("{}.json", file_name_for(device_id)))
+    })
+}
+
+/// Load a device's call log. A missing or unreadable file yields an empty log.
+pub async fn load_call_log_async(device_id: String) -> CallLog {
+    match call_log_path(&device_id) {
+        Some(path) => load_json(&path, "call log").await,
+        None => CallLog::default(),
+    }
+}
+
+/// Record a telephony event with a locked read-modify-write of the
+/// device's log file ([`update_json`](crate::storage::update_json)) and
+/// return the log as written, including calls other applet processes
+/// recorded.
+pub async fn record_call_async(
+    device_id: String,
+    event: String,
+    phone_number: String,
+    contact_name: String,
+) -> Result<CallLog, String> {
+    let path = call_log_path(&device_id).ok_or("No XDG data directory")?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    update_json_async(path, "call log", move |log: &mut CallLog| {
+        log.record(&event, &phone_number, &contact_name, now_ms)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_then_miss_is_one_missed_call() {
+        let mut log = CallLog::default();
+        assert!(log.record("callReceived", "+1 555 123 4567", "Anna", 1_000));
+        // Repeated ring signal for the same call
+        assert!(!log.record("callReceived", "5551234567", "Anna", 2_000));
+        assert!(log.record("missedCall", "(555) 123-4567", "Anna", 20_000));
+        // Another applet process recording the same miss
+        assert!(!log.record("missedCall", "5551234567", "Anna", 20_000));
+        assert_eq!(log.entries.len(), 1);
+        assert_eq!(log.entries[0].kind, CallKind::Missed);
+        assert_eq!(log.entries[0].timestamp, 1_000);
+    }
+
+    #[test]
+    fn separate_calls() {
+        let mut log = CallLog::default();
+        log.record("callReceived", "5551234567", "", 0);
+        // Events the daemon doesn't emit are ignored
+        assert!(!log.record("talking", "5551234567", "Anna", 5_000));
+        assert_eq!(log.entries[0].kind, CallKind::Incoming);
+
+        // A later ring from the same number is a new call
+        let later = CALL_EVENT_MERGE_WINDOW_MS + 10_000;
+        log.record("callReceived", "5551234567", "", later);
+        assert_eq!(log.entries.len(), 2);
+        assert_eq!(log.entries[0].kind, CallKind::Incoming);
+        assert_eq!(log.entries[0].display_name(), "5551234567");
+
+        // A miss with no ring seen is still logged
+        log.record("missedCall", "5559876543", "Bo", later);
+        assert_eq!(log.entries.len(), 3);
+        assert!(!log.record("sms", "5559876543", "Bo", later));
+    }
+}
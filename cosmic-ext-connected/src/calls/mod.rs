@@ -0,0 +1,10 @@
+//! Call history recorded from KDE Connect telephony signals, and pausing
+//! local media during calls.
+
+pub mod log;
+pub mod pause;
+pub mod views;
+
+pub use log::*;
+pub use pause::{pause_media_for_call_async, resume_media_after_call_async};
+pub use views::*;
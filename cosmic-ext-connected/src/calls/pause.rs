@@ -0,0 +1,405 @@
+//! Pausing local media players while the phone rings.
+//!
+//! Players are found through MPRIS on the session bus. Only players that were
+//! playing when the call came in are touched, and they are remembered so that
+//! exactly those are resumed (or have their volume restored) afterwards.
+//!
+//! KDE Connect's `callReceived` signal only reports a ringing or a missed
+//! call; the daemon emits nothing when a call is answered or hung up. Media is
+//! therefore resumed on a missed call or when the user resumes it from the
+//! applet. Resuming once the ring window has passed is opt-in
+//! (`call_resume_after_ring`), since it also resumes during an answered call.
+
+use crate::constants::calls::{CALL_MEDIA_VOLUME_FACTOR, CALL_PAUSE_EXPIRY_MS};
+use crate::constants::notifications::CALL_RING_TIMEOUT_MS;
+use kdeconnect_dbus::plugins::REMOTE_PLAYER_PREFIX;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, Write};
+use std::os::unix::io::AsRawFd;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use zbus::{proxy, Connection};
+
+/// Bus name prefix shared by all MPRIS players.
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+/// File listing the players paused for the ringing call. Shared across
+/// applet processes because the missed-call event may be delivered to a
+/// different one than the ring.
+const PAUSED_PLAYERS_PATH: &str = "/tmp/cosmic-connected-call-paused-players";
+
+/// Player control interface of a local MPRIS player.
+#[proxy(
+    interface = "org.mpris.MediaPlayer2.Player",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait LocalPlayer {
+    fn play(&self) -> zbus::Result<()>;
+    fn pause(&self) -> zbus::Result<()>;
+    #[zbus(property)]
+    fn playback_status(&self) -> zbus::Result<String>;
+    #[zbus(property)]
+    fn volume(&self) -> zbus::Result<f64>;
+    #[zbus(property)]
+    fn set_volume(&self, volume: f64) -> zbus::Result<()>;
+}
+
+/// How a player was changed for a call, and so how to undo it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Restore {
+    /// The player was paused; resume playback.
+    Play,
+    /// The player's volume was lowered; set it back to this value.
+    Volume(f64),
+}
+
+/// A local player changed for the current call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PausedPlayer {
+    pub bus_name: String,
+    pub restore: Restore,
+}
+
+/// Local MPRIS players currently on the bus. The daemon's players for phone
+/// media sessions are left out: the phone handles its own media during calls.
+async fn list_players(conn: &Connection) -> zbus::Result<Vec<String>> {
+    let dbus = zbus::fdo::DBusProxy::new(conn).await?;
+    Ok(dbus
+        .list_names()
+        .await?
+        .into_iter()
+        .map(|name| name.to_string())
+        .filter(|name| name.starts_with(MPRIS_PREFIX) && !name.starts_with(REMOTE_PLAYER_PREFIX))
+        .collect())
+}
+
+async fn player<'a>(conn: &Connection, bus_name: &'a str) -> zbus::Result<LocalPlayerProxy<'a>> {
+    LocalPlayerProxy::builder(conn)
+        .destination(bus_name)?
+        .cache_properties(zbus::proxy::CacheProperties::No)
+        .build()
+        .await
+}
+
+/// Pause (or turn down) every playing local player, returning the ones that
+/// were changed. Players in `skip` were already changed for this call; a
+/// lowered player still reports "Playing" and must not be lowered again.
+/// Players that fail to respond are skipped.
+pub async fn pause_players(
+    conn: &Connection,
+    lower_volume: bool,
+    skip: &[String],
+) -> Vec<PausedPlayer> {
+    let names = match list_players(conn).await {
+        Ok(names) => names,
+        Err(e) => {
+            tracing::warn!("Failed to list media players: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut paused = Vec::new();
+    for bus_name in names.into_iter().filter(|name| !skip.contains(name)) {
+        let result = async {
+            let proxy = player(conn, &bus_name).await?;
+            if proxy.playback_status().await? != "Playing" {
+                return Ok(None);
+            }
+            let restore = if lower_volume {
+                let volume = proxy.volume().await?;
+                proxy.set_volume(volume * CALL_MEDIA_VOLUME_FACTOR).await?;
+                Restore::Volume(volume)
+            } else {
+                proxy.pause().await?;
+                Restore::Play
+            };
+            Ok::<_, zbus::Error>(Some(restore))
+        }
+        .await;
+        match result {
+            Ok(Some(restore)) => {
+                tracing::debug!("Paused {} for call", bus_name);
+                paused.push(PausedPlayer { bus_name, restore });
+            }
+            Ok(None) => {}
+            Err(e) => tracing::debug!("Skipping media player {}: {}", bus_name, e),
+        }
+    }
+    paused
+}
+
+/// Undo [`pause_players`]. A paused player is only resumed if it is still
+/// paused, so one the user stopped or restarted during the call is left alone.
+pub async fn resume_players(conn: &Connection, players: &[PausedPlayer]) {
+    for paused in players {
+        let result = async {
+            let proxy = player(conn, &paused.bus_name).await?;
+            match paused.restore {
+                Restore::Play => {
+                    if proxy.playback_status().await? == "Paused" {
+                        proxy.play().await?;
+                    }
+                }
+                Restore::Volume(volume) => proxy.set_volume(volume).await?,
+            }
+            Ok::<_, zbus::Error>(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::debug!("Failed to resume media player {}: {}", paused.bus_name, e);
+        }
+    }
+}
+
+/// Players paused for the ringing call, as saved in [`PAUSED_PLAYERS_PATH`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PausedCall {
+    /// When the phone last reported the call ringing (ms since epoch).
+    last_ring_ms: i64,
+    players: Vec<PausedPlayer>,
+}
+
+impl PausedCall {
+    /// A list left behind by an applet that exited mid-ring. Its players are
+    /// forgotten rather than resumed by some unrelated call much later.
+    fn is_expired(&self, now_ms: i64) -> bool {
+        now_ms - self.last_ring_ms > CALL_PAUSE_EXPIRY_MS
+    }
+
+    /// Whether the ring that paused these players has had time to end. Only
+    /// meaningful as an end of the call if the call wasn't answered.
+    fn ring_over(&self, now_ms: i64) -> bool {
+        now_ms - self.last_ring_ms >= i64::from(CALL_RING_TIMEOUT_MS)
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Read-modify-write the saved list under an exclusive file lock. The call's
+/// events may reach different applet processes, which would otherwise race
+/// on the file. Expired lists are dropped before `f` sees them.
+fn update_paused<R>(path: &str, f: impl FnOnce(&mut PausedCall, i64) -> R) -> Option<R> {
+    let now_ms = now_ms();
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| tracing::warn!("Failed to open paused media players: {}", e))
+        .ok()?;
+
+    let fd = file.as_raw_fd();
+    // SAFETY: flock is a standard POSIX system call that operates on valid file descriptors
+    unsafe {
+        if libc::flock(fd, libc::LOCK_EX) != 0 {
+            return None;
+        }
+    }
+
+    let mut contents = String::new();
+    let _ = file.read_to_string(&mut contents);
+    let mut paused: PausedCall = serde_json::from_str(&contents).unwrap_or_default();
+    if paused.is_expired(now_ms) && !paused.players.is_empty() {
+        tracing::debug!("Forgetting media players paused for an expired call");
+        paused = PausedCall::default();
+    }
+
+    let result = f(&mut paused, now_ms);
+
+    let _ = file.set_len(0);
+    let _ = file.rewind();
+    if !paused.players.is_empty() {
+        if let Ok(json) = serde_json::to_string(&paused) {
+            let _ = file.write_all(json.as_bytes());
+        }
+    }
+
+    // SAFETY: flock is a standard POSIX system call that operates on valid file descriptors
+    unsafe {
+        libc::flock(fd, libc::LOCK_UN);
+    }
+
+    Some(result)
+}
+
+/// Pause local media while the phone rings. Returns whether any players are
+/// paused for the call, by this or another applet process.
+pub async fn pause_media_for_call_async(conn: Arc<Mutex<Connection>>, lower_volume: bool) -> bool {
+    let already_paused: Vec<String> = update_paused(PAUSED_PLAYERS_PATH, |paused, _| {
+        paused.players.iter().map(|p| p.bus_name.clone()).collect()
+    })
+    .unwrap_or_default();
+    let conn = conn.lock().await;
+    let newly_paused = pause_players(&conn, lower_volume, &already_paused).await;
+    update_paused(PAUSED_PLAYERS_PATH, |paused, now_ms| {
+        paused.last_ring_ms = now_ms;
+        for player in newly_paused {
+            if !paused.players.iter().any(|p| p.bus_name == player.bus_name) {
+                paused.players.push(player);
+            }
+        }
+        !paused.players.is_empty()
+    })
+    .unwrap_or(false)
+}
+
+/// Resume the local media paused for a call, on a missed call or at the
+/// user's request. Returns whether players are still paused, which is only
+/// the case for `after_ring`.
+///
+/// `after_ring` is the opt-in timed resume: the daemon reports nothing when
+/// a call is answered or hung up, so the players are resumed once the ring
+/// window has passed with no later ring restarting it, even if the call was
+/// answered.
+pub async fn resume_media_after_call_async(conn: Arc<Mutex<Connection>>, after_ring: bool) -> bool {
+    let (players, still_paused) = update_paused(PAUSED_PLAYERS_PATH, |paused, now_ms| {
+        if after_ring && !paused.ring_over(now_ms) {
+            return (Vec::new(), !paused.players.is_empty());
+        }
+        (std::mem::take(&mut paused.players), false)
+    })
+    .unwrap_or_default();
+    if !players.is_empty() {
+        let conn = conn.lock().await;
+        resume_players(&conn, &players).await;
+    }
+    still_paused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+
+    /// A fake MPRIS player recording what it was asked to do.
+    struct FakePlayer {
+        status: String,
+        volume: f64,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl FakePlayer {
+        fn play(&mut self) {
+            self.status = "Playing".to_string();
+        }
+        fn pause(&mut self) {
+            self.status = "Paused".to_string();
+        }
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            self.status.clone()
+        }
+        #[zbus(property)]
+        fn volume(&self) -> f64 {
+            self.volume
+        }
+        #[zbus(property)]
+        fn set_volume(&mut self, volume: f64) {
+            self.volume = volume;
+        }
+    }
+
+    /// A private session bus, killed on drop.
+    struct PrivateBus(Child);
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    /// Start a private bus, or `None` where `dbus-daemon` isn't installed.
+    fn private_bus() -> Option<(PrivateBus, String)> {
+        let mut child = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .ok()?;
+        let mut address = String::new();
+        BufReader::new(child.stdout.take()?)
+            .read_line(&mut address)
+            .ok()?;
+        Some((PrivateBus(child), address.trim().to_string()))
+    }
+
+    async fn serve(address: &str, name: &str, status: &str) -> Connection {
+        let player = FakePlayer {
+            status: status.to_string(),
+            volume: 0.5,
+        };
+        zbus::connection::Builder::address(address)
+            .unwrap()
+            .name(format!("{MPRIS_PREFIX}{name}"))
+            .unwrap()
+            .serve_at("/org/mpris/MediaPlayer2", player)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+    }
+
+    async fn status(conn: &Connection, name: &str) -> String {
+        let bus_name = format!("{MPRIS_PREFIX}{name}");
+        let proxy = player(conn, &bus_name).await.unwrap();
+        proxy.playback_status().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn pauses_only_playing_players_and_resumes_them() {
+        let Some((_bus, address)) = private_bus() else {
+            return;
+        };
+        let _playing = serve(&address, "playing", "Playing").await;
+        let _stopped = serve(&address, "stopped", "Paused").await;
+        // The daemon's player for a phone's media session is never touched
+        let _phone = serve(&address, "kdeconnect.mpris_abc", "Playing").await;
+        let conn = zbus::connection::Builder::address(address.as_str())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let paused = pause_players(&conn, false, &[]).await;
+        assert_eq!(
+            paused,
+            [PausedPlayer {
+                bus_name: format!("{MPRIS_PREFIX}playing"),
+                restore: Restore::Play,
+            }]
+        );
+        assert_eq!(status(&conn, "playing").await, "Paused");
+
+        resume_players(&conn, &paused).await;
+        assert_eq!(status(&conn, "playing").await, "Playing");
+        assert_eq!(status(&conn, "stopped").await, "Paused");
+
+        let lowered = pause_players(&conn, true, &[]).await;
+        assert_eq!(lowered[0].restore, Restore::Volume(0.5));
+        assert_eq!(status(&conn, "playing").await, "Playing");
+        // A second ring must not lower the already lowered player again
+        let skip = [lowered[0].bus_name.clone()];
+        assert!(pause_players(&conn, true, &skip).await.is_empty());
+        resume_players(&conn, &lowered).await;
+        let proxy = player(&conn, &lowered[0].bus_name).await.unwrap();
+        assert_eq!(proxy.volume().await.unwrap(), 0.5);
+    }
+
+    #[test]
+    fn paused_call_expires_and_ring_ends() {
+        let paused = PausedCall {
+            last_ring_ms: 1_000,
+            players: Vec::new(),
+        };
+        assert!(!paused.ring_over(1_000 + 5_000));
+        assert!(paused.ring_over(1_000 + i64::from(CALL_RING_TIMEOUT_MS)));
+        assert!(!paused.is_expired(1_000 + i64::from(CALL_RING_TIMEOUT_MS)));
+        assert!(paused.is_expired(1_000 + CALL_PAUSE_EXPIRY_MS + 1));
+    }
+}
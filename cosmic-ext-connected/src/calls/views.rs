@@ -0,0 +1,140 @@
+//! Call history view components.
+
+use crate::app::Message;
+use crate::calls::log::{CallEntry, CallKind, CallLog};
+use crate::fl;
+use crate::views::helpers::format_timestamp;
+use cosmic::applet;
+use cosmic::iced::advanced::widget::text::Style as TextStyle;
+use cosmic::iced::widget::{column, row};
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{self, text};
+use cosmic::Element;
+
+/// Parameters for the call history view.
+pub struct CallHistoryParams<'a> {
+    pub device_id: &'a str,
+    pub device_name: Option<&'a str>,
+    /// The device's log; `None` while it loads.
+    pub call_log: Option<&'a CallLog>,
+}
+
+fn call_kind_label(kind: CallKind) -> String {
+    match kind {
+        CallKind::Incoming => fl!("call-incoming"),
+        CallKind::Missed => fl!("call-missed"),
+    }
+}
+
+/// One call: icon, caller, kind and time, and a "Send SMS" action.
+fn view_call_row<'a>(device_id: &str, entry: &CallEntry) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    let missed = entry.kind == CallKind::Missed;
+    let icon_name = match entry.kind {
+        CallKind::Incoming => "call-incoming-symbolic",
+        CallKind::Missed => "call-missed-symbolic",
+    };
+
+    fn missed_style(theme: &cosmic::Theme) -> TextStyle {
+        TextStyle {
+            color: Some(theme.cosmic().destructive_text_color().into()),
+        }
+    }
+
+    let mut icon = widget::icon::from_name(icon_name).size(20).icon();
+    let mut name = text::body(entry.display_name().to_string())
+        .wrapping(cosmic::iced::widget::text::Wrapping::None);
+    if missed {
+        icon = icon.class(cosmic::theme::Svg::custom(|theme| {
+            cosmic::iced::widget::svg::Style {
+                color: Some(theme.cosmic().destructive_text_color().into()),
+            }
+        }));
+        name = name
+            .font(cosmic::font::bold())
+            .class(cosmic::theme::Text::Custom(missed_style));
+    }
+
+    let mut details = column![name, text::caption(call_kind_label(entry.kind))].spacing(2);
+    if entry.display_name() != entry.phone_number {
+        details = details.push(text::caption(entry.phone_number.clone()));
+    }
+
+    applet::padded_control(
+        row![
+            icon,
+            widget::container(details).width(Length::Fill).clip(true),
+            text::caption(format_timestamp(entry.timestamp)),
+            widget::tooltip(
+                widget::button::icon(widget::icon::from_name("mail-message-new-symbolic"))
+                    .class(cosmic::theme::Button::Link)
+                    .on_press(Message::CallHistorySendSms {
+                        device_id: device_id.to_string(),
+                        name: entry.display_name().to_string(),
+                        phone_number: entry.phone_number.clone(),
+                    }),
+                text::caption(fl!("send-sms")),
+                widget::tooltip::Position::Left,
+            )
+            .gap(sp.space_xxxs),
+        ]
+        .spacing(sp.space_xs)
+        .align_y(Alignment::Center),
+    )
+    .into()
+}
+
+/// Render the call history view.
+pub fn view_call_history(params: CallHistoryParams<'_>) -> Element<'_, Message> {
+    let sp = cosmic::theme::spacing();
+    let default_device = fl!("device");
+    let device_name = params.device_name.unwrap_or(&default_device);
+
+    let header = applet::padded_control(
+        row![
+            widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                .class(cosmic::theme::Button::Link)
+                .on_press(Message::CloseCallHistory),
+            text::heading(fl!("calls-title", device = device_name)),
+            widget::space::horizontal(),
+        ]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center),
+    );
+
+    let content: Element<Message> = match params.call_log {
+        None => widget::container(text::body(fl!("loading")))
+            .width(Length::Fill)
+            .align_x(Alignment::Center)
+            .padding(sp.space_m)
+            .into(),
+        Some(log) if log.entries.is_empty() => widget::container(
+            column![
+                widget::icon::from_name("call-start-symbolic").size(48),
+                text::body(fl!("no-calls")),
+                text::caption(fl!("no-calls-hint")),
+            ]
+            .spacing(sp.space_xs)
+            .align_x(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .padding(sp.space_m)
+        .into(),
+        Some(log) => {
+            let rows: Vec<Element<Message>> = log
+                .entries
+                .iter()
+                .map(|entry| view_call_row(params.device_id, entry))
+                .collect();
+            widget::scrollable(column(rows).spacing(sp.space_xxxs))
+                .width(Length::Fill)
+                .into()
+        }
+    };
+
+    column![header, content]
+        .spacing(sp.space_xxs)
+        .width(Length::Fill)
+        .into()
+}
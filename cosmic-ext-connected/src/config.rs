@@ -1,6 +1,7 @@
 //! Configuration management for the Connected applet.
 
 use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use kdeconnect_dbus::PhoneRegion;
 use serde::{Deserialize, Serialize};
 
 /// Application ID for configuration storage.
@@ -23,14 +24,31 @@ pub struct Config {
     /// against the user-displayed thread (which can produce duplicate delivery
     /// on the recipient side for symmetric merges — see v0.5.0 Topic 2).
     pub merge_reaction_threads: bool,
+    /// Show iOS tapback messages ("Liked “…”") as plain bubbles instead of
+    /// emoji badges on the message they react to.
+    pub show_raw_reactions: bool,
+    /// ISO country code used to read phone numbers written without a
+    /// country code. Empty: derive from the locale.
+    pub phone_region: String,
     /// Enable desktop notifications for incoming/missed calls
     pub call_notifications: bool,
     /// Show phone number in call notifications (privacy)
     pub call_notification_show_number: bool,
     /// Show contact name in call notifications (privacy)
     pub call_notification_show_name: bool,
+    /// Record incoming/answered/missed calls in the per-device call log
+    pub call_history: bool,
+    /// Pause playing desktop media while the phone rings
+    pub call_pause_media: bool,
+    /// Turn desktop media down instead of pausing it during calls
+    pub call_lower_volume: bool,
+    /// Resume paused media once the ring ends, even if the call was answered
+    /// (the daemon doesn't report answered calls)
+    pub call_resume_after_ring: bool,
     /// Enable desktop notifications for received files
     pub file_notifications: bool,
+    /// Send shared folders as a single zip archive instead of file by file
+    pub share_folders_as_zip: bool,
 }
 
 impl Default for Config {
@@ -41,10 +59,17 @@ impl Default for Config {
             sms_notification_show_content: true,
             sms_notification_show_sender: true,
             merge_reaction_threads: true,
+            show_raw_reactions: false,
+            phone_region: String::new(),
             call_notifications: true,
             call_notification_show_number: true,
             call_notification_show_name: true,
+            call_history: true,
+            call_pause_media: true,
+            call_lower_volume: false,
+            call_resume_after_ring: false,
             file_notifications: true,
+            share_folders_as_zip: false,
         }
     }
 }
@@ -68,6 +93,12 @@ impl Config {
         }
     }
 
+    /// Region for phone-number matching: the configured one if known,
+    /// otherwise the locale's.
+    pub fn phone_region(&self) -> &'static PhoneRegion {
+        PhoneRegion::from_code(&self.phone_region).unwrap_or_else(PhoneRegion::from_env)
+    }
+
     /// Save configuration to disk.
     pub fn save(&self) -> Result<(), cosmic_config::Error> {
         let config_handler = cosmic_config::Config::new(APP_ID, Self::VERSION)?;
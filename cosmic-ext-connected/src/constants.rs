@@ -27,6 +27,51 @@ pub mod sms {
     /// Max contact suggestions shown in the new-message recipient dropdown
     pub const MAX_SUGGESTIONS_SHOWN: usize = 5;
 
+    /// Max results shown for a conversation-list search.
+    pub const MAX_SEARCH_RESULTS: usize = 50;
+
+    /// How often unsaved SMS archive changes are written to disk (seconds).
+    /// Batches the write-out while a sync streams in many messages.
+    pub const ARCHIVE_FLUSH_INTERVAL_SECS: u64 = 5;
+
+    /// Retried failed messages remembered per device so the phone's failed
+    /// copy stays hidden. Old entries are dropped first.
+    pub const MAX_RETRIED_MESSAGES: usize = 200;
+
+    /// How often changed compose drafts are written to disk (seconds).
+    pub const DRAFT_FLUSH_INTERVAL_SECS: u64 = 2;
+
+    /// Largest file accepted as an MMS attachment (bytes). The phone
+    /// recompresses images to fit the carrier limit, but video and audio go
+    /// out as-is, so keep this well under what the MMS stack will try.
+    pub const MAX_ATTACHMENT_BYTES: u64 = 5 * 1024 * 1024;
+
+    /// Max attachments pending on a single outgoing message.
+    pub const MAX_PENDING_ATTACHMENTS: usize = 10;
+
+    /// How often scheduled messages are checked for being due (seconds).
+    /// Also bounds how long a due message waits after its phone reconnects.
+    pub const SCHEDULED_CHECK_INTERVAL_SECS: u64 = 30;
+
+    /// Send attempts for a scheduled message before it is marked as not
+    /// sent and left for the user to retry or cancel.
+    pub const SCHEDULED_MAX_ATTEMPTS: u32 = 5;
+
+    /// Wait after a scheduled message's first failed attempt (milliseconds).
+    /// Doubles with each further failure.
+    pub const SCHEDULED_RETRY_BASE_MS: i64 = 60 * 1000;
+
+    /// Age after which an applet process's claim on a scheduled message is
+    /// ignored (milliseconds), so a message isn't stuck if that process
+    /// exited mid-send. Far longer than a send takes.
+    pub const SCHEDULED_CLAIM_EXPIRY_MS: i64 = 10 * 60 * 1000;
+
+    /// How often the device's vCard directory is checked for changes
+    /// (seconds) until the contacts plugin's `localCacheSynchronized` signal
+    /// has been seen for the device; after that the signal alone drives
+    /// reloads. Only modified files are re-parsed.
+    pub const CONTACTS_FALLBACK_INTERVAL_SECS: u64 = 300;
+
     /// Timeout for conversation loading when cache exists (seconds).
     /// Shorter since we only need incremental updates.
     pub const CONVERSATION_TIMEOUT_CACHED_SECS: u64 = 3;
@@ -48,6 +93,10 @@ pub mod sms {
     /// Timeout for loading messages in a conversation thread (seconds).
     pub const MESSAGE_FETCH_TIMEOUT_SECS: u64 = 10;
 
+    /// Timeout for loading the full history of one thread for an export
+    /// (seconds). Threads that don't finish in time are reported as incomplete.
+    pub const EXPORT_THREAD_TIMEOUT_SECS: u64 = 30;
+
     /// Hard timeout for the local store phase of message loading (seconds).
     /// Safety net if conversationLoaded signal never arrives. After this fires,
     /// ConversationLoadComplete is emitted but the subscription continues.
@@ -91,8 +140,29 @@ pub mod sms {
 
 /// Refresh and polling interval constants.
 pub mod refresh {
-    /// Interval for refreshing media player state (seconds).
-    pub const MEDIA_INTERVAL_SECS: u64 = 2;
+    /// Interval for re-reading media player state while the media view is
+    /// open (seconds). Updates normally arrive through mprisremote signals;
+    /// this only catches changes a missed signal would leave stale.
+    pub const MEDIA_FALLBACK_INTERVAL_SECS: u64 = 15;
+
+    /// Interval for advancing the interpolated media position while playing
+    /// (milliseconds).
+    pub const MEDIA_POSITION_TICK_MS: u64 = 500;
+}
+
+/// Media controls constants.
+pub mod media {
+    /// Album art images kept in the applet's cache; the oldest are removed
+    /// once a new image pushes the count past this.
+    pub const MAX_CACHED_ALBUM_ART: usize = 50;
+
+    /// Quiet period that ends a burst of mprisremote `propertiesChanged`
+    /// signals before player state is re-read (milliseconds).
+    pub const MEDIA_SIGNAL_DEBOUNCE_MS: u64 = 150;
+
+    /// How long a player without a matching per-player MPRIS service is left
+    /// alone before the bus is scanned for it again (seconds).
+    pub const PLAYER_SERVICE_RETRY_SECS: u64 = 60;
 }
 
 /// Notification display constants.
@@ -106,8 +176,42 @@ pub mod notifications {
     /// Display duration (ms) for the Critical incoming-call toast. COSMIC does NOT cap
     /// urgent notifications (`max_timeout_urgent = None`), so this is the LITERAL on-screen
     /// time — distinct mechanism from NORMAL_* above despite the equal number. ~30s ≈ the
-    /// Android ring-to-voicemail window. NOT Timeout::Never: Connected has no active
-    /// dismissal on call-end, so Never would leave a stale toast until manual dismiss.
-    /// (True persist-while-ringing + dismissal is v0.7.0 candidate D.3.)
+    /// Android ring-to-voicemail window. A missed call replaces the toast early, but the
+    /// daemon reports nothing when a call is answered or hung up, so NOT Timeout::Never:
+    /// that would leave a stale toast until manual dismiss. Also how long a ringing toast's
+    /// id is remembered.
     pub const CALL_RING_TIMEOUT_MS: u32 = 30_000;
 }
+
+/// Call history constants.
+pub mod calls {
+    /// How long after a ring a "missedCall" event for the same
+    /// number still belongs to that call (milliseconds). Covers a long ring
+    /// plus the phone's delay reporting the outcome.
+    pub const CALL_EVENT_MERGE_WINDOW_MS: i64 = 2 * 60 * 1000;
+
+    /// Calls kept per device; older entries are dropped.
+    pub const MAX_CALL_LOG_ENTRIES: usize = 500;
+
+    /// Volume multiplier for local media players during a call, when they
+    /// are turned down instead of paused.
+    pub const CALL_MEDIA_VOLUME_FACTOR: f64 = 0.2;
+
+    /// Age after which the saved list of players paused for a call is
+    /// discarded instead of resumed (milliseconds). The list waits for a
+    /// missed call or the user's resume, so this outlasts any phone call and
+    /// only catches a list that was never resumed.
+    pub const CALL_PAUSE_EXPIRY_MS: i64 = 4 * 60 * 60 * 1000;
+}
+
+/// File sharing constants.
+pub mod share {
+    /// Files a shared folder may contain, whether it is sent file by file or
+    /// zipped; larger folders are refused so a misdropped home directory isn't
+    /// uploaded or packed.
+    pub const MAX_FOLDER_FILES: usize = 1000;
+
+    /// Age after which zip archives made for shared folders are removed from
+    /// the cache (seconds). Long enough for the daemon to finish uploading.
+    pub const ZIP_ARCHIVE_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+}
@@ -1,11 +1,13 @@
-//! Device actions: ping, find my phone, share, pairing, clipboard, notifications.
+//! Device actions: ping, find my phone, share, pairing, clipboard, notifications,
+//! contact sync.
 
 use crate::app::Message;
 use kdeconnect_dbus::{
-    plugins::{ClipboardProxy, FindMyPhoneProxy, NotificationProxy, PingProxy, ShareProxy},
+    plugins::{
+        ClipboardProxy, ContactsProxy, FindMyPhoneProxy, NotificationProxy, PingProxy, ShareProxy,
+    },
     DeviceProxy,
 };
-use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use zbus::Connection;
@@ -50,24 +52,30 @@ pub async fn find_my_phone_async(
     findmyphone.ring().await.map_err(|e| e.to_string())
 }
 
-/// Share a file to a device.
-pub async fn share_file_async(
+/// Ask a device to sync its contacts into the local vCard directory.
+/// Returns once the request is sent; the files arrive asynchronously.
+pub async fn sync_contacts_async(
     conn: Arc<Mutex<Connection>>,
     device_id: String,
-    path: PathBuf,
 ) -> Result<(), String> {
     let conn = conn.lock().await;
-    let share_path = format!("{}/devices/{}/share", kdeconnect_dbus::BASE_PATH, device_id);
+    let path = format!(
+        "{}/devices/{}/contacts",
+        kdeconnect_dbus::BASE_PATH,
+        device_id
+    );
 
-    let share = ShareProxy::builder(&conn)
-        .path(share_path.as_str())
+    let contacts = ContactsProxy::builder(&conn)
+        .path(path.as_str())
         .map_err(|e| e.to_string())?
         .build()
         .await
         .map_err(|e| e.to_string())?;
 
-    let url = format!("file://{}", path.display());
-    share.share_url(&url).await.map_err(|e| e.to_string())
+    contacts
+        .synchronize_remote_with_local()
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Share text to a device.
@@ -3,7 +3,9 @@
 pub mod actions;
 pub mod class;
 pub mod fetch;
+pub mod share;
 
 pub use actions::*;
 pub use class::DeviceClass;
 pub use fetch::*;
+pub use share::{pick_share_paths_async, share_paths_async, DroppedFiles, ShareSummary};
@@ -0,0 +1,370 @@
+//! Sharing batches of files and folders with a device.
+//!
+//! Paths come from the file-picker portal or from a drag-and-drop onto a
+//! device row or the Send-to view. Each file is handed to the daemon with its
+//! own `shareUrl` call so a single failure doesn't abort the batch. Folders
+//! are either expanded into the files they contain or packed into a zip
+//! archive in the applet's cache first, depending on the
+//! `share_folders_as_zip` setting.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use cosmic::iced::clipboard::mime::AllowedMimeTypes;
+use kdeconnect_dbus::plugins::ShareProxy;
+use tokio::sync::Mutex;
+use zbus::Connection;
+
+use crate::config::APP_ID;
+use crate::constants::share::{MAX_FOLDER_FILES, ZIP_ARCHIVE_MAX_AGE_SECS};
+use crate::uri::{file_url, local_path};
+
+/// MIME type file managers use for dragged files.
+const URI_LIST_MIME: &str = "text/uri-list";
+
+/// Local paths from a `text/uri-list` payload (RFC 2483). Comment lines and
+/// anything that isn't a local file are skipped.
+pub fn parse_uri_list(data: &str) -> Vec<PathBuf> {
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(local_path)
+        .collect()
+}
+
+/// Files dropped onto a device row or the Send-to view.
+#[derive(Debug, Clone, Default)]
+pub struct DroppedFiles(pub Vec<PathBuf>);
+
+impl AllowedMimeTypes for DroppedFiles {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::Owned(vec![URI_LIST_MIME.to_string()])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for DroppedFiles {
+    type Error = std::string::FromUtf8Error;
+
+    fn try_from((data, _mime): (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        Ok(Self(parse_uri_list(&String::from_utf8(data)?)))
+    }
+}
+
+/// Outcome of sharing one batch, shown as a single status message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShareSummary {
+    /// Files the daemon accepted.
+    pub shared: usize,
+    /// Files or folders that could not be shared.
+    pub failed: usize,
+    /// Most recent error, for the status message.
+    pub last_error: Option<String>,
+}
+
+impl ShareSummary {
+    fn fail(&mut self, error: String) {
+        self.failed += 1;
+        self.last_error = Some(error);
+    }
+}
+
+/// Show the file picker for files, or for folders when `folders` is set.
+/// Multiple selection is allowed; returns an empty list if cancelled.
+pub async fn pick_share_paths_async(title: String, folders: bool) -> Vec<PathBuf> {
+    use cosmic::dialog::file_chooser;
+    let dialog = file_chooser::open::Dialog::new().title(title);
+    let result = if folders {
+        dialog.open_folders().await
+    } else {
+        dialog.open_files().await
+    };
+    match result {
+        Ok(response) => response
+            .urls()
+            .iter()
+            .filter_map(|url| url.to_file_path().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Files below `dir`, recursively and sorted. Symlinks are skipped, as in
+/// [`zip_folder`], so a link can't loop back up the tree or pull in files
+/// from outside the folder.
+async fn expand_folder(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current)
+            .await
+            .map_err(|e| format!("{}: {}", current.display(), e))?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(file_type) = entry.file_type().await else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                files.push(path);
+            }
+            if files.len() > MAX_FOLDER_FILES {
+                return Err(format!(
+                    "{}: more than {} files",
+                    dir.display(),
+                    MAX_FOLDER_FILES
+                ));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn archive_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(APP_ID).join("share"))
+}
+
+/// Pack `dir` into `dest`. Entries are stored under the folder's own name so
+/// the archive unpacks into a single directory on the phone. Folders with
+/// more than `MAX_FOLDER_FILES` files are refused, as when they are sent file
+/// by file. Symlinks are skipped rather than followed.
+fn zip_folder(dir: &Path, dest: &Path) -> std::io::Result<()> {
+    use zip::write::SimpleFileOptions;
+
+    let root = dir.parent().unwrap_or(dir);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(dest)?);
+
+    let mut count = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let relative = current.strip_prefix(root).unwrap_or(&current);
+        zip.add_directory_from_path(relative, options)?;
+        let mut entries: Vec<_> = std::fs::read_dir(&current)?
+            .filter_map(Result::ok)
+            .collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                count += 1;
+                if count > MAX_FOLDER_FILES {
+                    return Err(std::io::Error::other(format!(
+                        "more than {} files",
+                        MAX_FOLDER_FILES
+                    )));
+                }
+                zip.start_file_from_path(path.strip_prefix(root).unwrap_or(&path), options)?;
+                std::io::copy(&mut std::fs::File::open(&path)?, &mut zip)?;
+            }
+        }
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+/// Distinguishes archives created within the same clock tick.
+static NEXT_ARCHIVE: AtomicU64 = AtomicU64::new(0);
+
+/// Zip `dir` into the applet's cache and return the archive path. Each
+/// archive gets a directory of its own, so folders with the same name (or the
+/// same folder shared twice while the daemon is still uploading) don't
+/// overwrite each other and the phone still receives `<folder name>.zip`.
+/// Archives left over from earlier shares are removed once they are old
+/// enough that the daemon has finished reading them.
+async fn archive_folder(dir: &Path) -> Result<PathBuf, String> {
+    let cache = archive_dir().ok_or_else(|| "No cache directory".to_string())?;
+    tokio::fs::create_dir_all(&cache)
+        .await
+        .map_err(|e| e.to_string())?;
+    prune_archives(&cache).await;
+
+    let stamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let unique = format!(
+        "{}-{}-{}",
+        stamp,
+        std::process::id(),
+        NEXT_ARCHIVE.fetch_add(1, Ordering::Relaxed)
+    );
+    let slot = cache.join(unique);
+    tokio::fs::create_dir(&slot)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "folder".to_string());
+    let dest = slot.join(format!("{}.zip", name));
+    let source = dir.to_path_buf();
+    let archive = dest.clone();
+    let result = tokio::task::spawn_blocking(move || zip_folder(&source, &archive))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| format!("{}: {}", dir.display(), e)));
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_dir_all(&slot).await;
+        return Err(e);
+    }
+    Ok(dest)
+}
+
+async fn prune_archives(cache: &Path) {
+    let Ok(mut entries) = tokio::fs::read_dir(cache).await else {
+        return;
+    };
+    let max_age = Duration::from_secs(ZIP_ARCHIVE_MAX_AGE_SECS);
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let expired = entry
+            .metadata()
+            .await
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+        if expired {
+            let path = entry.path();
+            let _ = if path.is_dir() {
+                tokio::fs::remove_dir_all(&path).await
+            } else {
+                tokio::fs::remove_file(&path).await
+            };
+        }
+    }
+}
+
+/// Share files and folders with a device. Folders are zipped when
+/// `zip_folders` is set and expanded into their files otherwise.
+pub async fn share_paths_async(
+    conn: Arc<Mutex<Connection>>,
+    device_id: String,
+    paths: Vec<PathBuf>,
+    zip_folders: bool,
+) -> ShareSummary {
+    let mut summary = ShareSummary::default();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        match tokio::fs::metadata(&path).await {
+            Ok(meta) if meta.is_dir() && zip_folders => match archive_folder(&path).await {
+                Ok(archive) => files.push(archive),
+                Err(e) => summary.fail(e),
+            },
+            Ok(meta) if meta.is_dir() => match expand_folder(&path).await {
+                Ok(contents) => files.extend(contents),
+                Err(e) => summary.fail(e),
+            },
+            Ok(_) => files.push(path),
+            Err(e) => summary.fail(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    // Hold the shared connection only long enough to clone it; a large batch
+    // would otherwise block every other D-Bus call until it finished.
+    let conn = conn.lock().await.clone();
+    let share_path = format!("{}/devices/{}/share", kdeconnect_dbus::BASE_PATH, device_id);
+    let share = match ShareProxy::builder(&conn).path(share_path.as_str()) {
+        Ok(builder) => builder.build().await,
+        Err(e) => Err(e),
+    };
+    let share = match share {
+        Ok(share) => share,
+        Err(e) => {
+            summary.failed += files.len();
+            summary.last_error = Some(e.to_string());
+            return summary;
+        }
+    };
+
+    for file in files {
+        match share.share_url(&file_url(&file)).await {
+            Ok(()) => summary.shared += 1,
+            Err(e) => {
+                tracing::warn!("Failed to share {}: {}", file.display(), e);
+                summary.fail(e.to_string());
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_uri_list_keeps_local_files() {
+        let data = "# dragged from Files\r\n\
+                    file:///home/me/Pictures/a%20b.png\r\n\
+                    \r\n\
+                    https://example.com/remote.txt\r\n\
+                    file:///home/me/Documents\r\n";
+        assert_eq!(
+            parse_uri_list(data),
+            vec![
+                PathBuf::from("/home/me/Pictures/a b.png"),
+                PathBuf::from("/home/me/Documents"),
+            ]
+        );
+        assert!(parse_uri_list("").is_empty());
+    }
+
+    #[test]
+    fn dropped_files_from_uri_list_bytes() {
+        let files = DroppedFiles::try_from((
+            b"file:///tmp/one.txt\nfile:///tmp/two.txt\n".to_vec(),
+            URI_LIST_MIME.to_string(),
+        ))
+        .unwrap();
+        assert_eq!(
+            files.0,
+            vec![PathBuf::from("/tmp/one.txt"), PathBuf::from("/tmp/two.txt")]
+        );
+    }
+
+    #[test]
+    fn zip_folder_refuses_too_many_files() {
+        let dir = std::env::temp_dir().join(format!("connected-share-{}", std::process::id()));
+        let folder = dir.join("many");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&folder).unwrap();
+        for i in 0..=MAX_FOLDER_FILES {
+            std::fs::write(folder.join(i.to_string()), b"x").unwrap();
+        }
+
+        assert!(zip_folder(&folder, &dir.join("many.zip")).is_err());
+        std::fs::remove_file(folder.join("0")).unwrap();
+        assert!(zip_folder(&folder, &dir.join("many.zip")).is_ok());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn zip_folder_skips_symlinks() {
+        let dir = std::env::temp_dir().join(format!("connected-links-{}", std::process::id()));
+        let folder = dir.join("links");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&folder).unwrap();
+        for i in 0..MAX_FOLDER_FILES {
+            std::fs::write(folder.join(i.to_string()), b"x").unwrap();
+        }
+        std::fs::write(dir.join("outside"), b"x").unwrap();
+        std::os::unix::fs::symlink(dir.join("outside"), folder.join("link")).unwrap();
+        std::os::unix::fs::symlink(&dir, folder.join("loop")).unwrap();
+
+        // The link would be one file too many if it were followed
+        assert!(zip_folder(&folder, &dir.join("links.zip")).is_ok());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -4,6 +4,7 @@
 //! with a native COSMIC desktop interface.
 
 mod app;
+mod calls;
 mod config;
 mod constants;
 mod device;
@@ -11,8 +12,10 @@ mod i18n;
 mod media;
 mod notifications;
 mod sms;
+mod storage;
 mod subscriptions;
 mod ui;
+mod uri;
 mod views;
 
 use app::ConnectApplet;
@@ -0,0 +1,99 @@
+//! Album art for the phone's current track.
+//!
+//! The daemon downloads artwork from the phone into its own cache and
+//! reports it through the `albumArtUrl` property as a `file://` URL. The
+//! file is copied into the applet's cache under a name derived from the URL,
+//! so the view keeps a stable image even if the daemon cleans up its copy.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use crate::config::APP_ID;
+use crate::constants::media::MAX_CACHED_ALBUM_ART;
+use crate::uri::local_path;
+
+/// File name in the art cache for `url`, keeping the source extension so
+/// the image decoder can sniff the format.
+fn cache_file_name(url: &str, source: &Path) -> String {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    let ext = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| e.chars().all(|c| c.is_ascii_alphanumeric()))
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_else(|| "img".to_string());
+    format!("{:016x}.{}", hasher.finish(), ext)
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(APP_ID).join("album-art"))
+}
+
+/// Copy the artwork behind `url` into the applet's cache and return the
+/// cached path. Returns `None` when there is no art, the URL isn't a local
+/// file, or the copy fails — the view falls back to a generic icon.
+pub async fn cache_album_art_async(url: &str) -> Option<PathBuf> {
+    if url.is_empty() {
+        return None;
+    }
+    let Some(source) = local_path(url) else {
+        tracing::debug!("Ignoring non-local album art URL: {}", url);
+        return None;
+    };
+    let dir = cache_dir()?;
+    let cached = dir.join(cache_file_name(url, &source));
+    if tokio::fs::try_exists(&cached).await.unwrap_or(false) {
+        return Some(cached);
+    }
+
+    if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+        tracing::warn!("Failed to create album art cache {}: {}", dir.display(), e);
+        return None;
+    }
+    if let Err(e) = tokio::fs::copy(&source, &cached).await {
+        tracing::debug!("Failed to cache album art {}: {}", source.display(), e);
+        return None;
+    }
+    prune_cache(&dir).await;
+    Some(cached)
+}
+
+/// Remove the oldest cached images beyond [`MAX_CACHED_ALBUM_ART`].
+async fn prune_cache(dir: &Path) {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return;
+    };
+    let mut files = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if let Ok(modified) = entry.metadata().await.and_then(|m| m.modified()) {
+            files.push((modified, entry.path()));
+        }
+    }
+    if files.len() <= MAX_CACHED_ALBUM_ART {
+        return;
+    }
+    files.sort();
+    let excess = files.len() - MAX_CACHED_ALBUM_ART;
+    for (_, path) in files.into_iter().take(excess) {
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_file_name_is_stable_and_keeps_extension() {
+        let url = "file:///tmp/Cover.JPG";
+        let name = cache_file_name(url, Path::new("/tmp/Cover.JPG"));
+        assert_eq!(name, cache_file_name(url, Path::new("/tmp/Cover.JPG")));
+        assert!(name.ends_with(".jpg"));
+        assert!(cache_file_name("file:///tmp/art", Path::new("/tmp/art")).ends_with(".img"));
+        assert_ne!(
+            name,
+            cache_file_name("file:///tmp/other.jpg", Path::new("/tmp/other.jpg"))
+        );
+    }
+}
@@ -1,8 +1,16 @@
 //! Media information fetching and control actions.
 
+use super::art::cache_album_art_async;
 use crate::app::{MediaInfo, Message};
-use kdeconnect_dbus::plugins::MprisRemoteProxy;
-use std::sync::Arc;
+use crate::constants::media::PLAYER_SERVICE_RETRY_SECS;
+use kdeconnect_dbus::plugins::{
+    find_remote_player_service, read_mprisremote_properties, read_remote_player_capabilities,
+    LoopStatus, MprisRemoteProxy, RemotePlayerProxy,
+};
+use kdeconnect_dbus::DeviceProxy;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex as StdMutex};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use zbus::Connection;
 
@@ -12,65 +20,205 @@ pub enum MediaAction {
     Next,
     Previous,
     SetVolume(i32),
+    /// Seek to an absolute position in milliseconds.
+    SetPosition(i32),
     SelectPlayer(String),
+    /// Turn shuffle on or off via the player's MPRIS service.
+    SetShuffle {
+        service: String,
+        shuffle: bool,
+    },
+    /// Set the repeat mode via the player's MPRIS service.
+    SetLoopStatus {
+        service: String,
+        status: LoopStatus,
+    },
 }
 
-/// Fetch media information from a device.
-pub async fn fetch_media_info_async(conn: Arc<Mutex<Connection>>, device_id: String) -> Message {
-    let conn = conn.lock().await;
+/// What the selected player supports, read from its per-player MPRIS
+/// service. Defaults to everything allowed and the modes unknown, which is
+/// what the mprisremote interface alone can tell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerCapabilities {
+    /// Bus name of the per-player service, if it was found.
+    pub service: Option<String>,
+    pub can_play: bool,
+    pub can_pause: bool,
+    pub can_next: bool,
+    pub can_previous: bool,
+    /// `None` when the player doesn't report shuffle.
+    pub shuffle: Option<bool>,
+    /// `None` when the player doesn't report a loop mode.
+    pub loop_status: Option<LoopStatus>,
+}
+
+impl Default for PlayerCapabilities {
+    fn default() -> Self {
+        Self {
+            service: None,
+            can_play: true,
+            can_pause: true,
+            can_next: true,
+            can_previous: true,
+            shuffle: None,
+            loop_status: None,
+        }
+    }
+}
+
+/// Per-player service lookups, keyed by device id and player name.
+#[derive(Debug, Clone)]
+enum PlayerService {
+    Found(String),
+    /// No service matched; scanned again after `PLAYER_SERVICE_RETRY_SECS`.
+    Missing(Instant),
+}
+
+/// Finding a player's service means listing every bus name and reading one
+/// `Identity` per candidate, so the result is kept until the service goes
+/// away instead of being redone on every media read.
+static PLAYER_SERVICES: LazyLock<StdMutex<HashMap<(String, String), PlayerService>>> =
+    LazyLock::new(Default::default);
+
+async fn find_player_service(conn: &Connection, device_id: &str, player: &str) -> Option<String> {
+    let key = (device_id.to_string(), player.to_string());
+    let cached = PLAYER_SERVICES
+        .lock()
+        .ok()
+        .and_then(|services| services.get(&key).cloned());
+    match cached {
+        Some(PlayerService::Found(service)) => return Some(service),
+        Some(PlayerService::Missing(since))
+            if since.elapsed() < Duration::from_secs(PLAYER_SERVICE_RETRY_SECS) =>
+        {
+            return None;
+        }
+        _ => {}
+    }
+
+    let device_path = format!("{}/devices/{}", kdeconnect_dbus::BASE_PATH, device_id);
+    let device_name = match DeviceProxy::builder(conn).path(device_path.as_str()) {
+        Ok(builder) => match builder.build().await {
+            Ok(proxy) => proxy.name().await.unwrap_or_default(),
+            Err(_) => String::new(),
+        },
+        Err(_) => String::new(),
+    };
+    let service = find_remote_player_service(conn, &device_name, player)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::debug!("Failed to look up MPRIS service for {}: {}", player, e);
+            None
+        });
+    if let Ok(mut services) = PLAYER_SERVICES.lock() {
+        let entry = match &service {
+            Some(name) => PlayerService::Found(name.clone()),
+            None => PlayerService::Missing(Instant::now()),
+        };
+        services.insert(key, entry);
+    }
+    service
+}
+
+fn forget_player_service(device_id: &str, player: &str) {
+    if let Ok(mut services) = PLAYER_SERVICES.lock() {
+        services.remove(&(device_id.to_string(), player.to_string()));
+    }
+}
+
+/// Find the daemon's MPRIS service for `player` on `device_id` and read its
+/// capabilities. Falls back to the defaults if the service can't be found.
+async fn read_player_capabilities(
+    conn: &Connection,
+    device_id: &str,
+    player: &str,
+) -> PlayerCapabilities {
+    if player.is_empty() {
+        return PlayerCapabilities::default();
+    }
+    // A cached service can disappear when the phone's player closes and come
+    // back under a new name; look it up once more before giving up.
+    for _ in 0..2 {
+        let Some(service) = find_player_service(conn, device_id, player).await else {
+            break;
+        };
+        match read_remote_player_capabilities(conn, &service).await {
+            Ok(caps) => {
+                return PlayerCapabilities {
+                    service: Some(service),
+                    can_play: caps.can_play,
+                    can_pause: caps.can_pause,
+                    can_next: caps.can_go_next,
+                    can_previous: caps.can_go_previous,
+                    shuffle: caps.shuffle,
+                    loop_status: caps.loop_status,
+                };
+            }
+            Err(e) => {
+                tracing::debug!("Failed to read MPRIS service {}: {}", service, e);
+                forget_player_service(device_id, player);
+            }
+        }
+    }
+    PlayerCapabilities::default()
+}
+
+/// Read the current player state of a device. `album_art` is left empty;
+/// callers cache the artwork behind `album_art_url` once the connection is
+/// no longer needed. Returns `None` if the mprisremote properties can't be
+/// read.
+async fn read_media_info(conn: &Connection, device_id: &str) -> Option<MediaInfo> {
     let path = format!(
         "{}/devices/{}/mprisremote",
         kdeconnect_dbus::BASE_PATH,
         device_id
     );
 
-    let proxy = match MprisRemoteProxy::builder(&conn)
-        .path(path.as_str())
-        .ok()
-        .map(|b| b.build())
-    {
-        Some(fut) => match fut.await {
-            Ok(p) => p,
-            Err(e) => {
-                tracing::debug!("Failed to create mpris proxy: {}", e);
-                return Message::MediaInfoLoaded(None);
-            }
-        },
-        None => {
-            tracing::debug!("Failed to build mpris proxy path");
-            return Message::MediaInfoLoaded(None);
+    let properties = match read_mprisremote_properties(conn, &path).await {
+        Ok(properties) => properties,
+        Err(e) => {
+            tracing::debug!("Failed to read mprisremote properties: {}", e);
+            return None;
         }
     };
+    let now = std::time::Instant::now();
+    let mut info = MediaInfo {
+        players: Vec::new(),
+        current_player: String::new(),
+        title: String::new(),
+        artist: String::new(),
+        album: String::new(),
+        album_art_url: String::new(),
+        album_art: None,
+        is_playing: false,
+        volume: 0,
+        position: 0,
+        length: 0,
+        position_updated: now,
+        can_seek: false,
+        capabilities: PlayerCapabilities::default(),
+    };
+    for property in properties {
+        info.apply(property, now);
+    }
+    // canGoNext/canGoPrevious etc. are per-player properties not exposed on the
+    // main interface; they come from the player's own MPRIS service.
+    info.capabilities = read_player_capabilities(conn, device_id, &info.current_player).await;
+    Some(info)
+}
 
-    // Fetch all properties
-    let players = proxy.player_list().await.unwrap_or_default();
-    let current_player = proxy.player().await.unwrap_or_default();
-    let title = proxy.title().await.unwrap_or_default();
-    let artist = proxy.artist().await.unwrap_or_default();
-    let album = proxy.album().await.unwrap_or_default();
-    let is_playing = proxy.is_playing().await.unwrap_or(false);
-    let volume = proxy.volume().await.unwrap_or(0);
-    // D-Bus returns i32 for position/length, convert to i64
-    let position = proxy.position().await.unwrap_or(0) as i64;
-    let length = proxy.length().await.unwrap_or(0) as i64;
-    // Note: canGoNext/canGoPrevious are per-player properties not exposed on the main interface.
-    // We default to true to allow actions; the phone will handle if unsupported.
-    let can_next = true;
-    let can_previous = true;
-
-    Message::MediaInfoLoaded(Some(MediaInfo {
-        players,
-        current_player,
-        title,
-        artist,
-        album,
-        is_playing,
-        volume,
-        position,
-        length,
-        can_next,
-        can_previous,
-    }))
+/// Fetch media information from a device.
+pub async fn fetch_media_info_async(conn: Arc<Mutex<Connection>>, device_id: String) -> Message {
+    let info = {
+        let conn = conn.lock().await;
+        read_media_info(&conn, &device_id).await
+    };
+    let Some(mut info) = info else {
+        return Message::MediaInfoLoaded(None);
+    };
+    // Copy the artwork after releasing the D-Bus connection
+    info.album_art = cache_album_art_async(&info.album_art_url).await;
+    Message::MediaInfoLoaded(Some(info))
 }
 
 /// Execute a media control action on a device.
@@ -117,7 +265,20 @@ pub async fn media_action_async(
         MediaAction::Next => proxy.send_action("Next").await,
         MediaAction::Previous => proxy.send_action("Previous").await,
         MediaAction::SetVolume(vol) => proxy.set_volume(vol).await,
+        MediaAction::SetPosition(pos) => proxy.set_position(pos).await,
         MediaAction::SelectPlayer(player) => proxy.set_player(&player).await,
+        MediaAction::SetShuffle { service, shuffle } => {
+            match remote_player(&conn, &service).await {
+                Ok(player) => player.set_shuffle(shuffle).await,
+                Err(e) => Err(e),
+            }
+        }
+        MediaAction::SetLoopStatus { service, status } => {
+            match remote_player(&conn, &service).await {
+                Ok(player) => player.set_loop_status(status.as_str()).await,
+                Err(e) => Err(e),
+            }
+        }
     };
 
     match result {
@@ -125,3 +286,13 @@ pub async fn media_action_async(
         Err(e) => Message::MediaActionResult(Err(format!("Action failed: {}", e))),
     }
 }
+
+async fn remote_player<'a>(
+    conn: &Connection,
+    service: &'a str,
+) -> zbus::Result<RemotePlayerProxy<'a>> {
+    RemotePlayerProxy::builder(conn)
+        .destination(service)?
+        .build()
+        .await
+}
@@ -1,5 +1,6 @@
 //! Media controls for KDE Connect devices.
 
+pub mod art;
 pub mod fetch;
 pub mod views;
 
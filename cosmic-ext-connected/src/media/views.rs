@@ -4,16 +4,24 @@ use crate::app::{MediaInfo, Message};
 use crate::fl;
 use crate::views::helpers::format_duration;
 use cosmic::applet;
+use cosmic::iced::advanced::image::Handle as ImageHandle;
 use cosmic::iced::widget::{column, row};
-use cosmic::iced::{Alignment, Length};
+use cosmic::iced::{Alignment, ContentFit, Length};
 use cosmic::widget::{self, text};
 use cosmic::Element;
+use kdeconnect_dbus::plugins::LoopStatus;
+
+/// Edge length of the album art image (px).
+const ALBUM_ART_SIZE: f32 = 96.0;
 
 /// Parameters for the media controls view.
 pub struct MediaControlsParams<'a> {
     pub device_name: Option<&'a str>,
     pub media_info: Option<&'a MediaInfo>,
     pub media_loading: bool,
+    /// Playback position to show (milliseconds): the seek bar's drag
+    /// position, or the interpolated position from `media_info`.
+    pub position: Option<i64>,
 }
 
 /// Render the media controls view.
@@ -62,7 +70,7 @@ pub fn view_media_controls(params: MediaControlsParams<'_>) -> Element<'_, Messa
             .into()
         } else {
             // Show media controls
-            view_media_player(info)
+            view_media_player(info, params.position.unwrap_or(info.position))
         }
     } else {
         // Error or no media plugin
@@ -87,8 +95,8 @@ pub fn view_media_controls(params: MediaControlsParams<'_>) -> Element<'_, Messa
         .into()
 }
 
-/// Render the media player with controls.
-pub fn view_media_player(info: &MediaInfo) -> Element<'_, Message> {
+/// Render the media player with controls, showing playback at `position`.
+pub fn view_media_player(info: &MediaInfo, position: i64) -> Element<'_, Message> {
     let sp = cosmic::theme::spacing();
 
     // Player selector (if multiple players)
@@ -146,8 +154,8 @@ pub fn view_media_player(info: &MediaInfo) -> Element<'_, Message> {
     .align_x(Alignment::Center)
     .width(Length::Fill);
 
-    // Position display
-    let position_str = format_duration(info.position);
+    // Seek bar and position display
+    let position_str = format_duration(position);
     let length_str = format_duration(info.length);
     let position_display = row![
         text::caption(position_str),
@@ -156,6 +164,24 @@ pub fn view_media_player(info: &MediaInfo) -> Element<'_, Message> {
     ]
     .padding([0, sp.space_xs as u16]);
 
+    // The slider has no disabled state; when the player can't seek, its
+    // changes are ignored by `update`, so the handle stays at the position.
+    let seek_bar: Element<Message> = if info.length > 0 {
+        let length = info.length.min(i32::MAX as i64) as i32;
+        let value = position.clamp(0, length as i64) as i32;
+        let slider = widget::slider(0..=length, value, Message::MediaSeekPreview);
+        let slider = if info.can_seek {
+            slider.on_release(Message::MediaSeekRelease)
+        } else {
+            slider
+        };
+        widget::container(slider)
+            .padding([0, sp.space_xs as u16])
+            .into()
+    } else {
+        widget::Space::new().into()
+    };
+
     // Playback controls
     let play_icon = if info.is_playing {
         "media-playback-pause-symbolic"
@@ -164,25 +190,74 @@ pub fn view_media_player(info: &MediaInfo) -> Element<'_, Message> {
     };
 
     let prev_button = widget::button::icon(widget::icon::from_name("media-skip-backward-symbolic"))
-        .on_press_maybe(if info.can_previous {
+        .on_press_maybe(if info.capabilities.can_previous {
             Some(Message::MediaPrevious)
         } else {
             None
         });
 
-    let play_button =
-        widget::button::icon(widget::icon::from_name(play_icon)).on_press(Message::MediaPlayPause);
+    let can_toggle = if info.is_playing {
+        info.capabilities.can_pause
+    } else {
+        info.capabilities.can_play
+    };
+    let play_button = widget::button::icon(widget::icon::from_name(play_icon))
+        .on_press_maybe(can_toggle.then_some(Message::MediaPlayPause));
+
+    // Shuffle and repeat, only for players that report them
+    let shuffle_button: Option<Element<Message>> = info.capabilities.shuffle.map(|shuffle| {
+        widget::tooltip(
+            widget::button::icon(widget::icon::from_name("media-playlist-shuffle-symbolic"))
+                .selected(shuffle)
+                .on_press(Message::MediaToggleShuffle),
+            text::caption(if shuffle {
+                fl!("media-shuffle-on")
+            } else {
+                fl!("media-shuffle-off")
+            }),
+            widget::tooltip::Position::Top,
+        )
+        .gap(sp.space_xxxs)
+        .padding(sp.space_xxs)
+        .into()
+    });
+    let loop_button: Option<Element<Message>> = info.capabilities.loop_status.map(|status| {
+        let (icon, label) = match status {
+            LoopStatus::None => ("media-playlist-repeat-symbolic", fl!("media-repeat-off")),
+            LoopStatus::Playlist => ("media-playlist-repeat-symbolic", fl!("media-repeat-all")),
+            LoopStatus::Track => (
+                "media-playlist-repeat-song-symbolic",
+                fl!("media-repeat-one"),
+            ),
+        };
+        widget::tooltip(
+            widget::button::icon(widget::icon::from_name(icon))
+                .selected(status != LoopStatus::None)
+                .on_press(Message::MediaCycleLoop),
+            text::caption(label),
+            widget::tooltip::Position::Top,
+        )
+        .gap(sp.space_xxxs)
+        .padding(sp.space_xxs)
+        .into()
+    });
 
     let next_button = widget::button::icon(widget::icon::from_name("media-skip-forward-symbolic"))
-        .on_press_maybe(if info.can_next {
+        .on_press_maybe(if info.capabilities.can_next {
             Some(Message::MediaNext)
         } else {
             None
         });
 
-    let playback_controls = row![prev_button, play_button, next_button,]
-        .spacing(sp.space_s)
-        .align_y(Alignment::Center);
+    let playback_controls = widget::row::with_children(
+        shuffle_button
+            .into_iter()
+            .chain([prev_button.into(), play_button.into(), next_button.into()])
+            .chain(loop_button)
+            .collect::<Vec<_>>(),
+    )
+    .spacing(sp.space_s)
+    .align_y(Alignment::Center);
 
     let controls_container = widget::container(playback_controls)
         .width(Length::Fill)
@@ -210,17 +285,31 @@ pub fn view_media_player(info: &MediaInfo) -> Element<'_, Message> {
     .align_y(Alignment::Center)
     .padding([0, sp.space_xs as u16]);
 
+    // Album art, or a generic icon when the track has none
+    let album_art: Element<Message> = match &info.album_art {
+        Some(path) => cosmic::iced::widget::image(ImageHandle::from_path(path))
+            .width(Length::Fixed(ALBUM_ART_SIZE))
+            .height(Length::Fixed(ALBUM_ART_SIZE))
+            .content_fit(ContentFit::Cover)
+            .border_radius([sp.space_xxs as f32; 4])
+            .into(),
+        None => widget::icon::from_name("multimedia-player-symbolic")
+            .size(48)
+            .into(),
+    };
+
     let divider = || applet::padded_control(widget::divider::horizontal::default());
 
     // Assemble the view
     column![
         player_selector,
         divider(),
-        widget::container(widget::icon::from_name("multimedia-player-symbolic").size(48))
+        widget::container(album_art)
             .width(Length::Fill)
             .align_x(Alignment::Center),
         applet::padded_control(track_info),
         divider(),
+        seek_bar,
         position_display,
         controls_container,
         divider(),
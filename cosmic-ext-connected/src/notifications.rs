@@ -9,6 +9,8 @@ use std::io::{Read, Seek, Write};
 use std::os::unix::io::AsRawFd;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::constants::notifications::CALL_RING_TIMEOUT_MS;
+
 /// Deduplication window in milliseconds (2 seconds).
 const DEDUP_WINDOW_MS: u128 = 2000;
 
@@ -21,6 +23,9 @@ const SMS_DEDUP_PATH: &str = "/tmp/cosmic-connected-sms-dedup";
 /// File path for call notification deduplication.
 const CALL_DEDUP_PATH: &str = "/tmp/cosmic-connected-call-dedup";
 
+/// File path mapping ringing calls to the id of their on-screen toast.
+const CALL_TOAST_PATH: &str = "/tmp/cosmic-connected-call-toasts";
+
 /// Check if we should show a file notification (cross-process deduplication via file lock).
 /// Returns true if this is the first notification for this file within the dedup window.
 pub fn should_show_file_notification(file_url: &str) -> bool {
@@ -44,6 +49,96 @@ pub fn should_show_call_notification(event: &str, phone_number: &str) -> bool {
     should_show_notification(CALL_DEDUP_PATH, &key)
 }
 
+/// Id of the ringing toast currently shown for `call_key`, if any.
+pub fn ringing_call_toast(call_key: &str) -> Option<u32> {
+    update_call_toasts(CALL_TOAST_PATH, |toasts, _| {
+        toasts.iter().find(|t| t.0 == call_key).map(|t| t.1)
+    })
+    .flatten()
+}
+
+/// Remember the ringing toast shown for `call_key`.
+///
+/// The call's later events may reach a different applet process than the one
+/// that showed the toast, so the id is kept in a shared file rather than in
+/// app state.
+pub fn remember_call_toast(call_key: &str, notification_id: u32) {
+    update_call_toasts(CALL_TOAST_PATH, |toasts, now_ms| {
+        toasts.retain(|t| t.0 != call_key);
+        toasts.push((call_key.to_string(), notification_id, now_ms));
+    });
+}
+
+/// Forget the ringing toast for `call_key`, returning its id so the caller
+/// can close or replace it.
+pub fn take_call_toast(call_key: &str) -> Option<u32> {
+    update_call_toasts(CALL_TOAST_PATH, |toasts, _| {
+        let index = toasts.iter().position(|t| t.0 == call_key)?;
+        Some(toasts.remove(index).1)
+    })
+    .flatten()
+}
+
+/// Read-modify-write the ringing-toast table under an exclusive file lock.
+///
+/// Each line is `key\tid\tshown_at_ms`. Entries older than the ring timeout
+/// are dropped first: their toast has expired on its own, because the call
+/// was answered or the phone stopped ringing without a missed-call event.
+fn update_call_toasts<R>(
+    path: &str,
+    f: impl FnOnce(&mut Vec<(String, u32, u128)>, u128) -> R,
+) -> Option<R> {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .ok()?;
+
+    let fd = file.as_raw_fd();
+    // SAFETY: flock is a standard POSIX system call that operates on valid file descriptors
+    unsafe {
+        if libc::flock(fd, libc::LOCK_EX) != 0 {
+            return None;
+        }
+    }
+
+    let mut contents = String::new();
+    let _ = file.read_to_string(&mut contents);
+    let mut toasts: Vec<(String, u32, u128)> = contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let key = fields.next()?.to_string();
+            let id = fields.next()?.parse().ok()?;
+            let shown_at = fields.next()?.parse().ok()?;
+            Some((key, id, shown_at))
+        })
+        .filter(|t| now_ms.saturating_sub(t.2) < u128::from(CALL_RING_TIMEOUT_MS))
+        .collect();
+
+    let result = f(&mut toasts, now_ms);
+
+    let _ = file.set_len(0);
+    let _ = file.rewind();
+    for (key, id, shown_at) in &toasts {
+        let _ = writeln!(file, "{}\t{}\t{}", key, id, shown_at);
+    }
+
+    // SAFETY: flock is a standard POSIX system call that operates on valid file descriptors
+    unsafe {
+        libc::flock(fd, libc::LOCK_UN);
+    }
+
+    Some(result)
+}
+
 /// Generic notification deduplication using file-based locking.
 ///
 /// This function:
@@ -282,4 +377,27 @@ mod tests {
 
         cleanup(&path);
     }
+
+    #[test]
+    fn call_toasts_are_taken_once_and_expire() {
+        let path = temp_dedup_path("call-toasts");
+        cleanup(&path);
+
+        update_call_toasts(&path, |toasts, now_ms| {
+            toasts.push(("dev:5551234".to_string(), 7, now_ms));
+            toasts.push(("dev:5559999".to_string(), 8, 0)); // long expired
+        });
+        let take = |key: &str| {
+            update_call_toasts(&path, |toasts, _| {
+                let index = toasts.iter().position(|t| t.0 == key)?;
+                Some(toasts.remove(index).1)
+            })
+            .flatten()
+        };
+        assert_eq!(take("dev:5559999"), None);
+        assert_eq!(take("dev:5551234"), Some(7));
+        assert_eq!(take("dev:5551234"), None);
+
+        cleanup(&path);
+    }
 }
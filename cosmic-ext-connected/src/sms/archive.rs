@@ -0,0 +1,337 @@
+//! On-disk SMS archive for offline reading.
+//!
+//! One JSON file per device under `$XDG_DATA_HOME/<APP_ID>/sms/`. The store
+//! records every conversation head and message it sees, so the Messages
+//! view renders instantly on open and stays readable while the phone is
+//! out of reach. Messages merge by `uid`; conversation heads by thread ID
+//! with the newer timestamp winning. Saving merges into the file on disk, so
+//! applet processes sharing a device's archive don't drop each other's
+//! messages.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use kdeconnect_dbus::plugins::{
+    ConversationSummary, DeliveryState, SmsMessage, OPTIMISTIC_MESSAGE_UID,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::APP_ID;
+use crate::constants::sms::MAX_RETRIED_MESSAGES;
+use crate::storage::{file_name_for, load_json, update_json_async};
+
+/// A failed message that was sent again. The phone keeps its failed copy
+/// and may report it again with the next thread load; it's recognized by
+/// thread, date and body and hidden.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetriedMessage {
+    pub thread_id: i64,
+    pub date: i64,
+    pub body: String,
+}
+
+impl RetriedMessage {
+    fn matches(&self, message: &SmsMessage) -> bool {
+        message.delivery_state() == DeliveryState::Failed
+            && self.thread_id == message.thread_id
+            && self.date == message.date
+            && self.body == message.body
+    }
+}
+
+/// Archived conversations and messages for a single device.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmsArchive {
+    /// Latest known head of each underlying thread.
+    pub conversations: Vec<ConversationSummary>,
+    /// Messages keyed by underlying thread ID, sorted oldest first.
+    pub messages: HashMap<i64, Vec<SmsMessage>>,
+    /// Failed messages replaced by a successful retry, oldest first.
+    #[serde(default)]
+    pub retried: Vec<RetriedMessage>,
+}
+
+impl SmsArchive {
+    /// Insert or refresh a conversation head. Returns `true` if the archive
+    /// changed.
+    pub fn upsert_conversation(&mut self, conversation: &ConversationSummary) -> bool {
+        match self
+            .conversations
+            .iter_mut()
+            .find(|cs| cs.thread_id == conversation.thread_id)
+        {
+            Some(existing) if conversation.timestamp > existing.timestamp => {
+                *existing = conversation.clone();
+                true
+            }
+            Some(_) => false,
+            None => {
+                self.conversations.push(conversation.clone());
+                true
+            }
+        }
+    }
+
+    /// Merge a message into its thread by `uid`, replacing any stored copy.
+    /// Optimistic (unconfirmed) messages are never archived. Returns `true`
+    /// if the archive changed.
+    pub fn merge_message(&mut self, message: &SmsMessage) -> bool {
+        if message.uid == OPTIMISTIC_MESSAGE_UID || self.is_retried(message) {
+            return false;
+        }
+        let thread = self.messages.entry(message.thread_id).or_default();
+        if let Some(existing) = thread.iter_mut().find(|m| m.uid == message.uid) {
+            if existing.body == message.body
+                && existing.date == message.date
+                && existing.message_type == message.message_type
+                && existing.raw_type == message.raw_type
+                && existing.read == message.read
+            {
+                return false;
+            }
+            *existing = message.clone();
+        } else {
+            let insert_pos = thread
+                .iter()
+                .position(|m| m.date > message.date)
+                .unwrap_or(thread.len());
+            thread.insert(insert_pos, message.clone());
+        }
+        true
+    }
+
+    /// Whether `message` is the phone's failed copy of a message that has
+    /// since been retried.
+    pub fn is_retried(&self, message: &SmsMessage) -> bool {
+        self.retried.iter().any(|r| r.matches(message))
+    }
+
+    /// Remember that a failed message was sent again and drop it, so the
+    /// phone's copy stays hidden when it comes back. Returns `true` if the
+    /// archive changed.
+    pub fn mark_retried(&mut self, message: &SmsMessage) -> bool {
+        let retried = RetriedMessage {
+            thread_id: message.thread_id,
+            date: message.date,
+            body: message.body.clone(),
+        };
+        if self.retried.contains(&retried) {
+            return false;
+        }
+        self.retried.push(retried);
+        self.forget_retried();
+        true
+    }
+
+    /// Drop retried messages' failed copies and cap the retried list.
+    fn forget_retried(&mut self) {
+        let excess = self.retried.len().saturating_sub(MAX_RETRIED_MESSAGES);
+        self.retried.drain(..excess);
+        let retried = &self.retried;
+        for thread in self.messages.values_mut() {
+            thread.retain(|m| !retried.iter().any(|r| r.matches(m)));
+        }
+    }
+
+    /// Fold `other` into `self`, keeping the newer conversation heads, the
+    /// union of messages, and every retried message either side knows.
+    pub fn merge_from(&mut self, other: SmsArchive) {
+        for retried in &other.retried {
+            if !self.retried.contains(retried) {
+                self.retried.push(retried.clone());
+            }
+        }
+        self.forget_retried();
+        for conversation in &other.conversations {
+            self.upsert_conversation(conversation);
+        }
+        for message in other.messages.values().flatten() {
+            self.merge_message(message);
+        }
+    }
+
+    /// Archived messages for the given underlying threads, oldest first.
+    pub fn messages_for(&self, thread_ids: &[i64]) -> Vec<SmsMessage> {
+        let mut messages: Vec<SmsMessage> = thread_ids
+            .iter()
+            .filter_map(|id| self.messages.get(id))
+            .flatten()
+            .cloned()
+            .collect();
+        messages.sort_by_key(|m| m.date);
+        messages
+    }
+}
+
+/// Path of the archive file for `device_id`, or `None` if the XDG data
+/// directory can't be resolved.
+pub fn archive_path(device_id: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| {
+        dir.join(APP_ID)
+            .join("sms")
+            .join(format!("{}.json", file_name_for(device_id)))
+    })
+}
+
+/// Load the archive for `device_id`. Missing or unreadable archives come
+/// back empty — the archive is a cache, never the source of truth.
+pub async fn load_archive_async(device_id: String) -> SmsArchive {
+    let Some(path) = archive_path(&device_id) else {
+        return SmsArchive::default();
+    };
+    let archive: SmsArchive = load_json(&path, "SMS archive").await;
+    tracing::info!(
+        "Loaded SMS archive for {}: {} conversations, {} threads with messages",
+        device_id,
+        archive.conversations.len(),
+        archive.messages.len()
+    );
+    archive
+}
+
+/// Merge `archive` into the file for `device_id`, so messages another
+/// applet process archived in the meantime are kept.
+pub async fn save_archive_async(device_id: String, archive: SmsArchive) -> Result<(), String> {
+    let path = archive_path(&device_id).ok_or("No XDG data directory")?;
+    update_json_async(path, "SMS archive", move |saved: &mut SmsArchive| {
+        saved.merge_from(archive);
+        true
+    })
+    .await?;
+    tracing::debug!("Saved SMS archive for {}", device_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdeconnect_dbus::plugins::MessageType;
+
+    fn msg(thread_id: i64, uid: i32, date: i64, body: &str) -> SmsMessage {
+        SmsMessage {
+            body: body.to_string(),
+            addresses: vec!["5551234".to_string()],
+            date,
+            message_type: MessageType::Inbox,
+            raw_type: 1,
+            read: false,
+            thread_id,
+            uid,
+            sub_id: -1,
+            attachments: vec![],
+        }
+    }
+
+    fn head(thread_id: i64, timestamp: i64, last_message: &str) -> ConversationSummary {
+        ConversationSummary {
+            thread_id,
+            addresses: vec!["5551234".to_string()],
+            last_message: last_message.to_string(),
+            timestamp,
+            unread: false,
+            has_attachments: false,
+            sub_id: -1,
+        }
+    }
+
+    #[test]
+    fn merge_message_dedups_by_uid_and_keeps_date_order() {
+        let mut archive = SmsArchive::default();
+        assert!(archive.merge_message(&msg(1, 2, 200, "second")));
+        assert!(archive.merge_message(&msg(1, 1, 100, "first")));
+        assert!(!archive.merge_message(&msg(1, 2, 200, "second")));
+
+        let bodies: Vec<_> = archive.messages[&1]
+            .iter()
+            .map(|m| m.body.as_str())
+            .collect();
+        assert_eq!(bodies, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn merge_message_replaces_changed_copy() {
+        let mut archive = SmsArchive::default();
+        archive.merge_message(&msg(1, 1, 100, "hello"));
+        let mut read = msg(1, 1, 100, "hello");
+        read.read = true;
+        assert!(archive.merge_message(&read));
+        assert_eq!(archive.messages[&1].len(), 1);
+        assert!(archive.messages[&1][0].read);
+    }
+
+    #[test]
+    fn retried_failed_copy_stays_hidden() {
+        let mut failed = msg(1, 1, 100, "hello");
+        failed.message_type = MessageType::Sent;
+        failed.raw_type = 5;
+        let mut archive = SmsArchive::default();
+        archive.merge_message(&failed);
+        archive.merge_message(&msg(1, 2, 200, "kept"));
+
+        assert!(archive.mark_retried(&failed));
+        assert!(!archive.mark_retried(&failed));
+        assert_eq!(archive.messages[&1].len(), 1);
+        assert_eq!(archive.messages[&1][0].uid, 2);
+
+        // The phone reports its failed copy again, possibly under a new uid
+        failed.uid = 9;
+        assert!(archive.is_retried(&failed));
+        assert!(!archive.merge_message(&failed));
+
+        // Another process's archive still holding the copy doesn't revive it
+        let mut stale = SmsArchive::default();
+        stale.merge_message(&failed);
+        let mut saved = stale.clone();
+        saved.merge_from(archive.clone());
+        assert!(saved.messages[&1].iter().all(|m| m.uid != 9));
+        archive.merge_from(stale);
+        assert_eq!(archive.messages[&1].len(), 1);
+    }
+
+    #[test]
+    fn optimistic_messages_are_not_archived() {
+        let mut archive = SmsArchive::default();
+        assert!(!archive.merge_message(&msg(1, OPTIMISTIC_MESSAGE_UID, 100, "pending")));
+        assert!(archive.messages.is_empty());
+    }
+
+    #[test]
+    fn upsert_conversation_keeps_newest_head() {
+        let mut archive = SmsArchive::default();
+        assert!(archive.upsert_conversation(&head(1, 200, "new")));
+        assert!(!archive.upsert_conversation(&head(1, 100, "old")));
+        assert_eq!(archive.conversations[0].last_message, "new");
+    }
+
+    #[test]
+    fn messages_for_unions_merged_threads() {
+        let mut archive = SmsArchive::default();
+        archive.merge_message(&msg(1, 1, 300, "c"));
+        archive.merge_message(&msg(2, 2, 100, "a"));
+        archive.merge_message(&msg(3, 3, 200, "unrelated"));
+        let bodies: Vec<_> = archive
+            .messages_for(&[1, 2])
+            .into_iter()
+            .map(|m| m.body)
+            .collect();
+        assert_eq!(bodies, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut archive = SmsArchive::default();
+        archive.upsert_conversation(&head(7, 100, "hi"));
+        archive.merge_message(&msg(7, 1, 100, "hi"));
+        let json = serde_json::to_vec(&archive).unwrap();
+        let restored: SmsArchive = serde_json::from_slice(&json).unwrap();
+        assert_eq!(restored.conversations.len(), 1);
+        assert_eq!(restored.messages[&7][0].uid, 1);
+    }
+
+    #[test]
+    fn archive_path_sanitizes_device_id() {
+        if let Some(path) = archive_path("../evil/id") {
+            assert_eq!(path.file_name().unwrap(), "___evil_id.json");
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Outgoing MMS attachments picked from the compose box.
+//!
+//! Files are chosen through the file-picker portal, checked against the
+//! supported MIME types and [`MAX_ATTACHMENT_BYTES`], and handed to the
+//! daemon as `file://` URLs, which it reads and forwards to the phone.
+
+use std::path::{Path, PathBuf};
+
+use cosmic::iced::advanced::image::Handle as ImageHandle;
+
+use crate::constants::sms::MAX_ATTACHMENT_BYTES;
+use crate::uri::file_url;
+
+/// A validated file waiting to be sent with the next message.
+#[derive(Debug, Clone)]
+pub struct PendingAttachment {
+    pub path: PathBuf,
+    /// File name shown in the compose area.
+    pub name: String,
+    pub mime_type: String,
+    pub size: u64,
+    /// Preview for image attachments; loaded lazily from `path`.
+    pub thumbnail: Option<ImageHandle>,
+}
+
+impl PendingAttachment {
+    /// `file://` URL passed in the daemon's `attachment_urls` parameter.
+    pub fn url(&self) -> String {
+        file_url(&self.path)
+    }
+}
+
+/// Why a picked file was not attached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttachmentRejection {
+    /// Not a type MMS can carry.
+    UnsupportedType,
+    /// Larger than [`MAX_ATTACHMENT_BYTES`].
+    TooLarge,
+    /// Metadata couldn't be read (vanished, permissions).
+    Unreadable,
+}
+
+/// MIME type for a file MMS can carry, judged by extension. `None` for
+/// anything else.
+pub fn mime_type_for_path(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "heic" => "image/heic",
+        "mp4" | "m4v" => "video/mp4",
+        "3gp" => "video/3gpp",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "m4a" | "aac" => "audio/aac",
+        "amr" => "audio/amr",
+        "ogg" | "opus" => "audio/ogg",
+        "wav" => "audio/wav",
+        "vcf" => "text/x-vcard",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// Check a file's type and size before it's queued.
+pub fn validate(path: &Path, size: u64) -> Result<&'static str, AttachmentRejection> {
+    let mime_type = mime_type_for_path(path).ok_or(AttachmentRejection::UnsupportedType)?;
+    if size > MAX_ATTACHMENT_BYTES {
+        return Err(AttachmentRejection::TooLarge);
+    }
+    Ok(mime_type)
+}
+
+/// Result of checking one picked file: the attachment, or its file name
+/// and the reason it was refused.
+pub type PickedAttachment = Result<PendingAttachment, (String, AttachmentRejection)>;
+
+/// Show the file picker and validate whatever the user chose. Returns an
+/// empty list if the picker was cancelled.
+pub async fn pick_attachments_async(title: String) -> Vec<PickedAttachment> {
+    use cosmic::dialog::file_chooser;
+    let paths: Vec<PathBuf> = match file_chooser::open::Dialog::new()
+        .title(title)
+        .open_files()
+        .await
+    {
+        Ok(response) => response
+            .urls()
+            .iter()
+            .filter_map(|url| url.to_file_path().ok())
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+
+    let mut picked = Vec::with_capacity(paths.len());
+    for path in paths {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let size = match tokio::fs::metadata(&path).await {
+            Ok(meta) if meta.is_file() => meta.len(),
+            _ => {
+                picked.push(Err((name, AttachmentRejection::Unreadable)));
+                continue;
+            }
+        };
+        picked.push(match validate(&path, size) {
+            Ok(mime_type) => Ok(PendingAttachment {
+                thumbnail: mime_type
+                    .starts_with("image/")
+                    .then(|| ImageHandle::from_path(&path)),
+                path,
+                name,
+                mime_type: mime_type.to_string(),
+                size,
+            }),
+            Err(reason) => Err((name, reason)),
+        });
+    }
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_type_by_extension_ignores_case() {
+        assert_eq!(
+            mime_type_for_path(Path::new("/tmp/IMG_0001.JPG")),
+            Some("image/jpeg")
+        );
+        assert_eq!(mime_type_for_path(Path::new("clip.mp4")), Some("video/mp4"));
+        assert_eq!(mime_type_for_path(Path::new("setup.exe")), None);
+        assert_eq!(mime_type_for_path(Path::new("README")), None);
+    }
+
+    #[test]
+    fn validate_rejects_unsupported_and_oversized() {
+        assert_eq!(
+            validate(Path::new("a.png"), MAX_ATTACHMENT_BYTES),
+            Ok("image/png")
+        );
+        assert_eq!(
+            validate(Path::new("a.png"), MAX_ATTACHMENT_BYTES + 1),
+            Err(AttachmentRejection::TooLarge)
+        );
+        assert_eq!(
+            validate(Path::new("a.zip"), 10),
+            Err(AttachmentRejection::UnsupportedType)
+        );
+    }
+}
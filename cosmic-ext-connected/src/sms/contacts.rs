@@ -0,0 +1,145 @@
+//! Grouping, filtering and conversation lookup for the contacts browser.
+
+use crate::sms::logical::LogicalConversation;
+use kdeconnect_dbus::contacts::{normalize_phone_number, Contact};
+use kdeconnect_dbus::phone_key;
+use std::collections::HashMap;
+
+/// Section heading for names that don't start with a letter.
+const OTHER_SECTION: &str = "#";
+
+/// Minimum digits in a query before it is also matched against numbers, so
+/// short queries like "2" don't match nearly every contact.
+const MIN_NUMBER_QUERY_DIGITS: usize = 3;
+
+/// Contacts under one initial letter.
+#[derive(Debug)]
+pub struct ContactSection {
+    pub letter: String,
+    /// Indices into the contact slice the sections were built from
+    pub contacts: Vec<usize>,
+}
+
+/// Upper-cased first letter of `name`, or `#` for anything else.
+fn section_letter(name: &str) -> String {
+    match name.trim_start().chars().next() {
+        Some(c) if c.is_alphabetic() => c.to_uppercase().collect(),
+        _ => OTHER_SECTION.to_string(),
+    }
+}
+
+/// Whether `contact` matches a search: a case-insensitive substring of the
+/// name, or (for queries with enough digits) of one of its numbers.
+fn matches_query(contact: &Contact, query: &str) -> bool {
+    let query = query.trim();
+    if query.is_empty() {
+        return true;
+    }
+    if contact.name.to_lowercase().contains(&query.to_lowercase()) {
+        return true;
+    }
+    let digits = normalize_phone_number(query);
+    digits.len() >= MIN_NUMBER_QUERY_DIGITS
+        && contact
+            .phones
+            .iter()
+            .any(|p| normalize_phone_number(&p.number).contains(&digits))
+}
+
+/// Contacts with at least one phone number that match `query`, grouped by
+/// initial in alphabetical order with `#` last. `contacts` is expected
+/// sorted by name, as `ContactLookup::all_contacts` returns them.
+pub fn contact_sections(contacts: &[Contact], query: &str) -> Vec<ContactSection> {
+    let mut sections: Vec<ContactSection> = Vec::new();
+    for (index, contact) in contacts
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.phones.is_empty() && matches_query(c, query))
+    {
+        let letter = section_letter(&contact.name);
+        match sections.iter_mut().find(|s| s.letter == letter) {
+            Some(section) => section.contacts.push(index),
+            None => sections.push(ContactSection {
+                letter,
+                contacts: vec![index],
+            }),
+        }
+    }
+    sections.sort_by(|a, b| {
+        (a.letter == OTHER_SECTION)
+            .cmp(&(b.letter == OTHER_SECTION))
+            .then_with(|| a.letter.cmp(&b.letter))
+    });
+    sections
+}
+
+/// One-to-one conversations by [`phone_key`] of the other party, mapped to
+/// the thread to open. The first (most recent) conversation wins when a
+/// number has several, e.g. one per SIM.
+pub fn direct_threads(conversations: &[LogicalConversation]) -> HashMap<String, i64> {
+    let mut threads = HashMap::new();
+    for conv in conversations {
+        if let [address] = conv.addresses.as_slice() {
+            threads
+                .entry(phone_key(address))
+                .or_insert(conv.primary_thread_id);
+        }
+    }
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdeconnect_dbus::contacts::{ContactPhone, PhoneKind};
+
+    fn contact(name: &str, numbers: &[&str]) -> Contact {
+        Contact {
+            name: name.to_string(),
+            phones: numbers
+                .iter()
+                .map(|n| ContactPhone {
+                    number: n.to_string(),
+                    kind: PhoneKind::Mobile,
+                })
+                .collect(),
+            emails: Vec::new(),
+            organization: None,
+            photo: None,
+        }
+    }
+
+    #[test]
+    fn sections_are_alphabetical_with_other_last() {
+        let contacts = vec![
+            contact("42 Pizza", &["555-000-1111"]),
+            contact("anna", &["555-123-4567"]),
+            contact("Bertil", &["555-765-4321"]),
+            contact("Bo", &["555-222-3333"]),
+            contact("Émile", &["555-444-5555"]),
+            contact("No Number", &[]),
+        ];
+        let sections = contact_sections(&contacts, "");
+        let letters: Vec<&str> = sections.iter().map(|s| s.letter.as_str()).collect();
+        assert_eq!(letters, ["A", "B", "É", "#"]);
+        assert_eq!(sections[1].contacts.len(), 2);
+    }
+
+    #[test]
+    fn search_matches_name_or_number() {
+        let contacts = vec![
+            contact("Anna", &["+1 555 123 4567"]),
+            contact("Bertil", &["555-765-4321"]),
+        ];
+        let names = |query: &str| -> Vec<String> {
+            contact_sections(&contacts, query)
+                .iter()
+                .flat_map(|s| s.contacts.iter().map(|&i| contacts[i].name.clone()))
+                .collect()
+        };
+        assert_eq!(names("ANN"), ["Anna"]);
+        assert_eq!(names("123-45"), ["Anna"]);
+        assert_eq!(names("55"), Vec::<String>::new());
+        assert_eq!(names("zz"), Vec::<String>::new());
+    }
+}
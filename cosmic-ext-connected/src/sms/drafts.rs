@@ -0,0 +1,244 @@
+//! Unsent compose-box drafts.
+//!
+//! Each device keeps a draft per thread plus one in-progress new message.
+//! Drafts are updated in memory as the user types and written to
+//! `$XDG_DATA_HOME/<APP_ID>/sms/drafts.json` on a short timer, so they
+//! survive leaving the thread, closing the popup, and restarts. A write
+//! only touches the drafts this process changed, so applet processes don't
+//! overwrite each other's drafts.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::APP_ID;
+use crate::storage::{load_json, update_json_async};
+
+/// The new-message view's recipients and body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewMessageDraft {
+    /// (display name, address) chips, as in the compose view.
+    pub recipients: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl NewMessageDraft {
+    fn is_empty(&self) -> bool {
+        self.recipients.is_empty() && self.body.trim().is_empty()
+    }
+}
+
+/// Drafts for one device.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeviceDrafts {
+    /// Reply drafts keyed by the thread ID the conversation was opened with.
+    #[serde(default)]
+    pub threads: HashMap<i64, String>,
+    #[serde(default)]
+    pub new_message: Option<NewMessageDraft>,
+}
+
+/// One draft in [`SmsDrafts`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DraftSlot {
+    /// (device ID, thread ID)
+    Thread(String, i64),
+    /// Device ID
+    NewMessage(String),
+}
+
+/// Drafts for every device, keyed by device ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmsDrafts {
+    #[serde(default)]
+    pub devices: HashMap<String, DeviceDrafts>,
+}
+
+impl SmsDrafts {
+    /// Reply draft for any of a conversation's thread IDs.
+    pub fn thread(&self, device_id: &str, thread_ids: &[i64]) -> Option<&str> {
+        let threads = &self.devices.get(device_id)?.threads;
+        thread_ids
+            .iter()
+            .find_map(|id| threads.get(id))
+            .map(String::as_str)
+    }
+
+    /// All reply drafts for a device, for the conversation list preview.
+    pub fn threads_for(&self, device_id: &str) -> Option<&HashMap<i64, String>> {
+        self.devices
+            .get(device_id)
+            .map(|d| &d.threads)
+            .filter(|t| !t.is_empty())
+    }
+
+    /// Store a reply draft; whitespace-only text removes it. Returns whether
+    /// anything changed.
+    pub fn set_thread(&mut self, device_id: &str, thread_id: i64, text: &str) -> bool {
+        if text.trim().is_empty() {
+            let Some(device) = self.devices.get_mut(device_id) else {
+                return false;
+            };
+            let removed = device.threads.remove(&thread_id).is_some();
+            self.prune(device_id);
+            return removed;
+        }
+        let threads = &mut self
+            .devices
+            .entry(device_id.to_string())
+            .or_default()
+            .threads;
+        if threads.get(&thread_id).map(String::as_str) == Some(text) {
+            return false;
+        }
+        threads.insert(thread_id, text.to_string());
+        true
+    }
+
+    pub fn new_message(&self, device_id: &str) -> Option<&NewMessageDraft> {
+        self.devices.get(device_id)?.new_message.as_ref()
+    }
+
+    /// Store the new-message draft; an empty one removes it. Returns whether
+    /// anything changed.
+    pub fn set_new_message(&mut self, device_id: &str, draft: NewMessageDraft) -> bool {
+        if draft.is_empty() {
+            let Some(device) = self.devices.get_mut(device_id) else {
+                return false;
+            };
+            let removed = device.new_message.take().is_some();
+            self.prune(device_id);
+            return removed;
+        }
+        let slot = &mut self
+            .devices
+            .entry(device_id.to_string())
+            .or_default()
+            .new_message;
+        if slot.as_ref() == Some(&draft) {
+            return false;
+        }
+        *slot = Some(draft);
+        true
+    }
+
+    /// Make `slot` match its value in `from`, including removing it.
+    /// Returns whether anything changed.
+    pub fn copy_slot(&mut self, from: &SmsDrafts, slot: &DraftSlot) -> bool {
+        match slot {
+            DraftSlot::Thread(device_id, thread_id) => {
+                let text = from
+                    .devices
+                    .get(device_id)
+                    .and_then(|d| d.threads.get(thread_id))
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                self.set_thread(device_id, *thread_id, text)
+            }
+            DraftSlot::NewMessage(device_id) => {
+                let draft = from.new_message(device_id).cloned().unwrap_or_default();
+                self.set_new_message(device_id, draft)
+            }
+        }
+    }
+
+    /// Drop a device's entry once it holds no drafts.
+    fn prune(&mut self, device_id: &str) {
+        if self
+            .devices
+            .get(device_id)
+            .is_some_and(|d| d.threads.is_empty() && d.new_message.is_none())
+        {
+            self.devices.remove(device_id);
+        }
+    }
+}
+
+fn drafts_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_ID).join("sms").join("drafts.json"))
+}
+
+/// Load saved drafts. A missing or unreadable file yields none.
+pub async fn load_drafts_async() -> SmsDrafts {
+    match drafts_path() {
+        Some(path) => load_json(&path, "SMS drafts").await,
+        None => SmsDrafts::default(),
+    }
+}
+
+/// Write the `slots` of `drafts` into the file, leaving other drafts as
+/// they are on disk. Returns the file's drafts after the write.
+pub async fn save_drafts_async(
+    drafts: SmsDrafts,
+    slots: Vec<DraftSlot>,
+) -> Result<SmsDrafts, String> {
+    let path = drafts_path().ok_or("No XDG data directory")?;
+    update_json_async(path, "SMS drafts", move |saved: &mut SmsDrafts| {
+        slots.iter().fold(false, |changed, slot| {
+            saved.copy_slot(&drafts, slot) | changed
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thread_draft_set_and_clear() {
+        let mut drafts = SmsDrafts::default();
+        assert!(drafts.set_thread("dev", 7, "hello"));
+        assert!(!drafts.set_thread("dev", 7, "hello"));
+        assert_eq!(drafts.thread("dev", &[3, 7]), Some("hello"));
+        assert_eq!(drafts.thread("other", &[7]), None);
+
+        assert!(drafts.set_thread("dev", 7, "  \n"));
+        assert_eq!(drafts.thread("dev", &[7]), None);
+        assert!(drafts.devices.is_empty());
+        assert!(!drafts.set_thread("dev", 7, ""));
+    }
+
+    #[test]
+    fn new_message_draft_set_and_clear() {
+        let mut drafts = SmsDrafts::default();
+        let draft = NewMessageDraft {
+            recipients: vec![("Alice".to_string(), "+15551234567".to_string())],
+            body: String::new(),
+        };
+        assert!(drafts.set_new_message("dev", draft.clone()));
+        assert_eq!(drafts.new_message("dev"), Some(&draft));
+        assert!(drafts.set_new_message("dev", NewMessageDraft::default()));
+        assert!(drafts.new_message("dev").is_none());
+        assert!(drafts.devices.is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut drafts = SmsDrafts::default();
+        drafts.set_thread("dev", 42, "see you");
+        let json = serde_json::to_string(&drafts).unwrap();
+        let loaded: SmsDrafts = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.thread("dev", &[42]), Some("see you"));
+    }
+
+    #[test]
+    fn copy_slot_touches_only_that_draft() {
+        let mut saved = SmsDrafts::default();
+        saved.set_thread("dev", 1, "old");
+        saved.set_thread("dev", 2, "from another process");
+        let mut mine = SmsDrafts::default();
+        mine.set_thread("dev", 1, "new");
+
+        assert!(saved.copy_slot(&mine, &DraftSlot::Thread("dev".to_string(), 1)));
+        assert_eq!(saved.thread("dev", &[1]), Some("new"));
+        assert_eq!(saved.thread("dev", &[2]), Some("from another process"));
+
+        // A draft cleared here is cleared in the file too
+        mine.set_thread("dev", 1, "");
+        assert!(saved.copy_slot(&mine, &DraftSlot::Thread("dev".to_string(), 1)));
+        assert_eq!(saved.thread("dev", &[1]), None);
+        assert!(!saved.copy_slot(&mine, &DraftSlot::NewMessage("dev".to_string())));
+    }
+}
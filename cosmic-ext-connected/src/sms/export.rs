@@ -0,0 +1,422 @@
+//! Export SMS conversations to JSON, CSV, or SMS Backup & Restore XML.
+//!
+//! Exports cover what the SMS archive holds for each conversation, after the
+//! store has loaded each thread's full history into it. The store gathers the
+//! conversations and picks the destination through the save
+//! dialog; this module only formats and writes.
+
+use std::path::PathBuf;
+
+use kdeconnect_dbus::contacts::ContactLookup;
+use kdeconnect_dbus::plugins::{ConversationSummary, MessageType, SmsMessage};
+use serde::Serialize;
+
+/// Output format for an SMS export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    /// The XML layout written by the Android "SMS Backup & Restore" app,
+    /// which most restore and forensic tools can read.
+    SmsBackupXml,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Json,
+        ExportFormat::Csv,
+        ExportFormat::SmsBackupXml,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::SmsBackupXml => "xml",
+        }
+    }
+
+    /// Short label for the format picker.
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::SmsBackupXml => "XML",
+        }
+    }
+}
+
+/// Direction of a message relative to this device's owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+}
+
+impl From<MessageType> for Direction {
+    fn from(message_type: MessageType) -> Self {
+        match message_type {
+            MessageType::Inbox => Direction::Incoming,
+            MessageType::Sent => Direction::Outgoing,
+        }
+    }
+}
+
+/// A message as written to an export: the raw `SmsMessage` plus the
+/// resolved direction, contact name, and a local-time timestamp.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedMessage {
+    #[serde(flatten)]
+    pub message: SmsMessage,
+    pub direction: Direction,
+    pub contact_name: String,
+    pub date_local: String,
+}
+
+/// One conversation in an export. Merged conversations list every
+/// underlying thread head in `threads`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedConversation {
+    pub contact_name: String,
+    pub addresses: Vec<String>,
+    pub threads: Vec<ConversationSummary>,
+    pub messages: Vec<ExportedMessage>,
+}
+
+/// Format a millisecond timestamp in local time using chrono `fmt`.
+fn format_local(timestamp: i64, fmt: &str) -> String {
+    use chrono::{Local, TimeZone};
+    Local
+        .timestamp_millis_opt(timestamp)
+        .single()
+        .map(|dt| dt.format(fmt).to_string())
+        .unwrap_or_default()
+}
+
+/// Build the export record for one conversation. `messages` should be in
+/// date order (as returned by the archive).
+pub fn build_conversation(
+    addresses: &[String],
+    threads: Vec<ConversationSummary>,
+    messages: Vec<SmsMessage>,
+    contacts: &ContactLookup,
+) -> ExportedConversation {
+    let messages = messages
+        .into_iter()
+        .map(|message| ExportedMessage {
+            direction: message.message_type.into(),
+            contact_name: contacts.get_group_display_name(&message.addresses, usize::MAX),
+            date_local: format_local(message.date, "%Y-%m-%dT%H:%M:%S%:z"),
+            message,
+        })
+        .collect();
+    ExportedConversation {
+        contact_name: contacts.get_group_display_name(addresses, usize::MAX),
+        addresses: addresses.to_vec(),
+        threads,
+        messages,
+    }
+}
+
+/// Serialize conversations as pretty-printed JSON.
+pub fn to_json(conversations: &[ExportedConversation]) -> Result<String, String> {
+    serde_json::to_string_pretty(conversations).map_err(|e| e.to_string())
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Serialize conversations as CSV, one row per message. Multi-valued
+/// columns (addresses, attachments) are `;`-separated; attachments are
+/// written as `mime_type:unique_identifier`.
+pub fn to_csv(conversations: &[ExportedConversation]) -> String {
+    let mut out = String::from(
+        "thread_id,uid,date,date_local,direction,contact_name,addresses,body,read,attachments\n",
+    );
+    for conversation in conversations {
+        for exported in &conversation.messages {
+            let message = &exported.message;
+            let direction = match exported.direction {
+                Direction::Incoming => "incoming",
+                Direction::Outgoing => "outgoing",
+            };
+            let attachments: Vec<String> = message
+                .attachments
+                .iter()
+                .map(|a| format!("{}:{}", a.mime_type, a.unique_identifier))
+                .collect();
+            let fields = [
+                message.thread_id.to_string(),
+                message.uid.to_string(),
+                message.date.to_string(),
+                csv_field(&exported.date_local),
+                direction.to_string(),
+                csv_field(&exported.contact_name),
+                csv_field(&message.addresses.join(";")),
+                csv_field(&message.body),
+                message.read.to_string(),
+                csv_field(&attachments.join(";")),
+            ];
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Escape text for an XML attribute value. Line breaks become character
+/// references so multi-line bodies survive attribute normalization.
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            // Other control characters aren't allowed in XML 1.0 at all.
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Serialize conversations in the SMS Backup & Restore XML layout.
+///
+/// Plain one-to-one texts become `<sms>` elements. Messages with
+/// attachments or several participants become `<mms>` elements whose parts
+/// reference attachments by their KDE Connect unique identifier (`cl`); the
+/// attachment data itself is not embedded.
+pub fn to_sms_backup_xml(conversations: &[ExportedConversation]) -> String {
+    let count: usize = conversations.iter().map(|c| c.messages.len()).sum();
+    let mut out = String::from("<?xml version='1.0' encoding='UTF-8' standalone='yes' ?>\n");
+    out.push_str(&format!("<smses count=\"{}\">\n", count));
+
+    for conversation in conversations {
+        for exported in &conversation.messages {
+            let message = &exported.message;
+            let readable_date = format_local(message.date, "%b %-d, %Y %-I:%M:%S %p");
+            let read = u8::from(message.read);
+            let box_type = message.message_type as i32;
+
+            if message.attachments.is_empty() && message.addresses.len() <= 1 {
+                out.push_str(&format!(
+                    "  <sms protocol=\"0\" address=\"{}\" date=\"{}\" type=\"{}\" subject=\"null\" \
+                     body=\"{}\" toa=\"null\" sc_toa=\"null\" service_center=\"null\" read=\"{}\" \
+                     status=\"-1\" locked=\"0\" date_sent=\"0\" sub_id=\"{}\" readable_date=\"{}\" \
+                     contact_name=\"{}\" />\n",
+                    xml_escape(message.addresses.first().map_or("", String::as_str)),
+                    message.date,
+                    box_type,
+                    xml_escape(&message.body),
+                    read,
+                    message.sub_id,
+                    xml_escape(&readable_date),
+                    xml_escape(&exported.contact_name),
+                ));
+                continue;
+            }
+
+            out.push_str(&format!(
+                "  <mms date=\"{}\" ct_t=\"application/vnd.wap.multipart.related\" msg_box=\"{}\" \
+                 address=\"{}\" read=\"{}\" sub_id=\"{}\" text_only=\"{}\" readable_date=\"{}\" \
+                 contact_name=\"{}\">\n",
+                message.date,
+                box_type,
+                xml_escape(&message.addresses.join("~")),
+                read,
+                message.sub_id,
+                u8::from(message.attachments.is_empty()),
+                xml_escape(&readable_date),
+                xml_escape(&exported.contact_name),
+            ));
+            out.push_str("    <parts>\n");
+            let mut seq = 0;
+            if !message.body.is_empty() {
+                out.push_str(&format!(
+                    "      <part seq=\"{}\" ct=\"text/plain\" name=\"null\" chset=\"106\" \
+                     cl=\"null\" text=\"{}\" />\n",
+                    seq,
+                    xml_escape(&message.body)
+                ));
+                seq += 1;
+            }
+            for attachment in &message.attachments {
+                out.push_str(&format!(
+                    "      <part seq=\"{}\" ct=\"{}\" name=\"null\" chset=\"null\" cl=\"{}\" \
+                     text=\"null\" />\n",
+                    seq,
+                    xml_escape(&attachment.mime_type),
+                    xml_escape(&attachment.unique_identifier)
+                ));
+                seq += 1;
+            }
+            out.push_str("    </parts>\n    <addrs>\n");
+            for (i, address) in message.addresses.iter().enumerate() {
+                // PduHeaders: 137 = FROM, 151 = TO. The sender of an incoming
+                // message is listed first.
+                let addr_type = if i == 0 && exported.direction == Direction::Incoming {
+                    137
+                } else {
+                    151
+                };
+                out.push_str(&format!(
+                    "      <addr address=\"{}\" type=\"{}\" charset=\"106\" />\n",
+                    xml_escape(address),
+                    addr_type
+                ));
+            }
+            out.push_str("    </addrs>\n  </mms>\n");
+        }
+    }
+    out.push_str("</smses>\n");
+    out
+}
+
+/// Render conversations in `format`.
+pub fn render(
+    conversations: &[ExportedConversation],
+    format: ExportFormat,
+) -> Result<String, String> {
+    match format {
+        ExportFormat::Json => to_json(conversations),
+        ExportFormat::Csv => Ok(to_csv(conversations)),
+        ExportFormat::SmsBackupXml => Ok(to_sms_backup_xml(conversations)),
+    }
+}
+
+/// Suggested file name for the save dialog, e.g. `sms-export-20240105-1504.csv`.
+pub fn default_file_name(format: ExportFormat) -> String {
+    format!(
+        "sms-export-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M"),
+        format.extension()
+    )
+}
+
+/// Ask for a destination and write `contents` there. Returns `Ok(None)` if
+/// the user cancelled the dialog.
+pub async fn save_export_async(
+    title: String,
+    file_name: String,
+    contents: String,
+) -> Result<Option<PathBuf>, String> {
+    use cosmic::dialog::file_chooser;
+    let response = match file_chooser::save::Dialog::new()
+        .title(title)
+        .file_name(file_name)
+        .save_file()
+        .await
+    {
+        Ok(response) => response,
+        Err(_) => return Ok(None),
+    };
+    let Some(path) = response.url().and_then(|url| url.to_file_path().ok()) else {
+        return Ok(None);
+    };
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!("Exported SMS to {}", path.display());
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdeconnect_dbus::plugins::Attachment;
+
+    fn msg(uid: i32, message_type: MessageType, body: &str, addresses: &[&str]) -> SmsMessage {
+        SmsMessage {
+            body: body.to_string(),
+            addresses: addresses.iter().map(|s| (*s).to_string()).collect(),
+            date: 1_700_000_000_000 + i64::from(uid),
+            message_type,
+            raw_type: message_type as i32,
+            read: true,
+            thread_id: 1,
+            uid,
+            sub_id: -1,
+            attachments: vec![],
+        }
+    }
+
+    fn export(messages: Vec<SmsMessage>) -> Vec<ExportedConversation> {
+        vec![build_conversation(
+            &["5551234".to_string()],
+            vec![],
+            messages,
+            &ContactLookup::default(),
+        )]
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_commas_quotes_and_newlines() {
+        let csv = to_csv(&export(vec![msg(
+            1,
+            MessageType::Inbox,
+            "Hi, \"you\"\nthere",
+            &["5551234"],
+        )]));
+        let row = csv.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert!(row.contains("\"Hi, \"\"you\"\"\nthere\""));
+        assert!(row.contains(",incoming,"));
+    }
+
+    #[test]
+    fn json_flattens_message_and_adds_direction() {
+        let json = to_json(&export(vec![msg(1, MessageType::Sent, "ok", &["5551234"])])).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let message = &value[0]["messages"][0];
+        assert_eq!(message["body"], "ok");
+        assert_eq!(message["uid"], 1);
+        assert_eq!(message["direction"], "outgoing");
+    }
+
+    #[test]
+    fn xml_writes_sms_with_escaped_body_and_type() {
+        let xml = to_sms_backup_xml(&export(vec![msg(
+            1,
+            MessageType::Sent,
+            "<b> & \"x\"",
+            &["5551234"],
+        )]));
+        assert!(xml.contains("<smses count=\"1\">"));
+        assert!(xml.contains("type=\"2\""));
+        assert!(xml.contains("body=\"&lt;b&gt; &amp; &quot;x&quot;\""));
+    }
+
+    #[test]
+    fn xml_writes_mms_for_attachments_and_groups() {
+        let mut with_photo = msg(1, MessageType::Inbox, "look", &["5551111", "5552222"]);
+        with_photo.attachments.push(Attachment {
+            part_id: 3,
+            mime_type: "image/jpeg".to_string(),
+            base64_thumbnail: String::new(),
+            unique_identifier: "PART_3".to_string(),
+        });
+        let xml = to_sms_backup_xml(&export(vec![with_photo]));
+        assert!(xml.contains("<mms "));
+        assert!(xml.contains("address=\"5551111~5552222\""));
+        assert!(xml.contains("ct=\"image/jpeg\" name=\"null\" chset=\"null\" cl=\"PART_3\""));
+        assert!(xml.contains("address=\"5551111\" type=\"137\""));
+        assert!(xml.contains("address=\"5552222\" type=\"151\""));
+    }
+
+    #[test]
+    fn xml_escape_encodes_newlines_and_drops_control_chars() {
+        assert_eq!(xml_escape("a\nb\u{7}c"), "a&#10;bc");
+    }
+}
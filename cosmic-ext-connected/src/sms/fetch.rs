@@ -6,8 +6,8 @@
 use crate::app::Message;
 use crate::constants::sms::{
     CONVERSATION_TIMEOUT_CACHED_SECS, CONVERSATION_TIMEOUT_INITIAL_SECS,
-    FALLBACK_POLLING_DELAYS_MS, MESSAGE_FETCH_TIMEOUT_SECS, SIGNAL_ACTIVITY_TIMEOUT_MS,
-    SIGNAL_DRAIN_TIMEOUT_MS, TIMEOUT_CHECK_INTERVAL_MS,
+    EXPORT_THREAD_TIMEOUT_SECS, FALLBACK_POLLING_DELAYS_MS, MESSAGE_FETCH_TIMEOUT_SECS,
+    SIGNAL_ACTIVITY_TIMEOUT_MS, SIGNAL_DRAIN_TIMEOUT_MS, TIMEOUT_CHECK_INTERVAL_MS,
 };
 use futures_util::StreamExt;
 use kdeconnect_dbus::plugins::{
@@ -334,81 +334,38 @@ async fn fetch_conversations_fallback(conversations_proxy: &ConversationsProxy<'
     Message::ConversationsLoaded(best_result)
 }
 
-/// Fetch older messages for pagination (starting from a given offset).
-pub async fn fetch_older_messages_async(
-    conn: Arc<Mutex<Connection>>,
-    device_id: String,
+/// Request messages `start..end` of a thread and collect them from the
+/// `conversationUpdated` signals, oldest first. Also returns the thread's
+/// total message count from `conversationLoaded`, or `None` if that signal
+/// didn't arrive within `timeout`.
+async fn load_thread_messages(
+    conversations_proxy: &ConversationsProxy<'_>,
     thread_id: i64,
-    start_index: u32,
-    count: u32,
-) -> Message {
-    let conn = conn.lock().await;
-
-    // The conversations interface is on the device path
-    let device_path = format!("{}/devices/{}", kdeconnect_dbus::BASE_PATH, device_id);
-
-    // Build conversations proxy on the device path
-    let conversations_proxy = match ConversationsProxy::builder(&conn)
-        .path(device_path.as_str())
-        .ok()
-        .map(|b| b.build())
-    {
-        Some(fut) => match fut.await {
-            Ok(p) => p,
-            Err(e) => {
-                tracing::warn!("Failed to create conversations proxy: {}", e);
-                return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
-            }
-        },
-        None => {
-            return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
-        }
-    };
-
+    start: i32,
+    end: i32,
+    timeout: tokio::time::Duration,
+) -> Result<(Vec<SmsMessage>, Option<u64>), String> {
     // Set up signal stream for conversationUpdated BEFORE requesting
-    let mut updated_stream = match conversations_proxy.receive_conversation_updated().await {
-        Ok(stream) => stream,
-        Err(e) => {
-            tracing::warn!(
-                "Failed to subscribe to conversationUpdated for older messages: {}",
-                e
-            );
-            return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
-        }
-    };
+    let mut updated_stream = conversations_proxy
+        .receive_conversation_updated()
+        .await
+        .map_err(|e| format!("Failed to subscribe to conversationUpdated: {}", e))?;
 
     // Set up signal stream for conversationLoaded
-    let mut loaded_stream = match conversations_proxy.receive_conversation_loaded().await {
-        Ok(stream) => stream,
-        Err(e) => {
-            tracing::warn!(
-                "Failed to subscribe to conversationLoaded for older messages: {}",
-                e
-            );
-            return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
-        }
-    };
+    let mut loaded_stream = conversations_proxy
+        .receive_conversation_loaded()
+        .await
+        .map_err(|e| format!("Failed to subscribe to conversationLoaded: {}", e))?;
 
-    // Request the specific conversation with pagination offset
-    tracing::debug!(
-        "Requesting older messages for thread {} (messages {}-{})",
-        thread_id,
-        start_index,
-        start_index + count
-    );
-    if let Err(e) = conversations_proxy
-        .request_conversation(thread_id, start_index as i32, count as i32)
+    conversations_proxy
+        .request_conversation(thread_id, start, end)
         .await
-    {
-        tracing::warn!("Failed to request older messages: {}", e);
-        return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
-    }
+        .map_err(|e| format!("Failed to request messages: {}", e))?;
 
     // Collect messages from signals until conversationLoaded or timeout
     // Use uid (unique message ID) as key for reliable deduplication
     let mut messages_map: HashMap<i32, SmsMessage> = HashMap::new();
     let mut total_message_count: Option<u64> = None;
-    let timeout = tokio::time::Duration::from_secs(MESSAGE_FETCH_TIMEOUT_SECS);
     let start_time = tokio::time::Instant::now();
 
     loop {
@@ -422,7 +379,7 @@ pub async fn fetch_older_messages_async(
                                 // Use uid as key for reliable deduplication
                                 messages_map.insert(msg.uid, msg);
                                 tracing::debug!(
-                                    "Received older message for thread {}, total: {}",
+                                    "Received message for thread {}, total: {}",
                                     thread_id,
                                     messages_map.len()
                                 );
@@ -442,7 +399,7 @@ pub async fn fetch_older_messages_async(
                             // Capture the total message count for pagination
                             total_message_count = Some(args.message_count);
                             tracing::info!(
-                                "Older messages loaded for thread {}, total {} messages, got {}",
+                                "Messages loaded for thread {}, total {} messages, got {}",
                                 thread_id,
                                 args.message_count,
                                 messages_map.len()
@@ -476,7 +433,8 @@ pub async fn fetch_older_messages_async(
             // Timeout
             _ = tokio::time::sleep_until(start_time + timeout) => {
                 tracing::warn!(
-                    "Timeout waiting for older messages, got {} messages",
+                    "Timeout waiting for messages of thread {}, got {}",
+                    thread_id,
                     messages_map.len()
                 );
                 break;
@@ -484,9 +442,63 @@ pub async fn fetch_older_messages_async(
         }
     }
 
-    // Convert map to sorted vector (oldest first)
     let mut messages: Vec<SmsMessage> = messages_map.into_values().collect();
     messages.sort_by_key(|m| m.date);
+    Ok((messages, total_message_count))
+}
+
+/// Fetch older messages for pagination (starting from a given offset).
+pub async fn fetch_older_messages_async(
+    conn: Arc<Mutex<Connection>>,
+    device_id: String,
+    thread_id: i64,
+    start_index: u32,
+    count: u32,
+) -> Message {
+    let conn = conn.lock().await;
+
+    // The conversations interface is on the device path
+    let device_path = format!("{}/devices/{}", kdeconnect_dbus::BASE_PATH, device_id);
+
+    // Build conversations proxy on the device path
+    let conversations_proxy = match ConversationsProxy::builder(&conn)
+        .path(device_path.as_str())
+        .ok()
+        .map(|b| b.build())
+    {
+        Some(fut) => match fut.await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::warn!("Failed to create conversations proxy: {}", e);
+                return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
+            }
+        },
+        None => {
+            return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
+        }
+    };
+
+    tracing::debug!(
+        "Requesting older messages for thread {} (messages {}-{})",
+        thread_id,
+        start_index,
+        start_index + count
+    );
+    let (messages, total_message_count) = match load_thread_messages(
+        &conversations_proxy,
+        thread_id,
+        start_index as i32,
+        count as i32,
+        tokio::time::Duration::from_secs(MESSAGE_FETCH_TIMEOUT_SECS),
+    )
+    .await
+    {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            tracing::warn!("Failed to load older messages: {}", e);
+            return Message::OlderMessagesLoaded(thread_id, Vec::new(), false, None);
+        }
+    };
 
     // Determine if there are more messages available using heuristic
     // (will be overridden by total_message_count if available)
@@ -502,6 +514,55 @@ pub async fn fetch_older_messages_async(
     Message::OlderMessagesLoaded(thread_id, messages, has_more_heuristic, total_message_count)
 }
 
+/// Load the full history of `thread_ids` for an export. Returns the messages
+/// and the threads that didn't arrive in full.
+pub async fn fetch_threads_for_export_async(
+    conn: Arc<Mutex<Connection>>,
+    device_id: String,
+    thread_ids: Vec<i64>,
+) -> (Vec<SmsMessage>, Vec<i64>) {
+    // Loading every thread takes a while; hold the shared connection only
+    // long enough to clone it.
+    let conn = conn.lock().await.clone();
+    let device_path = format!("{}/devices/{}", kdeconnect_dbus::BASE_PATH, device_id);
+    let conversations_proxy = match ConversationsProxy::builder(&conn).path(device_path.as_str()) {
+        Ok(builder) => builder.build().await,
+        Err(e) => Err(e),
+    };
+    let conversations_proxy = match conversations_proxy {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            tracing::warn!("Failed to create conversations proxy for export: {}", e);
+            return (Vec::new(), thread_ids);
+        }
+    };
+
+    let mut messages = Vec::new();
+    let mut incomplete = Vec::new();
+    for thread_id in thread_ids {
+        match load_thread_messages(
+            &conversations_proxy,
+            thread_id,
+            0,
+            i32::MAX,
+            tokio::time::Duration::from_secs(EXPORT_THREAD_TIMEOUT_SECS),
+        )
+        .await
+        {
+            Ok((thread, Some(total))) if thread.len() as u64 >= total => messages.extend(thread),
+            Ok((thread, _)) => {
+                messages.extend(thread);
+                incomplete.push(thread_id);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load thread {} for export: {}", thread_id, e);
+                incomplete.push(thread_id);
+            }
+        }
+    }
+    (messages, incomplete)
+}
+
 /// Timeout for waiting for attachment retrieval from phone (seconds).
 const ATTACHMENT_TIMEOUT_SECS: u64 = 30;
 
@@ -0,0 +1,283 @@
+//! Local pin / mute / archive state for conversations.
+//!
+//! The phone knows nothing about these; they're kept per device in
+//! `$XDG_DATA_HOME/<APP_ID>/sms/conversation_flags.json`. Entries are keyed
+//! by the conversation's canonical address set rather than a thread ID, so
+//! they hold across reaction-thread merging (which changes the primary
+//! thread) and across the phone re-numbering threads. Phone numbers in the
+//! key are in E.164 form, so changing the phone-number region doesn't
+//! orphan flags on numbers the phone reports with a country code. Each
+//! change is written as a single flag set on the file's current contents,
+//! so applet processes don't undo each other's changes.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use kdeconnect_dbus::phone::{default_region, phone_key_in, to_e164, PhoneRegion};
+use serde::{Deserialize, Serialize};
+
+use crate::config::APP_ID;
+use crate::storage::{load_json, update_json_async};
+
+/// Version of the saved key format. Version 0 keyed phone numbers by
+/// region-dependent [`kdeconnect_dbus::phone_key`].
+const FLAGS_VERSION: u32 = 1;
+
+/// A single local conversation flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationFlag {
+    /// Listed above all other conversations.
+    Pinned,
+    /// No desktop notification for new messages.
+    Muted,
+    /// Hidden behind the "Archived" section of the list.
+    Archived,
+}
+
+/// Flags set on one conversation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationFlags {
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl ConversationFlags {
+    fn slot(&mut self, flag: ConversationFlag) -> &mut bool {
+        match flag {
+            ConversationFlag::Pinned => &mut self.pinned,
+            ConversationFlag::Muted => &mut self.muted,
+            ConversationFlag::Archived => &mut self.archived,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Stable key for a conversation's participants: the E.164 address set
+/// (bare digits for short codes), or the lower-cased raw addresses when none
+/// are phone numbers (e.g. email-only MMS).
+pub fn conversation_key(addresses: &[String]) -> String {
+    conversation_key_in(addresses, default_region())
+}
+
+/// [`conversation_key`] reading numbers without a country code in `region`.
+fn conversation_key_in(addresses: &[String], region: &PhoneRegion) -> String {
+    let canonical: BTreeSet<String> = addresses
+        .iter()
+        .filter_map(|addr| {
+            let key = phone_key_in(addr, region);
+            (!key.is_empty()).then(|| to_e164(addr, region).unwrap_or(key))
+        })
+        .collect();
+    if !canonical.is_empty() {
+        return canonical.into_iter().collect::<Vec<_>>().join(",");
+    }
+    let mut raw: Vec<String> = addresses
+        .iter()
+        .map(|a| a.trim().to_lowercase())
+        .filter(|a| !a.is_empty())
+        .collect();
+    raw.sort();
+    raw.dedup();
+    raw.join(",")
+}
+
+/// Flags for every device, keyed by device ID then [`conversation_key`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConversationFlagStore {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub devices: HashMap<String, HashMap<String, ConversationFlags>>,
+}
+
+impl ConversationFlagStore {
+    /// Bring keys saved by an older version up to date. Old keys hold
+    /// national numbers read in `region`, which must be the region they
+    /// were written in.
+    fn migrate(&mut self, region: &PhoneRegion) {
+        if self.version >= FLAGS_VERSION {
+            return;
+        }
+        for flags in self.devices.values_mut() {
+            let mut rekeyed: HashMap<String, ConversationFlags> = HashMap::new();
+            for (key, value) in flags.drain() {
+                let parts: Vec<String> = key.split(',').map(str::to_string).collect();
+                let entry = rekeyed
+                    .entry(conversation_key_in(&parts, region))
+                    .or_default();
+                entry.pinned |= value.pinned;
+                entry.muted |= value.muted;
+                entry.archived |= value.archived;
+            }
+            *flags = rekeyed;
+        }
+        self.version = FLAGS_VERSION;
+    }
+
+    pub fn get(&self, device_id: &str, addresses: &[String]) -> ConversationFlags {
+        self.devices
+            .get(device_id)
+            .and_then(|flags| flags.get(&conversation_key(addresses)))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Flip `flag` on a conversation. Returns the new value.
+    pub fn toggle(
+        &mut self,
+        device_id: &str,
+        addresses: &[String],
+        flag: ConversationFlag,
+    ) -> bool {
+        let on = !*self.get(device_id, addresses).slot(flag);
+        self.set(device_id, addresses, flag, on);
+        on
+    }
+
+    /// Set `flag` on a conversation. Returns whether it changed.
+    pub fn set(
+        &mut self,
+        device_id: &str,
+        addresses: &[String],
+        flag: ConversationFlag,
+        on: bool,
+    ) -> bool {
+        let key = conversation_key(addresses);
+        let device = self.devices.entry(device_id.to_string()).or_default();
+        let flags = device.entry(key.clone()).or_default();
+        let changed = std::mem::replace(flags.slot(flag), on) != on;
+        if flags.is_empty() {
+            device.remove(&key);
+            if device.is_empty() {
+                self.devices.remove(device_id);
+            }
+        }
+        changed
+    }
+}
+
+fn flags_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_ID).join("sms").join("conversation_flags.json"))
+}
+
+/// Load saved flags. A missing or unreadable file yields none.
+pub async fn load_flags_async() -> ConversationFlagStore {
+    let Some(path) = flags_path() else {
+        return ConversationFlagStore::default();
+    };
+    let mut flags: ConversationFlagStore = load_json(&path, "conversation flags").await;
+    flags.migrate(default_region());
+    flags
+}
+
+/// Set one flag in the file. Returns the file's flags after the write.
+pub async fn save_flag_async(
+    device_id: String,
+    addresses: Vec<String>,
+    flag: ConversationFlag,
+    on: bool,
+) -> Result<ConversationFlagStore, String> {
+    let path = flags_path().ok_or("No XDG data directory")?;
+    update_json_async(
+        path,
+        "conversation flags",
+        move |saved: &mut ConversationFlagStore| {
+            let migrating = saved.version < FLAGS_VERSION;
+            saved.migrate(default_region());
+            saved.set(&device_id, &addresses, flag, on) || migrating
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn key_ignores_formatting_and_order() {
+        assert_eq!(
+            conversation_key(&addrs(&["+1 (555) 123-4567", "555-987-6543"])),
+            conversation_key(&addrs(&["5559876543", "15551234567"]))
+        );
+        assert_eq!(
+            conversation_key(&addrs(&["Bob@Example.com"])),
+            "bob@example.com"
+        );
+    }
+
+    #[test]
+    fn toggle_survives_merged_address_variants() {
+        let mut store = ConversationFlagStore::default();
+        assert!(store.toggle("dev", &addrs(&["+15551234567"]), ConversationFlag::Pinned));
+        let flags = store.get("dev", &addrs(&["(555) 123-4567"]));
+        assert!(flags.pinned);
+        assert!(!flags.muted);
+        assert!(!store.get("other", &addrs(&["+15551234567"])).pinned);
+    }
+
+    #[test]
+    fn clearing_last_flag_prunes_entry() {
+        let mut store = ConversationFlagStore::default();
+        let a = addrs(&["5551234567"]);
+        store.toggle("dev", &a, ConversationFlag::Muted);
+        assert!(!store.toggle("dev", &a, ConversationFlag::Muted));
+        assert!(store.devices.is_empty());
+        assert!(!store.set("dev", &a, ConversationFlag::Muted, false));
+        assert!(store.devices.is_empty());
+    }
+
+    #[test]
+    fn key_survives_region_change() {
+        let us = PhoneRegion::from_code("US").unwrap();
+        let se = PhoneRegion::from_code("SE").unwrap();
+        assert_eq!(
+            conversation_key_in(&addrs(&["+1 555 123 4567", "+46 70 123 45 67"]), us),
+            conversation_key_in(&addrs(&["+15551234567", "070-123 45 67"]), se)
+        );
+        assert_eq!(conversation_key_in(&addrs(&["12345"]), us), "12345");
+    }
+
+    #[test]
+    fn migrates_region_dependent_keys() {
+        let us = PhoneRegion::from_code("US").unwrap();
+        let mut store = ConversationFlagStore::default();
+        let mut flags = HashMap::new();
+        let pinned = ConversationFlags {
+            pinned: true,
+            ..Default::default()
+        };
+        let muted = ConversationFlags {
+            muted: true,
+            ..Default::default()
+        };
+        flags.insert("+46701234567,5551234567".to_string(), pinned);
+        flags.insert("5559876543".to_string(), muted);
+        flags.insert("bob@example.com".to_string(), pinned);
+        store.devices.insert("dev".to_string(), flags);
+
+        store.migrate(us);
+        assert_eq!(store.version, FLAGS_VERSION);
+        let flags = &store.devices["dev"];
+        assert_eq!(flags["+15551234567,+46701234567"], pinned);
+        assert_eq!(flags["+15559876543"], muted);
+        assert_eq!(flags["bob@example.com"], pinned);
+
+        // Already migrated keys are left alone
+        let before = store.devices.clone();
+        store.version = 0;
+        store.migrate(us);
+        assert_eq!(store.devices, before);
+    }
+}
@@ -9,6 +9,7 @@
 
 use std::collections::{BTreeSet, HashMap, HashSet};
 
+use kdeconnect_dbus::phone_key;
 use kdeconnect_dbus::plugins::ConversationSummary;
 
 /// A user-perceived conversation, possibly composed of multiple underlying
@@ -38,8 +39,6 @@ pub struct LogicalConversation {
     /// Whether the most recent message is an MMS with attachments.
     pub has_attachments: bool,
     /// Sum of underlying threads currently flagged unread.
-    #[allow(dead_code)]
-    // Deferred to v0.6.0+ when the unread display work lands.
     pub unread_count: usize,
     /// True when this conversation's underlying thread(s) participate in a
     /// phone-side reaction-bucket group (per [`is_reaction_bucket`]).
@@ -69,16 +68,12 @@ impl LogicalConversation {
     }
 }
 
-/// Strip non-digit characters; if the result has more than 10 digits and
-/// starts with `1` (US country code), drop the leading `1`. Mirrors
-/// `analyze.py::normalize_addr`.
+/// Canonical form of one address: [`phone_key`] in the configured region,
+/// so `+46 70 123 45 67` and `070-123 45 67` agree. For North American
+/// numbers this matches `analyze.py::normalize_addr` (digits only, leading
+/// `1` dropped from 11-digit numbers).
 pub(crate) fn normalize_addr(addr: &str) -> String {
-    let digits: String = addr.chars().filter(|c| c.is_ascii_digit()).collect();
-    if digits.len() > 10 && digits.starts_with('1') {
-        digits[1..].to_string()
-    } else {
-        digits
-    }
+    phone_key(addr)
 }
 
 /// Canonical address set: normalize each address, drop empties, deduplicate.
@@ -1,9 +1,19 @@
 //! SMS-related functionality for KDE Connect conversations.
 
+pub mod archive;
+pub mod attach;
+pub mod contacts;
 pub mod conversation_subscription;
+pub mod drafts;
+pub mod export;
 pub mod fetch;
+pub mod flags;
 pub mod logical;
+pub mod reactions;
+pub mod scheduled;
+pub mod search;
 pub mod send;
+pub mod sim;
 pub mod store;
 pub mod views;
 
@@ -0,0 +1,449 @@
+//! iOS tapback reactions sent over SMS.
+//!
+//! When an iPhone reacts to a message from an Android phone, it sends a
+//! plain SMS such as `Liked “see you at 5”` in the reacting user's language.
+//! [`collect_reactions`] finds those messages in a thread, pairs each with the
+//! quoted original, and folds them into per-message emoji badges so the
+//! thread view can show them on the original instead of as bubbles.
+//!
+//! Phrases are matched against the English, Czech and Swedish iOS
+//! wordings — the languages the applet ships in.
+
+use std::collections::{HashMap, HashSet};
+
+use kdeconnect_dbus::plugins::{MessageType, SmsMessage};
+
+/// The six classic tapbacks, plus iOS 18's arbitrary emoji reactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TapbackKind {
+    Love,
+    Like,
+    Dislike,
+    Laugh,
+    Emphasize,
+    Question,
+    Emoji(String),
+}
+
+impl TapbackKind {
+    pub fn emoji(&self) -> &str {
+        match self {
+            TapbackKind::Love => "❤️",
+            TapbackKind::Like => "👍",
+            TapbackKind::Dislike => "👎",
+            TapbackKind::Laugh => "😂",
+            TapbackKind::Emphasize => "‼️",
+            TapbackKind::Question => "❓",
+            TapbackKind::Emoji(emoji) => emoji,
+        }
+    }
+}
+
+/// What a tapback points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TapbackTarget {
+    /// Quoted message text, possibly truncated with `…`.
+    Text(String),
+    /// "an image", "a movie", … — the most recent attachment.
+    Attachment,
+}
+
+/// A parsed reaction message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tapback {
+    pub kind: TapbackKind,
+    /// `Removed a heart from …` rather than `Loved …`.
+    pub removed: bool,
+    pub target: TapbackTarget,
+}
+
+/// `Loved “…”` style prefixes, per language.
+const ADDED: &[(&str, TapbackKind)] = &[
+    // English
+    ("Loved", TapbackKind::Love),
+    ("Liked", TapbackKind::Like),
+    ("Disliked", TapbackKind::Dislike),
+    ("Laughed at", TapbackKind::Laugh),
+    ("Emphasized", TapbackKind::Emphasize),
+    ("Emphasised", TapbackKind::Emphasize),
+    ("Questioned", TapbackKind::Question),
+    // Czech
+    ("Miluje", TapbackKind::Love),
+    ("Líbí se", TapbackKind::Like),
+    ("Nelíbí se", TapbackKind::Dislike),
+    ("Směje se", TapbackKind::Laugh),
+    ("Zdůrazňuje", TapbackKind::Emphasize),
+    ("Zpochybňuje", TapbackKind::Question),
+    // Swedish
+    ("Älskade", TapbackKind::Love),
+    ("Gillade", TapbackKind::Like),
+    ("Ogillade", TapbackKind::Dislike),
+    ("Skrattade åt", TapbackKind::Laugh),
+    ("Betonade", TapbackKind::Emphasize),
+    ("Ifrågasatte", TapbackKind::Question),
+];
+
+/// `Removed a heart from “…”` style prefixes, per language.
+const REMOVED: &[(&str, TapbackKind)] = &[
+    // English
+    ("Removed a heart from", TapbackKind::Love),
+    ("Removed a like from", TapbackKind::Like),
+    ("Removed a dislike from", TapbackKind::Dislike),
+    ("Removed a laugh from", TapbackKind::Laugh),
+    ("Removed an exclamation from", TapbackKind::Emphasize),
+    ("Removed a question mark from", TapbackKind::Question),
+    // Czech
+    ("Odstraňuje srdce z", TapbackKind::Love),
+    ("Odstraňuje palec nahoru z", TapbackKind::Like),
+    ("Odstraňuje palec dolů z", TapbackKind::Dislike),
+    ("Odstraňuje smích z", TapbackKind::Laugh),
+    ("Odstraňuje vykřičník z", TapbackKind::Emphasize),
+    ("Odstraňuje otazník z", TapbackKind::Question),
+    // Swedish
+    ("Tog bort ett hjärta från", TapbackKind::Love),
+    ("Tog bort en tumme upp från", TapbackKind::Like),
+    ("Tog bort en tumme ned från", TapbackKind::Dislike),
+    ("Tog bort ett skratt från", TapbackKind::Laugh),
+    ("Tog bort ett utropstecken från", TapbackKind::Emphasize),
+    ("Tog bort ett frågetecken från", TapbackKind::Question),
+];
+
+/// iOS 18 emoji reactions: `Reacted 😀 to “…”`, `Removed 😀 from “…”`.
+/// Each entry is (prefix, separator before the target, removed).
+const EMOJI_FORMS: &[(&str, &str, bool)] = &[
+    ("Reacted", "to", false),
+    ("Removed", "from", true),
+    ("Reaguje", "na", false),
+    ("Odstraňuje", "z", true),
+    ("Reagerade med", "på", false),
+    ("Tog bort", "från", true),
+];
+
+/// What iOS writes instead of a quote when reacting to an attachment.
+const ATTACHMENT_NOUNS: &[&str] = &[
+    "an image",
+    "a movie",
+    "a video",
+    "a photo",
+    "an attachment",
+    "an audio message",
+    "a sticker",
+    "obrázek",
+    "fotku",
+    "video",
+    "přílohu",
+    "zvukovou zprávu",
+    "en bild",
+    "ett foto",
+    "en film",
+    "en video",
+    "en bilaga",
+    "ett ljudmeddelande",
+];
+
+const OPEN_QUOTES: &[char] = &['“', '”', '„', '"', '«', '‘', '\''];
+const CLOSE_QUOTES: &[char] = &['”', '“', '"', '»', '’', '\''];
+
+/// Parse the target after a reaction prefix: a quoted text or an
+/// attachment noun. Anything else means the message isn't a reaction.
+fn parse_target(rest: &str) -> Option<TapbackTarget> {
+    let rest = rest.trim();
+    if ATTACHMENT_NOUNS.contains(&rest) {
+        return Some(TapbackTarget::Attachment);
+    }
+    let inner = rest.strip_prefix(OPEN_QUOTES)?.strip_suffix(CLOSE_QUOTES)?;
+    Some(TapbackTarget::Text(inner.to_string()))
+}
+
+/// `body` with `prefix` and one following space stripped.
+fn after_word<'a>(body: &'a str, prefix: &str) -> Option<&'a str> {
+    body.strip_prefix(prefix)?.strip_prefix(' ')
+}
+
+/// Recognise a tapback message body.
+pub fn parse_tapback(body: &str) -> Option<Tapback> {
+    let body = body.trim();
+    // Removal prefixes first: "Removed a heart from" must not fall through
+    // to the iOS 18 "Removed <emoji> from" form.
+    for (removed, table) in [(true, REMOVED), (false, ADDED)] {
+        for (prefix, kind) in table {
+            if let Some(target) = after_word(body, prefix).and_then(parse_target) {
+                return Some(Tapback {
+                    kind: kind.clone(),
+                    removed,
+                    target,
+                });
+            }
+        }
+    }
+    for &(prefix, separator, removed) in EMOJI_FORMS {
+        let Some(rest) = after_word(body, prefix) else {
+            continue;
+        };
+        let Some((emoji, target)) = rest.split_once(&format!(" {} ", separator)) else {
+            continue;
+        };
+        // Emoji are a few code points at most; a longer run is prose.
+        if emoji.is_empty() || emoji.chars().count() > 8 || emoji.chars().any(char::is_alphanumeric)
+        {
+            continue;
+        }
+        if let Some(target) = parse_target(target) {
+            return Some(Tapback {
+                kind: TapbackKind::Emoji(emoji.to_string()),
+                removed,
+                target,
+            });
+        }
+    }
+    None
+}
+
+/// One emoji shown under a message, with how many people reacted with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionBadge {
+    pub emoji: String,
+    pub count: usize,
+}
+
+/// Reactions folded onto their originals for one thread.
+#[derive(Debug, Default)]
+pub struct ThreadReactions {
+    /// Badges keyed by the uid of the message reacted to.
+    pub badges: HashMap<i32, Vec<ReactionBadge>>,
+    /// Uids of reaction messages that were matched and so shouldn't be
+    /// drawn as bubbles. Unmatched ones (original not loaded) stay visible.
+    pub absorbed: HashSet<i32>,
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether `body` is the message a reaction quoted as `quote`. iOS
+/// truncates long quotes with `…`.
+fn quotes(quote: &str, body: &str) -> bool {
+    let quote = collapse_whitespace(quote);
+    let body = collapse_whitespace(body);
+    match quote.strip_suffix('…') {
+        Some(head) if !head.is_empty() => body.starts_with(head.trim_end()),
+        _ => !body.is_empty() && body == quote,
+    }
+}
+
+/// Who sent a message, for "one reaction per person" bookkeeping.
+fn sender(message: &SmsMessage) -> &str {
+    match message.message_type {
+        MessageType::Sent => "",
+        MessageType::Inbox => message.primary_address(),
+    }
+}
+
+/// Pair each tapback in `messages` (oldest first) with the nearest earlier
+/// message it quotes. As on iOS, a person's new reaction to a message
+/// replaces their previous one and a removal clears it.
+pub fn collect_reactions(messages: &[SmsMessage]) -> ThreadReactions {
+    let mut reactions = ThreadReactions::default();
+    // (target uid, sender, emoji), in the order reactions were first made
+    let mut active: Vec<(i32, String, String)> = Vec::new();
+
+    for (index, message) in messages.iter().enumerate() {
+        let Some(tapback) = parse_tapback(&message.body) else {
+            continue;
+        };
+        let target = messages[..index].iter().rev().find(|m| {
+            !reactions.absorbed.contains(&m.uid)
+                && match &tapback.target {
+                    TapbackTarget::Text(quote) => quotes(quote, &m.body),
+                    TapbackTarget::Attachment => !m.attachments.is_empty(),
+                }
+        });
+        let Some(target) = target else {
+            continue;
+        };
+        reactions.absorbed.insert(message.uid);
+
+        let who = sender(message);
+        let existing = active
+            .iter()
+            .position(|(uid, s, _)| *uid == target.uid && s == who);
+        match (tapback.removed, existing) {
+            (true, Some(pos)) => {
+                active.remove(pos);
+            }
+            (true, None) => {}
+            (false, Some(pos)) => active[pos].2 = tapback.kind.emoji().to_string(),
+            (false, None) => active.push((
+                target.uid,
+                who.to_string(),
+                tapback.kind.emoji().to_string(),
+            )),
+        }
+    }
+
+    for (uid, _, emoji) in active {
+        let badges = reactions.badges.entry(uid).or_default();
+        match badges.iter_mut().find(|b| b.emoji == emoji) {
+            Some(badge) => badge.count += 1,
+            None => badges.push(ReactionBadge { emoji, count: 1 }),
+        }
+    }
+    reactions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdeconnect_dbus::plugins::Attachment;
+
+    fn msg(uid: i32, message_type: MessageType, from: &str, body: &str) -> SmsMessage {
+        SmsMessage {
+            body: body.to_string(),
+            addresses: vec![from.to_string()],
+            date: 1_700_000_000_000 + i64::from(uid),
+            message_type,
+            raw_type: message_type as i32,
+            read: true,
+            thread_id: 1,
+            uid,
+            sub_id: -1,
+            attachments: vec![],
+        }
+    }
+
+    #[test]
+    fn parses_each_language() {
+        let liked = parse_tapback("Liked “see you at 5”").unwrap();
+        assert_eq!(liked.kind, TapbackKind::Like);
+        assert!(!liked.removed);
+        assert_eq!(liked.target, TapbackTarget::Text("see you at 5".into()));
+
+        assert_eq!(
+            parse_tapback("Gillade ”hej”").unwrap().kind,
+            TapbackKind::Like
+        );
+        assert_eq!(
+            parse_tapback("Miluje „ahoj“").unwrap().kind,
+            TapbackKind::Love
+        );
+        assert_eq!(
+            parse_tapback("Loved an image").unwrap().target,
+            TapbackTarget::Attachment
+        );
+    }
+
+    #[test]
+    fn parses_removals_and_emoji_reactions() {
+        let removed = parse_tapback("Removed a heart from “hi”").unwrap();
+        assert_eq!(removed.kind, TapbackKind::Love);
+        assert!(removed.removed);
+
+        let emoji = parse_tapback("Reacted 🎉 to “we won”").unwrap();
+        assert_eq!(emoji.kind, TapbackKind::Emoji("🎉".into()));
+        assert!(parse_tapback("Removed 🎉 from “we won”").unwrap().removed);
+    }
+
+    #[test]
+    fn ordinary_messages_are_not_reactions() {
+        assert!(parse_tapback("Liked the movie a lot").is_none());
+        assert!(parse_tapback("Loved it").is_none());
+        assert!(parse_tapback("Reacted badly to “the news”").is_none());
+        assert!(parse_tapback("").is_none());
+    }
+
+    #[test]
+    fn folds_reactions_onto_quoted_original() {
+        let messages = vec![
+            msg(1, MessageType::Sent, "+15551234567", "see you at 5"),
+            msg(
+                2,
+                MessageType::Inbox,
+                "+15551234567",
+                "Liked “see you at 5”",
+            ),
+            msg(
+                3,
+                MessageType::Inbox,
+                "+15559876543",
+                "Liked “see you at 5”",
+            ),
+            msg(
+                4,
+                MessageType::Inbox,
+                "+15551234567",
+                "Loved “nobody said this”",
+            ),
+        ];
+        let reactions = collect_reactions(&messages);
+        assert_eq!(
+            reactions.badges[&1],
+            vec![ReactionBadge {
+                emoji: "👍".into(),
+                count: 2
+            }]
+        );
+        assert_eq!(reactions.absorbed, HashSet::from([2, 3]));
+    }
+
+    #[test]
+    fn later_reaction_replaces_and_removal_clears() {
+        let messages = vec![
+            msg(
+                1,
+                MessageType::Inbox,
+                "+15551234567",
+                "A rather long message that iOS will shorten",
+            ),
+            msg(
+                2,
+                MessageType::Sent,
+                "+15551234567",
+                "Liked “A rather long message…”",
+            ),
+            msg(
+                3,
+                MessageType::Sent,
+                "+15551234567",
+                "Laughed at “A rather long message…”",
+            ),
+            msg(
+                4,
+                MessageType::Inbox,
+                "+15551234567",
+                "Emphasized “A rather long message…”",
+            ),
+            msg(
+                5,
+                MessageType::Inbox,
+                "+15551234567",
+                "Removed an exclamation from “A rather long message…”",
+            ),
+        ];
+        let reactions = collect_reactions(&messages);
+        assert_eq!(
+            reactions.badges[&1],
+            vec![ReactionBadge {
+                emoji: "😂".into(),
+                count: 1
+            }]
+        );
+        assert_eq!(reactions.absorbed.len(), 4);
+    }
+
+    #[test]
+    fn attachment_reactions_use_latest_attachment() {
+        let mut photo = msg(1, MessageType::Inbox, "+15551234567", "");
+        photo.attachments.push(Attachment {
+            part_id: 1,
+            mime_type: "image/jpeg".into(),
+            base64_thumbnail: String::new(),
+            unique_identifier: "x".into(),
+        });
+        let messages = vec![
+            photo,
+            msg(2, MessageType::Sent, "+15551234567", "nice"),
+            msg(3, MessageType::Sent, "+15551234567", "Loved an image"),
+        ];
+        assert!(collect_reactions(&messages).badges.contains_key(&1));
+    }
+}
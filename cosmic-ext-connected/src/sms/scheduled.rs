@@ -0,0 +1,454 @@
+//! Scheduled ("Send later") SMS.
+//!
+//! Scheduled messages for every device live in one JSON file under
+//! `$XDG_DATA_HOME/<APP_ID>/sms/`. A timer in the store checks for due
+//! messages and sends them once the target device is reachable, so a
+//! message due while the phone was away goes out when it returns. A failed
+//! send is retried with a growing delay; after `SCHEDULED_MAX_ATTEMPTS` the
+//! message is marked as not sent until the user retries or cancels it.
+//!
+//! Every applet process runs the timer, so a process only sends a message
+//! after claiming it in the file. All changes are [`ScheduledChange`]s
+//! applied to the file under its lock, never a rewrite of one process's
+//! in-memory list.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::config::APP_ID;
+use crate::constants::sms::{
+    SCHEDULED_CLAIM_EXPIRY_MS, SCHEDULED_MAX_ATTEMPTS, SCHEDULED_RETRY_BASE_MS,
+};
+use crate::storage::{load_json, update_json_async};
+
+/// Hour of day used by the "tomorrow morning" preset.
+const MORNING_HOUR: u32 = 8;
+
+/// Where a scheduled message goes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduledTarget {
+    /// Reply in an existing conversation. `addresses` lets it go out via
+    /// `sendWithoutConversation` when the daemon hasn't loaded the thread.
+    Thread {
+        thread_id: i64,
+        addresses: Vec<String>,
+    },
+    /// First message to a new set of recipients.
+    NewConversation { recipients: Vec<String> },
+}
+
+/// An applet process sending a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendClaim {
+    pub pid: u32,
+    /// Unix-millis time of the claim; identifies the claiming tick.
+    pub at_ms: i64,
+}
+
+impl SendClaim {
+    /// A claim by this process at `at_ms`.
+    pub fn ours(at_ms: i64) -> Self {
+        Self {
+            pid: std::process::id(),
+            at_ms,
+        }
+    }
+}
+
+/// A message waiting for its send time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledMessage {
+    /// Unique per message; the creation time in milliseconds.
+    pub id: i64,
+    pub device_id: String,
+    pub target: ScheduledTarget,
+    pub body: String,
+    /// `file://` URLs of MMS attachments.
+    #[serde(default)]
+    pub attachment_urls: Vec<String>,
+    /// Unix-millis send time.
+    pub due_ms: i64,
+    /// SIM override chosen when scheduling; `None` lets the phone pick.
+    #[serde(default)]
+    pub sub_id: Option<i64>,
+    /// Error from the last send attempt.
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Failed send attempts so far.
+    #[serde(default)]
+    pub attempts: u32,
+    /// Unix-millis time of the next attempt after a failure.
+    #[serde(default)]
+    pub retry_at_ms: i64,
+    /// Set while a process is sending the message.
+    #[serde(default)]
+    pub claim: Option<SendClaim>,
+}
+
+impl ScheduledMessage {
+    /// Whether the message should be sent now: its time has come, any
+    /// retry delay has passed, it hasn't given up, and no process is
+    /// sending it.
+    pub fn is_due(&self, now_ms: i64) -> bool {
+        self.due_ms <= now_ms
+            && self.retry_at_ms <= now_ms
+            && !self.has_failed()
+            && !self.is_claimed(now_ms)
+    }
+
+    /// Whether a process is sending the message. A claim older than
+    /// `SCHEDULED_CLAIM_EXPIRY_MS` is from a process that exited mid-send
+    /// and no longer counts.
+    pub fn is_claimed(&self, now_ms: i64) -> bool {
+        self.claim
+            .is_some_and(|claim| now_ms - claim.at_ms < SCHEDULED_CLAIM_EXPIRY_MS)
+    }
+
+    /// Out of attempts; waits for the user to retry or cancel.
+    pub fn has_failed(&self) -> bool {
+        self.attempts >= SCHEDULED_MAX_ATTEMPTS
+    }
+
+    /// Record a failed attempt and schedule the next one.
+    pub fn record_failure(&mut self, error: String, now_ms: i64) {
+        self.last_error = Some(error);
+        self.attempts += 1;
+        let backoff = SCHEDULED_RETRY_BASE_MS << (self.attempts - 1).min(10);
+        self.retry_at_ms = now_ms + backoff;
+    }
+
+    /// Start over after the user pressed Retry.
+    pub fn reset_attempts(&mut self) {
+        self.attempts = 0;
+        self.retry_at_ms = 0;
+    }
+
+    /// Whether this message belongs to the open thread.
+    pub fn is_for_thread(&self, thread_ids: &[i64]) -> bool {
+        matches!(&self.target, ScheduledTarget::Thread { thread_id, .. } if thread_ids.contains(thread_id))
+    }
+
+    /// Send time in local time, e.g. "Tue 08:00".
+    pub fn due_label(&self) -> String {
+        Local
+            .timestamp_millis_opt(self.due_ms)
+            .single()
+            .map(|dt| dt.format("%a %H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// A change to the scheduled-message file.
+#[derive(Debug, Clone)]
+pub enum ScheduledChange {
+    Add(ScheduledMessage),
+    Cancel(i64),
+    /// Start over after the message ran out of attempts.
+    Retry(i64),
+    /// Replace the text; refused once a send is under way.
+    Edit(i64, String),
+    /// Claim the messages among these IDs that are still due, for sending
+    /// by this process.
+    Claim(Vec<i64>, SendClaim),
+    Sent(i64),
+    /// A send attempt failed at the given Unix-millis time.
+    Failed(i64, String, i64),
+}
+
+impl ScheduledChange {
+    /// Apply the change to `scheduled`. Returns whether anything changed.
+    pub fn apply(&self, scheduled: &mut Vec<ScheduledMessage>) -> bool {
+        let find = |scheduled: &mut Vec<ScheduledMessage>, id: i64| {
+            scheduled.iter_mut().position(|s| s.id == id)
+        };
+        match self {
+            ScheduledChange::Add(message) => {
+                if scheduled.iter().any(|s| s.id == message.id) {
+                    return false;
+                }
+                scheduled.push(message.clone());
+                scheduled.sort_by_key(|s| s.due_ms);
+                true
+            }
+            ScheduledChange::Cancel(id) | ScheduledChange::Sent(id) => {
+                let before = scheduled.len();
+                scheduled.retain(|s| s.id != *id);
+                scheduled.len() != before
+            }
+            ScheduledChange::Retry(id) => match find(scheduled, *id) {
+                Some(i) => {
+                    scheduled[i].reset_attempts();
+                    true
+                }
+                None => false,
+            },
+            ScheduledChange::Edit(id, body) => match find(scheduled, *id) {
+                Some(i) => {
+                    let message = &mut scheduled[i];
+                    if message.claim.is_some()
+                        || (body.trim().is_empty() && message.attachment_urls.is_empty())
+                    {
+                        return false;
+                    }
+                    message.body = body.clone();
+                    true
+                }
+                None => false,
+            },
+            ScheduledChange::Claim(ids, claim) => {
+                let mut changed = false;
+                for message in scheduled.iter_mut() {
+                    if ids.contains(&message.id) && message.is_due(claim.at_ms) {
+                        message.claim = Some(*claim);
+                        changed = true;
+                    }
+                }
+                changed
+            }
+            ScheduledChange::Failed(id, error, now_ms) => match find(scheduled, *id) {
+                Some(i) => {
+                    scheduled[i].claim = None;
+                    scheduled[i].record_failure(error.clone(), *now_ms);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// Quick choices in the "Send later" picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulePreset {
+    InOneHour,
+    TomorrowMorning,
+    /// The `HH:MM` typed into the picker, at its next occurrence.
+    Custom,
+}
+
+impl SchedulePreset {
+    /// Send time for this preset, or `None` if a custom time doesn't parse.
+    pub fn due_at(self, now: DateTime<Local>, custom_input: &str) -> Option<DateTime<Local>> {
+        match self {
+            SchedulePreset::InOneHour => Some(now + Duration::hours(1)),
+            SchedulePreset::TomorrowMorning => {
+                let tomorrow = now.date_naive().succ_opt()?;
+                let time = NaiveTime::from_hms_opt(MORNING_HOUR, 0, 0)?;
+                Local
+                    .from_local_datetime(&tomorrow.and_time(time))
+                    .earliest()
+            }
+            SchedulePreset::Custom => {
+                let time = parse_time_of_day(custom_input)?;
+                next_occurrence(now, time)
+            }
+        }
+    }
+}
+
+/// Parse `H:MM` / `HH:MM` (24-hour).
+pub fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    let (hour, minute) = input.trim().split_once(':')?;
+    NaiveTime::from_hms_opt(hour.trim().parse().ok()?, minute.trim().parse().ok()?, 0)
+}
+
+/// Next time the clock reads `time`: today if still ahead, else tomorrow.
+pub fn next_occurrence(now: DateTime<Local>, time: NaiveTime) -> Option<DateTime<Local>> {
+    let today = Local
+        .from_local_datetime(&now.date_naive().and_time(time))
+        .earliest();
+    match today {
+        Some(dt) if dt > now => Some(dt),
+        _ => {
+            let tomorrow = now.date_naive().succ_opt()?;
+            Local
+                .from_local_datetime(&tomorrow.and_time(time))
+                .earliest()
+        }
+    }
+}
+
+fn scheduled_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_ID).join("sms").join("scheduled.json"))
+}
+
+/// Load all scheduled messages. A missing or unreadable file yields none.
+pub async fn load_scheduled_async() -> Vec<ScheduledMessage> {
+    match scheduled_path() {
+        Some(path) => load_json(&path, "scheduled SMS file").await,
+        None => Vec::new(),
+    }
+}
+
+/// Apply `change` to the file and return every scheduled message after it,
+/// including changes made by other applet processes.
+pub async fn update_scheduled_async(
+    change: ScheduledChange,
+) -> Result<Vec<ScheduledMessage>, String> {
+    let path = scheduled_path().ok_or("No XDG data directory")?;
+    update_json_async(path, "scheduled SMS file", move |scheduled| {
+        change.apply(scheduled)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_time_of_day() {
+        assert_eq!(parse_time_of_day("7:05"), NaiveTime::from_hms_opt(7, 5, 0));
+        assert_eq!(
+            parse_time_of_day(" 23:59 "),
+            NaiveTime::from_hms_opt(23, 59, 0)
+        );
+        assert_eq!(parse_time_of_day("24:00"), None);
+        assert_eq!(parse_time_of_day("noon"), None);
+    }
+
+    #[test]
+    fn next_occurrence_rolls_to_tomorrow_when_past() {
+        let now = local(2024, 3, 10, 22, 0);
+        let eight = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        assert_eq!(next_occurrence(now, eight), Some(local(2024, 3, 11, 8, 0)));
+        let late = NaiveTime::from_hms_opt(23, 30, 0).unwrap();
+        assert_eq!(next_occurrence(now, late), Some(local(2024, 3, 10, 23, 30)));
+    }
+
+    #[test]
+    fn presets_compute_due_time() {
+        let now = local(2024, 3, 10, 22, 0);
+        assert_eq!(
+            SchedulePreset::TomorrowMorning.due_at(now, ""),
+            Some(local(2024, 3, 11, 8, 0))
+        );
+        assert_eq!(
+            SchedulePreset::InOneHour.due_at(now, ""),
+            Some(local(2024, 3, 10, 23, 0))
+        );
+        assert_eq!(SchedulePreset::Custom.due_at(now, "bogus"), None);
+    }
+
+    #[test]
+    fn matches_open_thread() {
+        let scheduled = ScheduledMessage {
+            id: 1,
+            device_id: "dev".to_string(),
+            target: ScheduledTarget::Thread {
+                thread_id: 7,
+                addresses: vec![],
+            },
+            body: "hi".to_string(),
+            attachment_urls: vec![],
+            due_ms: 100,
+            sub_id: None,
+            last_error: None,
+            attempts: 0,
+            retry_at_ms: 0,
+            claim: None,
+        };
+        assert!(scheduled.is_for_thread(&[3, 7]));
+        assert!(!scheduled.is_for_thread(&[3]));
+        assert!(scheduled.is_due(100));
+        assert!(!scheduled.is_due(99));
+    }
+
+    #[test]
+    fn failures_back_off_then_give_up() {
+        let mut scheduled = ScheduledMessage {
+            id: 1,
+            device_id: "dev".to_string(),
+            target: ScheduledTarget::NewConversation {
+                recipients: vec!["5551234567".to_string()],
+            },
+            body: "hi".to_string(),
+            attachment_urls: vec![],
+            due_ms: 0,
+            sub_id: None,
+            last_error: None,
+            attempts: 0,
+            retry_at_ms: 0,
+            claim: None,
+        };
+        scheduled.record_failure("offline".to_string(), 1_000);
+        assert!(!scheduled.is_due(1_000));
+        assert!(scheduled.is_due(1_000 + SCHEDULED_RETRY_BASE_MS));
+
+        scheduled.record_failure("offline".to_string(), 2_000);
+        assert!(!scheduled.is_due(2_000 + SCHEDULED_RETRY_BASE_MS));
+        assert!(scheduled.is_due(2_000 + 2 * SCHEDULED_RETRY_BASE_MS));
+
+        for _ in 2..SCHEDULED_MAX_ATTEMPTS {
+            scheduled.record_failure("offline".to_string(), 3_000);
+        }
+        assert!(scheduled.has_failed());
+        assert!(!scheduled.is_due(i64::MAX));
+
+        scheduled.reset_attempts();
+        assert!(scheduled.is_due(3_000));
+        assert_eq!(scheduled.last_error.as_deref(), Some("offline"));
+    }
+
+    #[test]
+    fn only_one_process_claims_a_message() {
+        let message = ScheduledMessage {
+            id: 1,
+            device_id: "dev".to_string(),
+            target: ScheduledTarget::NewConversation {
+                recipients: vec!["5551234567".to_string()],
+            },
+            body: "hi".to_string(),
+            attachment_urls: vec![],
+            due_ms: 0,
+            sub_id: None,
+            last_error: None,
+            attempts: 0,
+            retry_at_ms: 0,
+            claim: None,
+        };
+        let mut file = Vec::new();
+        assert!(ScheduledChange::Add(message).apply(&mut file));
+
+        let first = SendClaim {
+            pid: 1,
+            at_ms: 1_000,
+        };
+        let second = SendClaim {
+            pid: 2,
+            at_ms: 1_500,
+        };
+        assert!(ScheduledChange::Claim(vec![1], first).apply(&mut file));
+        assert!(!ScheduledChange::Claim(vec![1], second).apply(&mut file));
+        assert_eq!(file[0].claim, Some(first));
+        assert!(!ScheduledChange::Edit(1, "changed".to_string()).apply(&mut file));
+
+        // A failed send releases the claim and backs off
+        assert!(ScheduledChange::Failed(1, "offline".to_string(), 2_000).apply(&mut file));
+        assert_eq!(file[0].claim, None);
+        assert!(!file[0].is_due(2_000));
+
+        // A claim left by a process that exited expires
+        let retry_ms = 2_000 + SCHEDULED_RETRY_BASE_MS;
+        let stale = SendClaim {
+            pid: 1,
+            at_ms: retry_ms,
+        };
+        assert!(ScheduledChange::Claim(vec![1], stale).apply(&mut file));
+        let later = SendClaim {
+            pid: 2,
+            at_ms: retry_ms + SCHEDULED_CLAIM_EXPIRY_MS,
+        };
+        assert!(ScheduledChange::Claim(vec![1], later).apply(&mut file));
+        assert_eq!(file[0].claim, Some(later));
+
+        assert!(ScheduledChange::Sent(1).apply(&mut file));
+        assert!(file.is_empty());
+    }
+}
@@ -0,0 +1,331 @@
+//! Full-text search across the conversation list.
+//!
+//! Matches the query against each conversation's display name and
+//! addresses, the latest-message preview, and every archived message body
+//! for that conversation. Pure logic — the store feeds it the current state
+//! and the view renders the returned [`SearchHit`]s.
+
+use std::collections::HashMap;
+
+use kdeconnect_dbus::contacts::ContactLookup;
+use kdeconnect_dbus::normalize_phone_number;
+use kdeconnect_dbus::plugins::SmsMessage;
+
+use crate::sms::logical::LogicalConversation;
+
+/// Characters of context kept on each side of a body match in a snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 24;
+
+/// What part of the conversation matched the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchHitKind {
+    /// The contact name or one of the addresses matched.
+    Participant,
+    /// A message body (or the latest-message preview) matched.
+    Body,
+}
+
+/// A single search result, ready for rendering.
+///
+/// The snippet is pre-split around the match so the view can style the
+/// matched span without re-running the search.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// Primary thread ID of the `LogicalConversation` to open.
+    pub thread_id: i64,
+    /// UID of the matched message, when the hit came from a loaded message.
+    /// `None` for participant hits and preview-only hits, which open the
+    /// thread at the newest message as usual.
+    pub message_uid: Option<i32>,
+    pub kind: SearchHitKind,
+    pub display_name: String,
+    pub timestamp: i64,
+    pub snippet_before: String,
+    pub snippet_match: String,
+    pub snippet_after: String,
+}
+
+/// Find `needle` in `haystack` ignoring case. Returns the byte range of the
+/// match in `haystack`. `needle` must already be lowercase.
+pub(crate) fn find_case_insensitive(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    if needle.is_empty() {
+        return None;
+    }
+    for (start, _) in haystack.char_indices() {
+        let mut hay = haystack[start..].char_indices();
+        let mut matched = true;
+        let mut end = start;
+        for n in needle.chars() {
+            match hay.next() {
+                Some((offset, c)) if c.to_lowercase().eq(n.to_lowercase()) => {
+                    end = start + offset + c.len_utf8();
+                }
+                _ => {
+                    matched = false;
+                    break;
+                }
+            }
+        }
+        if matched {
+            return Some((start, end));
+        }
+    }
+    None
+}
+
+/// Collapse whitespace so multi-line bodies render on one snippet line.
+fn flatten(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Split `text` around the first case-insensitive match of `needle`,
+/// trimming each side to [`SNIPPET_CONTEXT_CHARS`] with an ellipsis.
+pub(crate) fn build_snippet(text: &str, needle: &str) -> Option<(String, String, String)> {
+    let flat = flatten(text);
+    let (start, end) = find_case_insensitive(&flat, needle)?;
+
+    let before_chars: Vec<char> = flat[..start].chars().collect();
+    let before = if before_chars.len() > SNIPPET_CONTEXT_CHARS {
+        let tail: String = before_chars[before_chars.len() - SNIPPET_CONTEXT_CHARS..]
+            .iter()
+            .collect();
+        format!("…{}", tail)
+    } else {
+        before_chars.into_iter().collect()
+    };
+
+    let after_full = &flat[end..];
+    let after = if after_full.chars().count() > SNIPPET_CONTEXT_CHARS {
+        let head: String = after_full.chars().take(SNIPPET_CONTEXT_CHARS).collect();
+        format!("{}…", head)
+    } else {
+        after_full.to_string()
+    };
+
+    Some((before, flat[start..end].to_string(), after))
+}
+
+/// Whether any of `addresses` contains the digits typed in `query`.
+/// Only applies when the query has at least three digits, so short numeric
+/// fragments in a name search don't match every phone number.
+fn address_matches(addresses: &[String], query: &str) -> bool {
+    let query_digits = normalize_phone_number(query);
+    if query_digits.len() < 3 {
+        return false;
+    }
+    addresses
+        .iter()
+        .any(|addr| normalize_phone_number(addr).contains(&query_digits))
+}
+
+/// Search `conversations` for `query`.
+///
+/// `messages_by_thread` maps each underlying thread ID to its known
+/// messages (the SMS archive); merged conversations search every thread
+/// they cover. Results are ordered participant hits first, then body hits
+/// newest-first, and capped at `limit`.
+pub fn search_conversations(
+    query: &str,
+    conversations: &[LogicalConversation],
+    contacts: &ContactLookup,
+    messages_by_thread: &HashMap<i64, Vec<SmsMessage>>,
+    limit: usize,
+) -> Vec<SearchHit> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut participant_hits = Vec::new();
+    let mut body_hits = Vec::new();
+
+    for conv in conversations {
+        let display_name = contacts.get_group_display_name(&conv.addresses, 3);
+
+        if find_case_insensitive(&display_name, &needle).is_some()
+            || address_matches(&conv.addresses, &needle)
+        {
+            let preview = flatten(&conv.last_message_preview);
+            participant_hits.push(SearchHit {
+                thread_id: conv.primary_thread_id,
+                message_uid: None,
+                kind: SearchHitKind::Participant,
+                display_name: display_name.clone(),
+                timestamp: conv.last_message_timestamp,
+                snippet_before: preview,
+                snippet_match: String::new(),
+                snippet_after: String::new(),
+            });
+        }
+
+        let loaded = conv
+            .merged_thread_ids
+            .iter()
+            .filter_map(|id| messages_by_thread.get(id))
+            .flatten();
+        let mut matched_preview = false;
+        for msg in loaded {
+            if let Some((before, matched, after)) = build_snippet(&msg.body, &needle) {
+                matched_preview |= msg.body == conv.last_message_preview;
+                body_hits.push(SearchHit {
+                    thread_id: conv.primary_thread_id,
+                    message_uid: Some(msg.uid),
+                    kind: SearchHitKind::Body,
+                    display_name: display_name.clone(),
+                    timestamp: msg.date,
+                    snippet_before: before,
+                    snippet_match: matched,
+                    snippet_after: after,
+                });
+            }
+        }
+
+        // Threads we haven't opened yet only have their preview to go on.
+        if !matched_preview {
+            if let Some((before, matched, after)) =
+                build_snippet(&conv.last_message_preview, &needle)
+            {
+                body_hits.push(SearchHit {
+                    thread_id: conv.primary_thread_id,
+                    message_uid: None,
+                    kind: SearchHitKind::Body,
+                    display_name,
+                    timestamp: conv.last_message_timestamp,
+                    snippet_before: before,
+                    snippet_match: matched,
+                    snippet_after: after,
+                });
+            }
+        }
+    }
+
+    body_hits.sort_by_key(|hit| std::cmp::Reverse(hit.timestamp));
+    participant_hits
+        .into_iter()
+        .chain(body_hits)
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kdeconnect_dbus::plugins::{ConversationSummary, MessageType};
+
+    fn conv(thread_id: i64, addresses: &[&str], last_message: &str) -> LogicalConversation {
+        LogicalConversation::from_single(ConversationSummary {
+            thread_id,
+            addresses: addresses.iter().map(|s| (*s).to_string()).collect(),
+            last_message: last_message.to_string(),
+            timestamp: thread_id * 1000,
+            unread: false,
+            has_attachments: false,
+            sub_id: -1,
+        })
+    }
+
+    fn msg(thread_id: i64, uid: i32, date: i64, body: &str) -> SmsMessage {
+        SmsMessage {
+            body: body.to_string(),
+            addresses: vec![],
+            date,
+            message_type: MessageType::Inbox,
+            raw_type: 1,
+            read: true,
+            thread_id,
+            uid,
+            sub_id: -1,
+            attachments: vec![],
+        }
+    }
+
+    #[test]
+    fn find_ignores_case_and_returns_byte_range() {
+        assert_eq!(find_case_insensitive("Hello World", "world"), Some((6, 11)));
+        assert_eq!(
+            find_case_insensitive("Grüße aus Köln", "köln"),
+            Some((12, 17))
+        );
+        assert_eq!(find_case_insensitive("abc", "abcd"), None);
+        assert_eq!(find_case_insensitive("abc", ""), None);
+    }
+
+    #[test]
+    fn snippet_trims_context_with_ellipses() {
+        let body = "The quick brown fox jumps over the lazy dog and keeps running far away";
+        let (before, matched, after) = build_snippet(body, "lazy").unwrap();
+        assert_eq!(matched, "lazy");
+        assert!(before.starts_with('…'));
+        assert!(before.ends_with("over the "));
+        assert!(after.ends_with('…'));
+    }
+
+    #[test]
+    fn snippet_flattens_newlines() {
+        let (before, matched, after) = build_snippet("see you\nat noon", "at").unwrap();
+        assert_eq!(
+            (before.as_str(), matched.as_str(), after.as_str()),
+            ("see you ", "at", " noon")
+        );
+    }
+
+    #[test]
+    fn matches_address_digits() {
+        let convs = vec![conv(1, &["+1 (555) 123-4567"], "hi")];
+        let hits = search_conversations(
+            "5551234",
+            &convs,
+            &ContactLookup::default(),
+            &HashMap::new(),
+            10,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::Participant);
+    }
+
+    #[test]
+    fn loaded_message_hits_carry_uid_and_sort_newest_first() {
+        let convs = vec![
+            conv(1, &["5551111"], "latest"),
+            conv(2, &["5552222"], "other"),
+        ];
+        let mut loaded = HashMap::new();
+        loaded.insert(
+            1,
+            vec![
+                msg(1, 10, 100, "dinner at eight"),
+                msg(1, 11, 300, "Dinner moved"),
+            ],
+        );
+        loaded.insert(2, vec![msg(2, 20, 200, "no dinner for me")]);
+
+        let hits = search_conversations("dinner", &convs, &ContactLookup::default(), &loaded, 10);
+        let uids: Vec<_> = hits.iter().map(|h| h.message_uid).collect();
+        assert_eq!(uids, vec![Some(11), Some(20), Some(10)]);
+    }
+
+    #[test]
+    fn preview_hit_when_thread_not_loaded() {
+        let convs = vec![conv(1, &["5551111"], "Parcel delivered")];
+        let hits = search_conversations(
+            "parcel",
+            &convs,
+            &ContactLookup::default(),
+            &HashMap::new(),
+            10,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message_uid, None);
+        assert_eq!(hits[0].snippet_match, "Parcel");
+    }
+
+    #[test]
+    fn preview_not_duplicated_when_loaded_message_matches() {
+        let convs = vec![conv(1, &["5551111"], "Parcel delivered")];
+        let mut loaded = HashMap::new();
+        loaded.insert(1, vec![msg(1, 5, 100, "Parcel delivered")]);
+        let hits = search_conversations("parcel", &convs, &ContactLookup::default(), &loaded, 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message_uid, Some(5));
+    }
+}
@@ -1,12 +1,63 @@
 //! SMS sending functionality.
 
 use crate::app::Message;
-use kdeconnect_dbus::plugins::ConversationsProxy;
+use kdeconnect_dbus::plugins::{ConversationsProxy, SmsProxy};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use zbus::zvariant::{Structure, Value};
 use zbus::Connection;
 
+/// Attachment URLs as the `av` the daemon expects (each a string variant).
+fn attachment_values(urls: Vec<String>) -> Vec<Value<'static>> {
+    urls.into_iter().map(Value::from).collect()
+}
+
+/// Send through the SMS plugin's `sendSms`, the only call that takes a SIM.
+///
+/// Used when the user picks a SIM other than the one the phone would choose:
+/// `replyToConversation` always uses the thread's last SIM and
+/// `sendWithoutConversation` the phone's default.
+async fn send_on_sim(
+    conn: &Connection,
+    device_id: &str,
+    recipients: &[String],
+    message: &str,
+    attachment_urls: Vec<String>,
+    sub_id: i64,
+) -> Result<(), String> {
+    let sms_path = format!("{}/devices/{}/sms", kdeconnect_dbus::BASE_PATH, device_id);
+    let sms_proxy = SmsProxy::builder(conn)
+        .path(sms_path.as_str())
+        .map_err(|e| format!("Failed to build SMS proxy path: {}", e))?
+        .build()
+        .await
+        .map_err(|e| format!("Failed to create SMS proxy: {}", e))?;
+
+    let addresses: Vec<Value<'_>> = recipients
+        .iter()
+        .map(|r| Value::Structure(Structure::from((r.clone(),))))
+        .collect();
+
+    tracing::info!(
+        "Sending SMS via sendSms sub_id={} recipients={}",
+        sub_id,
+        recipients.len()
+    );
+
+    sms_proxy
+        .send_sms(
+            addresses,
+            message,
+            attachment_values(attachment_urls),
+            sub_id,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("SMS send failed: {}", e);
+            format!("Send failed: {}", e)
+        })
+}
+
 /// Send an SMS reply to an existing conversation using replyToConversation.
 ///
 /// Uses the Conversations D-Bus interface with a thread ID. The daemon looks up
@@ -16,13 +67,36 @@ use zbus::Connection;
 ///
 /// Note: `replyToConversation` silently no-ops if the cache is empty (no D-Bus
 /// error). The cache is reliably primed by our conversation loading flow.
+///
+/// `attachment_urls` are `file://` URLs; the daemon reads each file and sends
+/// the message as MMS when any are present.
+///
+/// `sub_id` overrides the SIM: the reply then goes to `addresses` via
+/// `sendSms` instead. `addresses` is unused otherwise.
 pub async fn send_sms_async(
     conn: Arc<Mutex<Connection>>,
     device_id: String,
     thread_id: i64,
+    addresses: Vec<String>,
     message: String,
+    attachment_urls: Vec<String>,
+    sub_id: Option<i64>,
 ) -> Message {
     let conn = conn.lock().await;
+    if let Some(sub_id) = sub_id {
+        return Message::SmsSendResult(
+            send_on_sim(
+                &conn,
+                &device_id,
+                &addresses,
+                &message,
+                attachment_urls,
+                sub_id,
+            )
+            .await
+            .map(|()| message),
+        );
+    }
     let device_path = format!("{}/devices/{}", kdeconnect_dbus::BASE_PATH, device_id);
 
     let conversations_proxy = match ConversationsProxy::builder(&conn)
@@ -41,15 +115,16 @@ pub async fn send_sms_async(
         }
     };
 
-    let empty_attachments: Vec<Value<'_>> = vec![];
+    let attachments = attachment_values(attachment_urls);
 
     tracing::info!(
-        "Sending SMS via replyToConversation for thread_id={}",
-        thread_id
+        "Sending SMS via replyToConversation for thread_id={} attachments={}",
+        thread_id,
+        attachments.len()
     );
 
     match conversations_proxy
-        .reply_to_conversation(thread_id, &message, empty_attachments)
+        .reply_to_conversation(thread_id, &message, attachments)
         .await
     {
         Ok(()) => {
@@ -64,13 +139,32 @@ pub async fn send_sms_async(
 }
 
 /// Send an SMS to one or more recipients (creates or adds to existing conversation).
+///
+/// With `sub_id` set the message goes out on that SIM via `sendSms`;
+/// otherwise the phone picks its default SIM.
 pub async fn send_new_sms_async(
     conn: Arc<Mutex<Connection>>,
     device_id: String,
     recipients: Vec<String>,
     message: String,
+    attachment_urls: Vec<String>,
+    sub_id: Option<i64>,
 ) -> Message {
     let conn = conn.lock().await;
+    if let Some(sub_id) = sub_id {
+        return Message::NewMessageSendResult(
+            send_on_sim(
+                &conn,
+                &device_id,
+                &recipients,
+                &message,
+                attachment_urls,
+                sub_id,
+            )
+            .await
+            .map(|()| "Message sent".to_string()),
+        );
+    }
     let device_path = format!("{}/devices/{}", kdeconnect_dbus::BASE_PATH, device_id);
 
     let conversations_proxy = match ConversationsProxy::builder(&conn)
@@ -98,10 +192,10 @@ pub async fn send_new_sms_async(
         .iter()
         .map(|r| Value::Structure(Structure::from((r.clone(),))))
         .collect();
-    let empty_attachments: Vec<Value<'_>> = vec![];
+    let attachments = attachment_values(attachment_urls);
 
     match conversations_proxy
-        .send_without_conversation(addresses, &message, empty_attachments)
+        .send_without_conversation(addresses, &message, attachments)
         .await
     {
         Ok(()) => Message::NewMessageSendResult(Ok("Message sent".to_string())),
@@ -0,0 +1,61 @@
+//! Dual-SIM helpers.
+//!
+//! KDE Connect doesn't list the phone's SIMs, so the known set is inferred
+//! from the subscription IDs seen on conversations and messages. SIMs are
+//! numbered 1, 2, … in subscription-ID order, which matches the order
+//! Android assigns them in as long as neither card has been swapped.
+
+/// Subscription ID the daemon uses for "unknown / phone default".
+pub const DEFAULT_SUB_ID: i64 = -1;
+
+/// Distinct real subscription IDs, sorted.
+pub fn known_sims(sub_ids: impl IntoIterator<Item = i64>) -> Vec<i64> {
+    let mut sims: Vec<i64> = sub_ids
+        .into_iter()
+        .filter(|&id| id != DEFAULT_SUB_ID)
+        .collect();
+    sims.sort_unstable();
+    sims.dedup();
+    sims
+}
+
+/// 1-based SIM number for `sub_id`, or `None` if it isn't a known SIM.
+pub fn sim_slot(known: &[i64], sub_id: i64) -> Option<usize> {
+    known.iter().position(|&id| id == sub_id).map(|i| i + 1)
+}
+
+/// SIM override to send with, or `None` to let the phone choose.
+///
+/// `thread_sim` is the SIM the phone would use on its own (a thread's last
+/// SIM); picking that same SIM needs no override.
+pub fn sim_override(selected: Option<i64>, thread_sim: Option<i64>) -> Option<i64> {
+    selected.filter(|&sim| sim != DEFAULT_SUB_ID && Some(sim) != thread_sim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_sims_dedups_and_drops_default() {
+        assert_eq!(known_sims([3, -1, 1, 3, 1]), vec![1, 3]);
+        assert!(known_sims([-1, -1]).is_empty());
+    }
+
+    #[test]
+    fn slots_follow_subscription_order() {
+        let known = known_sims([7, 4]);
+        assert_eq!(sim_slot(&known, 4), Some(1));
+        assert_eq!(sim_slot(&known, 7), Some(2));
+        assert_eq!(sim_slot(&known, -1), None);
+    }
+
+    #[test]
+    fn override_only_when_sim_differs() {
+        assert_eq!(sim_override(Some(2), Some(1)), Some(2));
+        assert_eq!(sim_override(Some(1), Some(1)), None);
+        assert_eq!(sim_override(Some(2), None), Some(2));
+        assert_eq!(sim_override(None, Some(1)), None);
+        assert_eq!(sim_override(Some(-1), None), None);
+    }
+}
@@ -5,24 +5,45 @@
 use crate::app::{LoadingPhase, Message, SmsLoadingState};
 use crate::config::Config;
 use crate::constants::notifications::NORMAL_NOTIFICATION_TIMEOUT_MS;
-use crate::constants::sms::MESSAGES_PER_PAGE;
+use crate::constants::sms::{
+    ARCHIVE_FLUSH_INTERVAL_SECS, CONTACTS_FALLBACK_INTERVAL_SECS, DRAFT_FLUSH_INTERVAL_SECS,
+    MAX_ATTACHMENT_BYTES, MAX_PENDING_ATTACHMENTS, MAX_SEARCH_RESULTS, MESSAGES_PER_PAGE,
+    SCHEDULED_CHECK_INTERVAL_SECS,
+};
+use crate::device::sync_contacts_async;
 use crate::fl;
+use crate::sms::archive::{load_archive_async, save_archive_async, SmsArchive};
+use crate::sms::attach::{pick_attachments_async, AttachmentRejection, PendingAttachment};
+use crate::sms::contacts::{contact_sections, direct_threads, ContactSection};
+use crate::sms::drafts::{save_drafts_async, DraftSlot, NewMessageDraft, SmsDrafts};
+use crate::sms::export::{build_conversation, default_file_name, render, save_export_async};
+use crate::sms::flags::{
+    save_flag_async, ConversationFlag, ConversationFlagStore, ConversationFlags,
+};
 use crate::sms::logical::{merge_into_logical, split_candidate_thread_ids, LogicalConversation};
+use crate::sms::scheduled::{
+    update_scheduled_async, ScheduledChange, ScheduledMessage, ScheduledTarget, SendClaim,
+};
+use crate::sms::search::{search_conversations, SearchHit};
+use crate::sms::sim::{known_sims, sim_override, DEFAULT_SUB_ID};
 use crate::sms::{
-    conversation_list_subscription, fetch_older_messages_async, request_attachment_async,
-    send_new_sms_async, send_sms_async, view_conversation_list, view_message_thread,
-    view_new_message, ConversationListParams, MessageThreadParams, NewMessageParams,
+    conversation_list_subscription, fetch_older_messages_async, fetch_threads_for_export_async,
+    request_attachment_async, send_new_sms_async, send_sms_async, view_contacts,
+    view_conversation_list, view_message_thread, view_new_message, ContactsViewParams,
+    ConversationListParams, MessageThreadParams, NewMessageParams, ScheduleViewState,
 };
-use crate::subscriptions::conversation_message_subscription;
+use crate::subscriptions::{contacts_signal_subscription, conversation_message_subscription};
+use crate::ui::widgets::avatar::AvatarCache;
 use cosmic::iced::widget::scrollable;
 use cosmic::iced::{clipboard, Subscription};
 use cosmic::widget;
 use cosmic::Element;
 use kdeconnect_dbus::contacts::ContactLookup;
+use kdeconnect_dbus::phone_key;
 use kdeconnect_dbus::plugins::{
-    is_address_valid, ConversationSummary, MessageType, SmsMessage, OPTIMISTIC_MESSAGE_UID,
+    is_address_valid, ConversationSummary, DeliveryState, MessageType, SmsMessage,
+    OPTIMISTIC_MESSAGE_UID,
 };
-use kdeconnect_dbus::{normalize_phone_number, phone_suffix};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -52,12 +73,22 @@ pub enum SmsReply {
     NoOp,
 }
 
+/// Status to show when the phone reports `message` as not delivered.
+fn failure_reply(message: &SmsMessage) -> SmsReply {
+    if message.delivery_state() == DeliveryState::Failed {
+        SmsReply::Status(fl!("message-not-delivered"))
+    } else {
+        SmsReply::NoOp
+    }
+}
+
 /// Which SMS sub-view the parent app is rendering.
 #[derive(Debug, Clone, Copy)]
 pub enum SmsViewMode {
     ConversationList,
     MessageThread,
     NewMessage,
+    Contacts,
 }
 
 pub struct SmsConversationStore {
@@ -71,6 +102,12 @@ pub struct SmsConversationStore {
     /// re-derivation works off this cache without re-fetching.
     pub(crate) raw_conversations: Vec<ConversationSummary>,
     pub(crate) conversations: Vec<LogicalConversation>,
+    /// Local flags of each entry in `conversations`, by index.
+    pub(crate) conversation_list_flags: Vec<ConversationFlags>,
+    /// SIMs seen on this device's conversations and the open thread.
+    pub(crate) known_sims: Vec<i64>,
+    /// `messages` changed since `known_sims` was computed.
+    sims_stale: bool,
     pub(crate) sms_prefetch: Option<(String, Vec<ConversationSummary>)>,
     pub(crate) conversation_sync_active: bool,
     pub(crate) conversation_list_subscription_active: bool,
@@ -90,6 +127,13 @@ pub struct SmsConversationStore {
     pub(crate) messages: Vec<SmsMessage>,
     pub(crate) sms_loading_state: SmsLoadingState,
     pub(crate) contacts: ContactLookup,
+    /// Decoded photos of `contacts`; rebuilt with them in `set_contacts`.
+    pub(crate) avatars: AvatarCache,
+    /// Whether a phone→desktop contact sync request is in flight.
+    pub(crate) contacts_syncing: bool,
+    /// Whether the daemon has announced a contacts sync for this device;
+    /// until then the vCard directory is also polled.
+    pub(crate) contacts_signal_seen: bool,
     pub(crate) conversation_list_key: u32,
     pub(crate) conversations_displayed: usize,
 
@@ -97,6 +141,11 @@ pub struct SmsConversationStore {
     pub(crate) sms_compose_text: widget::text_editor::Content,
     pub(crate) sms_sending: bool,
     pub(crate) sms_sending_body: Option<String>,
+    /// UIDs of failed messages being resent via "Retry".
+    pub(crate) retrying: HashSet<i32>,
+    /// Files queued for the next message, in the thread or new-message
+    /// compose box (only one is open at a time).
+    pub(crate) pending_attachments: Vec<PendingAttachment>,
 
     // Message pagination / scroll preservation
     pub(crate) messages_loaded_count: u32,
@@ -109,6 +158,10 @@ pub struct SmsConversationStore {
     pub(crate) new_message_recipient_input: String,
     pub(crate) new_message_body: widget::text_editor::Content,
     pub(crate) new_message_sending: bool,
+    /// Whether the new-message view holds the device's saved draft. A message
+    /// started from a contact or the call log begins empty and leaves the
+    /// draft alone.
+    pub(crate) new_message_from_draft: bool,
     pub(crate) contact_suggestions: Vec<(String, String)>,
 
     // SMS notification deduplication. Keyed by (device_id, thread_id) because
@@ -120,6 +173,73 @@ pub struct SmsConversationStore {
     pub(crate) pressed_bubble_uid: Option<i32>,
     pub(crate) pressed_bubble_body: Option<String>,
     pub(crate) show_copy_hint: bool,
+
+    /// Newest message timestamp read here per underlying thread. Overrides
+    /// the phone's unread flag for anything not newer.
+    pub(crate) locally_read: HashMap<i64, i64>,
+
+    // On-disk archive
+    /// Everything seen for `sms_device_id`: conversation heads and messages.
+    /// Seeds the list and threads on open and backs conversation search.
+    pub(crate) archive: SmsArchive,
+    /// Archive has changes not yet written to disk; drives the flush timer.
+    pub(crate) archive_dirty: bool,
+
+    // Local conversation flags
+    /// Pin/mute/archive state for every device, persisted to disk.
+    pub(crate) conversation_flags: ConversationFlagStore,
+    /// Flag writes not yet finished.
+    pub(crate) flag_saves_pending: usize,
+    /// Whether the conversation list's "Archived" section is expanded.
+    pub(crate) show_archived: bool,
+
+    // SIM selection
+    /// SIM chosen in the compose view's selector; `None` lets the phone pick.
+    pub(crate) selected_sim: Option<i64>,
+    /// Last SIM sent from per device, the default for new messages.
+    pub(crate) last_used_sim: HashMap<String, i64>,
+
+    // Compose drafts
+    /// Unsent thread replies and new messages for every device.
+    pub(crate) drafts: SmsDrafts,
+    /// Drafts changed here and not yet written to disk; drives the flush
+    /// timer.
+    pub(crate) dirty_drafts: HashSet<DraftSlot>,
+
+    // Scheduled ("Send later") messages
+    /// Scheduled messages for every device, persisted to disk.
+    pub(crate) scheduled: Vec<ScheduledMessage>,
+    /// Scheduled-message file writes not yet finished.
+    pub(crate) scheduled_saves_pending: usize,
+    /// Scheduled message whose body is being edited, with the draft text.
+    pub(crate) editing_scheduled: Option<(i64, String)>,
+    pub(crate) schedule_picker_open: bool,
+    /// `HH:MM` typed into the "Send later" picker.
+    pub(crate) schedule_time_input: String,
+
+    /// Export format picker shown under the list/thread header.
+    pub(crate) export_menu_open: bool,
+
+    // Conversation search
+    pub(crate) search_query: String,
+    /// Hits for a non-empty `search_query`, recomputed when the query or
+    /// the searched data changes rather than on every redraw.
+    pub(crate) search_results: Option<Vec<SearchHit>>,
+    /// The archive changed since `search_results` was computed.
+    search_stale: bool,
+    /// Search field contents of the contacts browser.
+    pub(crate) contacts_query: String,
+    /// Contacts matching `contacts_query`, rebuilt when the contacts or the
+    /// query change rather than on every redraw.
+    pub(crate) contact_sections: Vec<ContactSection>,
+    /// One-to-one conversations for the contacts browser to open, rebuilt
+    /// with `conversations`.
+    pub(crate) direct_threads: HashMap<String, i64>,
+    /// Message to scroll to once the thread opened from a search hit has
+    /// loaded it. Cleared when found or when no older pages remain.
+    pub(crate) search_target_uid: Option<i32>,
+    /// Message highlighted in the thread after jumping to a search hit.
+    pub(crate) search_highlight_uid: Option<i32>,
 }
 
 impl SmsConversationStore {
@@ -129,6 +249,9 @@ impl SmsConversationStore {
             sms_device_name: None,
             raw_conversations: Vec::new(),
             conversations: Vec::new(),
+            conversation_list_flags: Vec::new(),
+            known_sims: Vec::new(),
+            sims_stale: false,
             sms_prefetch: None,
             conversation_sync_active: false,
             conversation_list_subscription_active: false,
@@ -142,11 +265,16 @@ impl SmsConversationStore {
             messages: Vec::new(),
             sms_loading_state: SmsLoadingState::Idle,
             contacts: ContactLookup::default(),
+            avatars: AvatarCache::default(),
+            contacts_syncing: false,
+            contacts_signal_seen: false,
             conversation_list_key: 0,
             conversations_displayed: 10,
             sms_compose_text: widget::text_editor::Content::new(),
+            pending_attachments: Vec::new(),
             sms_sending: false,
             sms_sending_body: None,
+            retrying: HashSet::new(),
             messages_loaded_count: 0,
             messages_has_more: true,
             scroll_offset_before_load: None,
@@ -155,11 +283,36 @@ impl SmsConversationStore {
             new_message_recipient_input: String::new(),
             new_message_body: widget::text_editor::Content::new(),
             new_message_sending: false,
+            new_message_from_draft: true,
             contact_suggestions: Vec::new(),
             last_seen_sms: HashMap::new(),
             pressed_bubble_uid: None,
             pressed_bubble_body: None,
             show_copy_hint: false,
+            locally_read: HashMap::new(),
+            archive: SmsArchive::default(),
+            archive_dirty: false,
+            conversation_flags: ConversationFlagStore::default(),
+            flag_saves_pending: 0,
+            show_archived: false,
+            selected_sim: None,
+            last_used_sim: HashMap::new(),
+            drafts: SmsDrafts::default(),
+            dirty_drafts: HashSet::new(),
+            scheduled: Vec::new(),
+            scheduled_saves_pending: 0,
+            editing_scheduled: None,
+            schedule_picker_open: false,
+            schedule_time_input: String::new(),
+            export_menu_open: false,
+            search_query: String::new(),
+            search_results: None,
+            search_stale: false,
+            contacts_query: String::new(),
+            contact_sections: Vec::new(),
+            direct_threads: HashMap::new(),
+            search_target_uid: None,
+            search_highlight_uid: None,
         }
     }
 
@@ -195,6 +348,17 @@ impl SmsConversationStore {
     /// sibling so the UI can surface "would-merge" indicators. Call after
     /// any mutation of `raw_conversations`, or after the toggle changes.
     pub(crate) fn rederive_conversations(&mut self, config: &Config) {
+        // The phone keeps reporting a thread unread until it's read there;
+        // threads opened here stay read until something newer arrives.
+        for cs in &mut self.raw_conversations {
+            if self
+                .locally_read
+                .get(&cs.thread_id)
+                .is_some_and(|&read_up_to| cs.timestamp <= read_up_to)
+            {
+                cs.unread = false;
+            }
+        }
         self.conversations = if config.merge_reaction_threads {
             merge_into_logical(&self.raw_conversations)
         } else {
@@ -209,6 +373,114 @@ impl SmsConversationStore {
                 })
                 .collect()
         };
+
+        // Pinned conversations first; the sort is stable so recency order
+        // holds within each group.
+        if let Some(device_id) = &self.sms_device_id {
+            let flags = &self.conversation_flags;
+            self.conversations
+                .sort_by_key(|lc| !flags.get(device_id, &lc.addresses).pinned);
+        }
+        self.direct_threads = direct_threads(&self.conversations);
+        self.refresh_list_flags();
+        self.refresh_known_sims();
+        self.refresh_search();
+    }
+
+    /// Replace the contact lookup and decode its photos for avatars.
+    pub(crate) fn set_contacts(&mut self, contacts: ContactLookup) {
+        self.avatars = AvatarCache::build(&contacts);
+        self.contacts = contacts;
+        self.refresh_contact_sections();
+        self.refresh_search();
+    }
+
+    /// Rebuild the contacts browser's sections for `contacts_query`.
+    pub(crate) fn refresh_contact_sections(&mut self) {
+        self.contact_sections =
+            contact_sections(self.contacts.all_contacts(), &self.contacts_query);
+    }
+
+    /// Look up the flags of every listed conversation. Call after
+    /// `conversations` or the flags change.
+    fn refresh_list_flags(&mut self) {
+        self.conversation_list_flags = self
+            .conversations
+            .iter()
+            .map(|lc| self.flags_for(&lc.addresses))
+            .collect();
+    }
+
+    /// Recompute `known_sims`. Call after `raw_conversations` or `messages`
+    /// change.
+    pub(crate) fn refresh_known_sims(&mut self) {
+        self.sims_stale = false;
+        self.known_sims = known_sims(
+            self.raw_conversations
+                .iter()
+                .map(|cs| cs.sub_id)
+                .chain(self.messages.iter().map(|m| m.sub_id)),
+        );
+    }
+
+    /// Re-run the conversation search for the current query.
+    fn refresh_search(&mut self) {
+        self.search_stale = false;
+        self.search_results = (!self.search_query.trim().is_empty()).then(|| {
+            search_conversations(
+                &self.search_query,
+                &self.conversations,
+                &self.contacts,
+                &self.archive.messages,
+                MAX_SEARCH_RESULTS,
+            )
+        });
+    }
+
+    /// Local flags of a conversation on the current device.
+    pub(crate) fn flags_for(&self, addresses: &[String]) -> ConversationFlags {
+        self.sms_device_id
+            .as_deref()
+            .map(|device_id| self.conversation_flags.get(device_id, addresses))
+            .unwrap_or_default()
+    }
+
+    /// Whether new messages in `thread_id` on `device_id` should stay
+    /// silent. Matches by the thread's known participants when it's in the
+    /// loaded list, else by the message's own addresses.
+    fn is_muted(&self, device_id: &str, thread_id: i64, addresses: &[String]) -> bool {
+        let addresses = self
+            .logical_for(thread_id)
+            .filter(|_| self.sms_device_id.as_deref() == Some(device_id))
+            .map(|lc| lc.addresses.as_slice())
+            .unwrap_or(addresses);
+        self.conversation_flags.get(device_id, addresses).muted
+    }
+
+    /// Clear the unread state of `thread_ids` locally (the phone isn't told)
+    /// and re-derive so merged counts drop together.
+    pub(crate) fn mark_read_locally(&mut self, thread_ids: &[i64], config: &Config) {
+        for cs in &self.raw_conversations {
+            if thread_ids.contains(&cs.thread_id) {
+                let read_up_to = self
+                    .locally_read
+                    .entry(cs.thread_id)
+                    .or_insert(cs.timestamp);
+                *read_up_to = (*read_up_to).max(cs.timestamp);
+            }
+        }
+        self.rederive_conversations(config);
+    }
+
+    /// Unread conversations across the list, for the panel badge. Archived
+    /// and muted conversations don't count.
+    pub(crate) fn total_unread(&self) -> usize {
+        self.conversations
+            .iter()
+            .zip(&self.conversation_list_flags)
+            .filter(|(_, flags)| !flags.archived && !flags.muted)
+            .map(|(lc, _)| lc.unread_count)
+            .sum()
     }
 
     /// Find the `LogicalConversation` containing `thread_id` (whether as
@@ -245,20 +517,13 @@ impl SmsConversationStore {
     }
 
     /// Find the latest conversation timestamp for a phone number.
-    /// Uses suffix matching (last 10 digits) to handle format variations.
+    /// Compares region-aware phone keys to handle format variations.
     pub(crate) fn find_conversation_timestamp(&self, phone: &str) -> Option<i64> {
-        let phone_digits = normalize_phone_number(phone);
-        let target_suffix = phone_suffix(&phone_digits);
+        let target = phone_key(phone);
 
         self.conversations
             .iter()
-            .filter(|conv| {
-                conv.addresses.iter().any(|addr| {
-                    let addr_digits = normalize_phone_number(addr);
-                    let addr_suffix = phone_suffix(&addr_digits);
-                    target_suffix == addr_suffix
-                })
-            })
+            .filter(|conv| conv.addresses.iter().any(|addr| phone_key(addr) == target))
             .map(|conv| conv.last_message_timestamp)
             .max()
     }
@@ -280,9 +545,9 @@ impl SmsConversationStore {
         // Expand each contact into (name, phone, timestamp) entries
         let mut entries: Vec<(String, String, Option<i64>)> = Vec::new();
         for contact in matching_contacts {
-            for phone in &contact.phone_numbers {
-                let timestamp = self.find_conversation_timestamp(phone);
-                entries.push((contact.name.clone(), phone.clone(), timestamp));
+            for phone in &contact.phones {
+                let timestamp = self.find_conversation_timestamp(&phone.number);
+                entries.push((contact.name.clone(), phone.number.clone(), timestamp));
             }
         }
 
@@ -294,29 +559,24 @@ impl SmsConversationStore {
             (None, None) => std::cmp::Ordering::Equal,  // Neither has timestamp: keep order
         });
 
-        // Dedup by phone-number suffix, keeping the most-recent entry
+        // Dedup by phone key, keeping the most-recent entry
         // Then take up to max_suggestions and drop the timestamp
         let mut seen_numbers = HashSet::new();
         entries
             .into_iter()
-            .filter(|(_, phone, _)| {
-                let normalized = normalize_phone_number(phone);
-                seen_numbers.insert(phone_suffix(&normalized).to_string())
-            })
+            .filter(|(_, phone, _)| seen_numbers.insert(phone_key(phone)))
             .take(max_suggestions)
             .map(|(name, phone, _)| (name, phone))
             .collect()
     }
 
     /// Check if a phone number is already in the committed recipients list.
-    /// Uses suffix matching (last 10 digits) to handle format variations.
+    /// Compares region-aware phone keys to handle format variations.
     pub(crate) fn is_recipient_duplicate(&self, phone: &str) -> bool {
-        let normalized = normalize_phone_number(phone);
-        let suffix = phone_suffix(&normalized);
-        self.new_message_recipients.iter().any(|(_, existing)| {
-            let existing_normalized = normalize_phone_number(existing);
-            phone_suffix(&existing_normalized) == suffix
-        })
+        let key = phone_key(phone);
+        self.new_message_recipients
+            .iter()
+            .any(|(_, existing)| phone_key(existing) == key)
     }
 
     /// Generate contact suggestions filtered to exclude already-added recipients.
@@ -332,7 +592,362 @@ impl SmsConversationStore {
             .collect()
     }
 
+    /// SIM the open thread last used, which `replyToConversation` sends on.
+    fn thread_sim(&self) -> Option<i64> {
+        self.current_thread_id
+            .and_then(|thread_id| self.logical_for(thread_id))
+            .map(|lc| lc.subscription_id)
+            .filter(|&sub_id| sub_id != DEFAULT_SUB_ID)
+    }
+
+    /// Default the SIM selector for the compose view being opened: the
+    /// thread's last SIM, else the last SIM sent from. Call after
+    /// `current_thread_id` is set (or cleared, for a new message). No
+    /// selection unless the phone has more than one SIM.
+    pub(crate) fn reset_selected_sim(&mut self) {
+        let last_used = self
+            .sms_device_id
+            .as_ref()
+            .and_then(|device_id| self.last_used_sim.get(device_id))
+            .copied();
+        self.selected_sim = if self.known_sims.len() > 1 {
+            self.thread_sim().or(last_used)
+        } else {
+            None
+        };
+    }
+
+    /// SIM override for a send from the open compose view.
+    fn send_sim(&mut self) -> Option<i64> {
+        if let (Some(device_id), Some(sim)) = (&self.sms_device_id, self.selected_sim) {
+            self.last_used_sim.insert(device_id.clone(), sim);
+        }
+        sim_override(self.selected_sim, self.thread_sim())
+    }
+
+    /// Record the open thread's compose text as its draft (an empty box
+    /// removes the draft).
+    pub(crate) fn stash_thread_draft(&mut self) {
+        if let (Some(device_id), Some(thread_id)) = (&self.sms_device_id, self.current_thread_id) {
+            if self
+                .drafts
+                .set_thread(device_id, thread_id, &self.sms_compose_text.text())
+            {
+                self.dirty_drafts
+                    .insert(DraftSlot::Thread(device_id.clone(), thread_id));
+            }
+        }
+    }
+
+    /// Fill the compose box from the open thread's draft, if any.
+    pub(crate) fn restore_thread_draft(&mut self) {
+        let draft = self.sms_device_id.as_deref().and_then(|device_id| {
+            self.drafts
+                .thread(device_id, &self.current_merged_thread_ids)
+        });
+        self.sms_compose_text = match draft {
+            Some(text) => widget::text_editor::Content::with_text(text),
+            None => widget::text_editor::Content::new(),
+        };
+    }
+
+    /// Record the new-message recipients and body as the device's draft.
+    pub(crate) fn stash_new_message_draft(&mut self) {
+        if !self.new_message_from_draft {
+            return;
+        }
+        if let Some(device_id) = &self.sms_device_id {
+            let draft = NewMessageDraft {
+                recipients: self.new_message_recipients.clone(),
+                body: self.new_message_body.text(),
+            };
+            if self.drafts.set_new_message(device_id, draft) {
+                self.dirty_drafts
+                    .insert(DraftSlot::NewMessage(device_id.clone()));
+            }
+        }
+    }
+
+    /// Fill the new-message view from the device's draft, if any.
+    pub(crate) fn restore_new_message_draft(&mut self) {
+        let draft = self
+            .sms_device_id
+            .as_deref()
+            .and_then(|device_id| self.drafts.new_message(device_id))
+            .cloned()
+            .unwrap_or_default();
+        self.new_message_recipients = draft.recipients;
+        self.new_message_body = widget::text_editor::Content::with_text(&draft.body);
+        self.new_message_from_draft = true;
+    }
+
+    /// Start a new message to a single contact with an empty body, without
+    /// touching the device's saved new-message draft.
+    pub(crate) fn start_message_to(&mut self, name: String, phone: String) {
+        self.new_message_recipients = vec![(name, phone)];
+        self.new_message_body = widget::text_editor::Content::new();
+        self.new_message_from_draft = false;
+    }
+
+    /// Write the drafts changed since the last write to disk.
+    pub(crate) fn flush_drafts(&mut self) -> cosmic::app::Task<Message> {
+        if self.dirty_drafts.is_empty() {
+            return cosmic::app::Task::none();
+        }
+        let slots = self.dirty_drafts.drain().collect();
+        cosmic::app::Task::perform(save_drafts_async(self.drafts.clone(), slots), |result| {
+            cosmic::Action::App(Message::SmsDraftsSaved(result))
+        })
+    }
+
+    /// Take drafts read from disk, keeping the ones changed here since.
+    fn adopt_drafts(&mut self, drafts: SmsDrafts) {
+        let mine = std::mem::replace(&mut self.drafts, drafts);
+        for slot in &self.dirty_drafts {
+            self.drafts.copy_slot(&mine, slot);
+        }
+    }
+
+    /// Swap the in-memory archive to `device_id`'s. Writes out any unsaved
+    /// changes for the previous device first, then loads the new archive
+    /// from disk. Call before `sms_device_id` is updated.
+    pub(crate) fn open_archive(&mut self, device_id: &str) -> cosmic::app::Task<Message> {
+        let mut tasks = Vec::new();
+        if let (true, Some(old_device_id)) = (self.archive_dirty, self.sms_device_id.clone()) {
+            let old_archive = std::mem::take(&mut self.archive);
+            tasks.push(cosmic::app::Task::perform(
+                save_archive_async(old_device_id, old_archive),
+                |result| cosmic::Action::App(Message::SmsArchiveSaved(result)),
+            ));
+        }
+        self.archive = SmsArchive::default();
+        self.archive_dirty = false;
+        self.search_stale = true;
+
+        let device_id = device_id.to_string();
+        tasks.push(cosmic::app::Task::perform(
+            load_archive_async(device_id.clone()),
+            move |archive| {
+                cosmic::Action::App(Message::SmsArchiveLoaded(device_id.clone(), archive))
+            },
+        ));
+        cosmic::app::Task::batch(tasks)
+    }
+
+    /// Close the "Send later" picker and any scheduled-message edit.
+    pub(crate) fn close_schedule_ui(&mut self) {
+        self.schedule_picker_open = false;
+        self.schedule_time_input.clear();
+        self.editing_scheduled = None;
+    }
+
+    /// Scheduled messages for this device matching `filter`, plus the
+    /// picker/edit state, for the compose views.
+    fn schedule_view_state(
+        &self,
+        filter: impl Fn(&ScheduledMessage) -> bool,
+    ) -> ScheduleViewState<'_> {
+        ScheduleViewState {
+            scheduled: self
+                .scheduled
+                .iter()
+                .filter(|s| self.sms_device_id.as_ref() == Some(&s.device_id) && filter(s))
+                .collect(),
+            editing: self
+                .editing_scheduled
+                .as_ref()
+                .map(|(id, draft)| (*id, draft.as_str())),
+            picker_open: self.schedule_picker_open,
+            time_input: &self.schedule_time_input,
+        }
+    }
+
+    /// Apply `change` here and to the file on disk.
+    fn change_scheduled(&mut self, change: ScheduledChange) -> cosmic::app::Task<Message> {
+        change.apply(&mut self.scheduled);
+        self.scheduled_saves_pending += 1;
+        cosmic::app::Task::perform(update_scheduled_async(change), |result| {
+            cosmic::Action::App(Message::ScheduledSmsSaved(result))
+        })
+    }
+
+    /// Take the scheduled messages read back from the file, which include
+    /// changes by other applet processes, unless a write of ours is still
+    /// on its way (its result will carry the newer list).
+    fn adopt_scheduled(&mut self, scheduled: Vec<ScheduledMessage>) {
+        if self.scheduled_saves_pending > 0 {
+            return;
+        }
+        self.scheduled = scheduled;
+        if let Some((id, _)) = &self.editing_scheduled {
+            if !self.scheduled.iter().any(|s| s.id == *id) {
+                self.editing_scheduled = None;
+            }
+        }
+    }
+
+    /// Claim every scheduled message that is due and whose device is in
+    /// `reachable`. Every applet process runs this timer, so a message is
+    /// only sent once the claim in the file is won (see
+    /// `Message::ScheduledSmsClaimed`).
+    pub(crate) fn claim_due_scheduled(
+        &mut self,
+        reachable: &[String],
+    ) -> cosmic::app::Task<Message> {
+        let claim = SendClaim::ours(chrono::Utc::now().timestamp_millis());
+        let due: Vec<i64> = self
+            .scheduled
+            .iter()
+            .filter(|s| s.is_due(claim.at_ms) && reachable.contains(&s.device_id))
+            .map(|s| s.id)
+            .collect();
+        if due.is_empty() {
+            return cosmic::app::Task::none();
+        }
+        // Marked here too, so the next tick doesn't claim them again.
+        let change = ScheduledChange::Claim(due, claim);
+        change.apply(&mut self.scheduled);
+        cosmic::app::Task::perform(update_scheduled_async(change), move |result| {
+            cosmic::Action::App(Message::ScheduledSmsClaimed(claim, result))
+        })
+    }
+
+    /// Send scheduled messages this process claimed. Replies to the open
+    /// thread go through `replyToConversation`; anything else uses
+    /// `sendWithoutConversation`, which doesn't depend on the daemon having
+    /// loaded the thread.
+    fn send_claimed_scheduled(
+        &mut self,
+        conn: &Arc<Mutex<Connection>>,
+        claimed: Vec<ScheduledMessage>,
+    ) -> cosmic::app::Task<Message> {
+        let mut tasks = Vec::new();
+        for scheduled in claimed {
+            let id = scheduled.id;
+            let outcome = move |msg: Message| {
+                let result = match msg {
+                    Message::SmsSendResult(r) | Message::NewMessageSendResult(r) => r.map(|_| ()),
+                    _ => Err("Unexpected send result".to_string()),
+                };
+                cosmic::Action::App(Message::ScheduledSmsSent(id, result))
+            };
+            let thread_open = self.sms_device_id.as_ref() == Some(&scheduled.device_id);
+            tracing::info!("Sending scheduled SMS {} (due {})", id, scheduled.due_ms);
+            tasks.push(match scheduled.target {
+                ScheduledTarget::Thread {
+                    thread_id,
+                    addresses,
+                } if thread_open && self.current_merged_thread_ids.contains(&thread_id) => {
+                    cosmic::app::Task::perform(
+                        send_sms_async(
+                            conn.clone(),
+                            scheduled.device_id,
+                            self.reply_target(thread_id),
+                            addresses,
+                            scheduled.body,
+                            scheduled.attachment_urls,
+                            scheduled.sub_id,
+                        ),
+                        outcome,
+                    )
+                }
+                ScheduledTarget::Thread {
+                    addresses: recipients,
+                    ..
+                }
+                | ScheduledTarget::NewConversation { recipients } => cosmic::app::Task::perform(
+                    send_new_sms_async(
+                        conn.clone(),
+                        scheduled.device_id,
+                        recipients,
+                        scheduled.body,
+                        scheduled.attachment_urls,
+                        scheduled.sub_id,
+                    ),
+                    outcome,
+                ),
+            });
+        }
+        cosmic::app::Task::batch(tasks)
+    }
+
+    /// Record a message in the archive, flagging it for the next flush.
+    fn archive_message(&mut self, message: &SmsMessage) {
+        if self.archive.merge_message(message) {
+            self.archive_dirty = true;
+            self.search_stale = true;
+        }
+    }
+
+    /// Snap the thread to the pending search target if it has loaded.
+    /// The offset is proportional to the message index — bubble heights
+    /// vary, but it lands the match in view for typical threads.
+    fn snap_to_search_target(&mut self) -> Option<cosmic::app::Task<Message>> {
+        let uid = self.search_target_uid?;
+        let index = self.messages.iter().position(|m| m.uid == uid)?;
+        self.search_target_uid = None;
+        self.search_highlight_uid = Some(uid);
+        let relative_y = if self.messages.len() > 1 {
+            index as f32 / (self.messages.len() - 1) as f32
+        } else {
+            0.0
+        };
+        Some(scrollable::snap_to(
+            widget::Id::new("message-thread"),
+            scrollable::RelativeOffset {
+                x: Some(0.0),
+                y: Some(relative_y),
+            },
+        ))
+    }
+
+    /// Jump to the pending search target, paging in older messages until
+    /// it shows up or the thread runs out of history.
+    fn continue_search_target(&mut self, ctx: &SmsCtx) -> cosmic::app::Task<Message> {
+        if let Some(task) = self.snap_to_search_target() {
+            return task;
+        }
+        if self.messages_has_more && !self.is_loading_more_messages() {
+            if let (Some(conn), Some(device_id), Some(thread_id)) = (
+                ctx.conn,
+                self.sms_device_id.as_ref(),
+                self.current_thread_id,
+            ) {
+                tracing::debug!(
+                    "Search target {:?} not loaded yet, fetching older messages",
+                    self.search_target_uid
+                );
+                self.sms_loading_state = SmsLoadingState::LoadingMoreMessages;
+                return cosmic::app::Task::perform(
+                    fetch_older_messages_async(
+                        conn.clone(),
+                        device_id.clone(),
+                        thread_id,
+                        self.messages_loaded_count,
+                        MESSAGES_PER_PAGE,
+                    ),
+                    cosmic::Action::App,
+                );
+            }
+        }
+        self.search_target_uid = None;
+        cosmic::app::Task::none()
+    }
+
     pub fn update(&mut self, msg: Message, ctx: &SmsCtx) -> (cosmic::app::Task<Message>, SmsReply) {
+        let result = self.handle(msg, ctx);
+        // Once per message rather than per archived SMS, so a bulk load
+        // doesn't re-run the search for every message it adds.
+        if self.search_stale {
+            self.refresh_search();
+        }
+        if self.sims_stale {
+            self.refresh_known_sims();
+        }
+        result
+    }
+
+    fn handle(&mut self, msg: Message, ctx: &SmsCtx) -> (cosmic::app::Task<Message>, SmsReply) {
         match msg {
             // === Batch 1: Conversation list ===
             Message::ConversationsLoaded(convs) => {
@@ -356,6 +971,9 @@ impl SmsConversationStore {
                         }
                     }
 
+                    for conv in &convs {
+                        self.archive_dirty |= self.archive.upsert_conversation(conv);
+                    }
                     self.raw_conversations = convs;
                     self.rederive_conversations(ctx.config);
                     self.conversation_list_key = self.conversation_list_key.wrapping_add(1);
@@ -394,6 +1012,8 @@ impl SmsConversationStore {
                     return (cosmic::app::Task::none(), SmsReply::NoOp);
                 }
 
+                self.archive_dirty |= self.archive.upsert_conversation(&conversation);
+
                 // Upsert into raw cache by underlying thread_id. Re-derive
                 // logical conversations after — incremental upsert on the
                 // logical list would create duplicate groups when a new
@@ -497,7 +1117,7 @@ impl SmsConversationStore {
                         contacts.len(),
                         device_id
                     );
-                    self.contacts = contacts;
+                    self.set_contacts(contacts);
                 } else {
                     tracing::debug!(
                         "Ignoring contacts for device {} (current: {:?})",
@@ -508,6 +1128,549 @@ impl SmsConversationStore {
                 (cosmic::app::Task::none(), SmsReply::NoOp)
             }
 
+            Message::CheckContactsChanged | Message::ContactsCacheSynchronized => {
+                if matches!(msg, Message::ContactsCacheSynchronized) {
+                    self.contacts_signal_seen = true;
+                }
+                let Some(device_id) = self.sms_device_id.clone() else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                (
+                    cosmic::app::Task::perform(
+                        self.contacts.reload_changed(&device_id),
+                        move |reloaded| match reloaded {
+                            Some(contacts) => {
+                                cosmic::Action::App(Message::ContactsLoaded(device_id, contacts))
+                            }
+                            None => cosmic::Action::None,
+                        },
+                    ),
+                    SmsReply::NoOp,
+                )
+            }
+
+            Message::SyncContacts => {
+                let (Some(conn), Some(device_id)) = (ctx.conn, self.sms_device_id.clone()) else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                if self.contacts_syncing {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                }
+                self.contacts_syncing = true;
+                (
+                    cosmic::app::Task::perform(
+                        sync_contacts_async(conn.clone(), device_id),
+                        |result| cosmic::Action::App(Message::SyncContactsResult(result)),
+                    ),
+                    SmsReply::NoOp,
+                )
+            }
+
+            Message::SyncContactsResult(result) => {
+                self.contacts_syncing = false;
+                match result {
+                    // New vCards are announced by localCacheSynchronized
+                    Ok(()) => (
+                        cosmic::app::Task::none(),
+                        SmsReply::Status(fl!("contacts-sync-requested")),
+                    ),
+                    Err(err) => {
+                        tracing::warn!("Contact sync failed: {}", err);
+                        (
+                            cosmic::app::Task::none(),
+                            SmsReply::Status(fl!("contacts-sync-failed")),
+                        )
+                    }
+                }
+            }
+
+            Message::SmsArchiveLoaded(device_id, loaded) => {
+                if self.sms_device_id.as_ref() != Some(&device_id) {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                }
+
+                // Signals may have landed before the file was read; fold them
+                // over the archived state so nothing live is lost.
+                let live = std::mem::replace(&mut self.archive, loaded);
+                self.archive.merge_from(live);
+                self.search_stale = true;
+
+                // Nothing from the phone yet (cold open or offline): render
+                // the archived list straight away.
+                if self.raw_conversations.is_empty() && !self.archive.conversations.is_empty() {
+                    for conv in &self.archive.conversations {
+                        let key = (device_id.clone(), conv.thread_id);
+                        let current = self.last_seen_sms.get(&key).copied();
+                        if current.is_none() || current < Some(conv.timestamp) {
+                            self.last_seen_sms.insert(key, conv.timestamp);
+                        }
+                    }
+                    self.raw_conversations = self.archive.conversations.clone();
+                    self.raw_conversations
+                        .sort_by_key(|cs| std::cmp::Reverse(cs.timestamp));
+                    self.raw_conversations
+                        .truncate(kdeconnect_dbus::plugins::MAX_CONVERSATIONS);
+                    self.rederive_conversations(ctx.config);
+                    self.conversation_list_key = self.conversation_list_key.wrapping_add(1);
+                }
+
+                // Drop the spinner once there's something to show, or when no
+                // subscription is coming to fill the list (device offline).
+                if matches!(
+                    self.sms_loading_state,
+                    SmsLoadingState::LoadingConversations(_)
+                ) && (!self.conversations.is_empty()
+                    || !self.conversation_list_subscription_active)
+                {
+                    self.sms_loading_state = SmsLoadingState::Idle;
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::SmsArchiveFlush => {
+                if let (true, Some(device_id)) = (self.archive_dirty, self.sms_device_id.clone()) {
+                    self.archive_dirty = false;
+                    return (
+                        cosmic::app::Task::perform(
+                            save_archive_async(device_id, self.archive.clone()),
+                            |result| cosmic::Action::App(Message::SmsArchiveSaved(result)),
+                        ),
+                        SmsReply::NoOp,
+                    );
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::SmsArchiveSaved(result) => {
+                if let Err(err) = result {
+                    tracing::warn!("Failed to save SMS archive: {}", err);
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::SmsDraftsLoaded(loaded) => {
+                self.adopt_drafts(loaded);
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::SmsDraftsFlush => (self.flush_drafts(), SmsReply::NoOp),
+            Message::SmsDraftsSaved(result) => {
+                // The file now also holds drafts saved by other processes.
+                match result {
+                    Ok(saved) => self.adopt_drafts(saved),
+                    Err(err) => tracing::warn!("Failed to save SMS drafts: {}", err),
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::ToggleConversationFlag(flag) => {
+                let (Some(device_id), Some(addresses)) =
+                    (&self.sms_device_id, &self.current_thread_addresses)
+                else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                let on = self.conversation_flags.toggle(device_id, addresses, flag);
+                tracing::info!("Conversation flag {:?} set to {}", flag, on);
+                self.flag_saves_pending += 1;
+                let save = save_flag_async(device_id.clone(), addresses.clone(), flag, on);
+                if flag == ConversationFlag::Pinned {
+                    self.rederive_conversations(ctx.config);
+                } else {
+                    self.refresh_list_flags();
+                }
+                let status = match (flag, on) {
+                    (ConversationFlag::Archived, true) => {
+                        SmsReply::Status(fl!("conversation-archived"))
+                    }
+                    (ConversationFlag::Muted, true) => SmsReply::Status(fl!("conversation-muted")),
+                    _ => SmsReply::NoOp,
+                };
+                (
+                    cosmic::app::Task::perform(save, |result| {
+                        cosmic::Action::App(Message::ConversationFlagsSaved(result))
+                    }),
+                    status,
+                )
+            }
+            Message::ToggleArchivedSection => {
+                self.show_archived = !self.show_archived;
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::ConversationFlagsLoaded(mut loaded) => {
+                // Keep anything toggled before the file finished loading.
+                for (device_id, flags) in std::mem::take(&mut self.conversation_flags.devices) {
+                    loaded.devices.entry(device_id).or_default().extend(flags);
+                }
+                self.conversation_flags = loaded;
+                self.rederive_conversations(ctx.config);
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::ConversationFlagsSaved(result) => {
+                self.flag_saves_pending = self.flag_saves_pending.saturating_sub(1);
+                match result {
+                    // Pick up flags changed by other applet processes, unless
+                    // a later toggle's write is still on its way.
+                    Ok(saved) if self.flag_saves_pending == 0 => {
+                        self.conversation_flags = saved;
+                        self.rederive_conversations(ctx.config);
+                    }
+                    Ok(_) => {}
+                    Err(err) => tracing::warn!("Failed to save conversation flags: {}", err),
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::SelectSim(sub_id) => {
+                self.selected_sim = Some(sub_id);
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            // Scheduled ("Send later") messages
+            Message::ToggleSchedulePicker => {
+                self.schedule_picker_open = !self.schedule_picker_open;
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::ScheduleTimeInput(input) => {
+                self.schedule_time_input = input;
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::ScheduleSms(preset) => {
+                let Some(device_id) = self.sms_device_id.clone() else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                let now = chrono::Local::now();
+                let Some(due) = preset.due_at(now, &self.schedule_time_input) else {
+                    return (
+                        cosmic::app::Task::none(),
+                        SmsReply::Status(fl!("schedule-invalid-time")),
+                    );
+                };
+
+                // The open thread's compose box, otherwise the new-message one.
+                let in_thread = self.current_thread_id.is_some();
+                let (target, body) = if let Some(thread_id) = self.current_thread_id {
+                    (
+                        ScheduledTarget::Thread {
+                            thread_id,
+                            addresses: self.current_thread_addresses.clone().unwrap_or_default(),
+                        },
+                        self.sms_compose_text.text(),
+                    )
+                } else {
+                    (
+                        ScheduledTarget::NewConversation {
+                            recipients: self
+                                .new_message_recipients
+                                .iter()
+                                .map(|(_, phone)| phone.clone())
+                                .collect(),
+                        },
+                        self.new_message_body.text(),
+                    )
+                };
+                let no_recipients = matches!(
+                    &target,
+                    ScheduledTarget::NewConversation { recipients } if recipients.is_empty()
+                );
+                if no_recipients || (body.trim().is_empty() && self.pending_attachments.is_empty())
+                {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                }
+
+                let sub_id = self.send_sim();
+                let scheduled = ScheduledMessage {
+                    id: now.timestamp_millis(),
+                    device_id,
+                    target,
+                    body: body.trim_end().to_string(),
+                    attachment_urls: self.pending_attachments.iter().map(|a| a.url()).collect(),
+                    due_ms: due.timestamp_millis(),
+                    sub_id,
+                    last_error: None,
+                    attempts: 0,
+                    retry_at_ms: 0,
+                    claim: None,
+                };
+                let status = fl!("sms-scheduled", time = scheduled.due_label());
+                tracing::info!("Scheduled SMS {} for {}", scheduled.id, due);
+                let save = self.change_scheduled(ScheduledChange::Add(scheduled));
+
+                self.pending_attachments.clear();
+                self.schedule_picker_open = false;
+                self.schedule_time_input.clear();
+                if in_thread {
+                    self.sms_compose_text = widget::text_editor::Content::new();
+                    self.stash_thread_draft();
+                    (save, SmsReply::Status(status))
+                } else {
+                    self.new_message_recipients.clear();
+                    self.new_message_recipient_input.clear();
+                    self.new_message_body = widget::text_editor::Content::new();
+                    self.stash_new_message_draft();
+                    (save, SmsReply::NewMessageSent(status))
+                }
+            }
+            Message::CancelScheduledSms(id) => {
+                if self
+                    .editing_scheduled
+                    .as_ref()
+                    .is_some_and(|(e, _)| *e == id)
+                {
+                    self.editing_scheduled = None;
+                }
+                (
+                    self.change_scheduled(ScheduledChange::Cancel(id)),
+                    SmsReply::NoOp,
+                )
+            }
+            Message::RetryScheduledSms(id) => (
+                self.change_scheduled(ScheduledChange::Retry(id)),
+                SmsReply::NoOp,
+            ),
+            Message::EditScheduledSms(id) => {
+                self.editing_scheduled = self
+                    .scheduled
+                    .iter()
+                    .find(|s| s.id == id)
+                    .map(|s| (id, s.body.clone()));
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::ScheduledEditInput(text) => {
+                if let Some((_, draft)) = self.editing_scheduled.as_mut() {
+                    *draft = text;
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::SaveScheduledEdit => {
+                let Some((id, draft)) = self.editing_scheduled.take() else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                // Refused (here and in the file) once a send is under way.
+                (
+                    self.change_scheduled(ScheduledChange::Edit(id, draft)),
+                    SmsReply::NoOp,
+                )
+            }
+            Message::ScheduledSmsLoaded(loaded) => {
+                // Anything scheduled before the file finished loading is
+                // already on its way to the file.
+                self.adopt_scheduled(loaded);
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+            Message::ScheduledSmsClaimed(claim, result) => {
+                let scheduled = match result {
+                    Ok(scheduled) => scheduled,
+                    Err(err) => {
+                        // Release the local marks; the file wasn't changed.
+                        tracing::warn!("Failed to claim scheduled SMS: {}", err);
+                        for s in &mut self.scheduled {
+                            if s.claim == Some(claim) {
+                                s.claim = None;
+                            }
+                        }
+                        return (cosmic::app::Task::none(), SmsReply::NoOp);
+                    }
+                };
+                // Only what this process won; another process sends the rest.
+                let claimed: Vec<ScheduledMessage> = scheduled
+                    .iter()
+                    .filter(|s| s.claim == Some(claim))
+                    .cloned()
+                    .collect();
+                self.adopt_scheduled(scheduled);
+                match ctx.conn {
+                    Some(conn) => (self.send_claimed_scheduled(conn, claimed), SmsReply::NoOp),
+                    None => {
+                        let now_ms = chrono::Utc::now().timestamp_millis();
+                        let tasks: Vec<_> = claimed
+                            .into_iter()
+                            .map(|s| {
+                                self.change_scheduled(ScheduledChange::Failed(
+                                    s.id,
+                                    "Not connected to D-Bus".to_string(),
+                                    now_ms,
+                                ))
+                            })
+                            .collect();
+                        (cosmic::app::Task::batch(tasks), SmsReply::NoOp)
+                    }
+                }
+            }
+            Message::ScheduledSmsSent(id, result) => match result {
+                Ok(()) => {
+                    tracing::info!("Scheduled SMS {} sent", id);
+                    if self
+                        .editing_scheduled
+                        .as_ref()
+                        .is_some_and(|(e, _)| *e == id)
+                    {
+                        self.editing_scheduled = None;
+                    }
+                    (
+                        self.change_scheduled(ScheduledChange::Sent(id)),
+                        SmsReply::Status(fl!("scheduled-sms-sent")),
+                    )
+                }
+                Err(err) => {
+                    let now_ms = chrono::Utc::now().timestamp_millis();
+                    let save =
+                        self.change_scheduled(ScheduledChange::Failed(id, err.clone(), now_ms));
+                    let gave_up = self.scheduled.iter().any(|s| s.id == id && s.has_failed());
+                    let status = if gave_up {
+                        tracing::warn!("Scheduled SMS {} failed, giving up: {}", id, err);
+                        fl!("scheduled-sms-failed")
+                    } else {
+                        tracing::warn!("Scheduled SMS {} failed, will retry: {}", id, err);
+                        format!("{}: {}", fl!("sms-failed"), err)
+                    };
+                    (save, SmsReply::Status(status))
+                }
+            },
+            Message::ScheduledSmsSaved(result) => {
+                self.scheduled_saves_pending = self.scheduled_saves_pending.saturating_sub(1);
+                match result {
+                    Ok(scheduled) => self.adopt_scheduled(scheduled),
+                    Err(err) => tracing::warn!("Failed to save scheduled SMS: {}", err),
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::ToggleSmsExportMenu => {
+                self.export_menu_open = !self.export_menu_open;
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::ExportSms(format) => {
+                self.export_menu_open = false;
+
+                // An open thread exports just that conversation; the list
+                // exports every conversation.
+                let scopes: Vec<(Vec<String>, Vec<i64>)> = if self.current_thread_id.is_some() {
+                    vec![(
+                        self.current_thread_addresses.clone().unwrap_or_default(),
+                        self.current_merged_thread_ids.clone(),
+                    )]
+                } else {
+                    self.conversations
+                        .iter()
+                        .map(|lc| (lc.addresses.clone(), lc.merged_thread_ids.clone()))
+                        .collect()
+                };
+                let thread_ids: Vec<i64> = scopes
+                    .iter()
+                    .flat_map(|(_, ids)| ids.iter().copied())
+                    .collect();
+
+                // The archive only holds what has been opened or synced, which
+                // for most threads is just the latest message; load the full
+                // history from the daemon first.
+                match (ctx.conn, self.sms_device_id.clone()) {
+                    (Some(conn), Some(device_id)) => (
+                        cosmic::app::Task::perform(
+                            fetch_threads_for_export_async(conn.clone(), device_id, thread_ids),
+                            move |(messages, incomplete)| {
+                                cosmic::Action::App(Message::SmsExportThreadsLoaded(
+                                    format, scopes, messages, incomplete,
+                                ))
+                            },
+                        ),
+                        SmsReply::SetStatus(Some(fl!("sms-export-loading"))),
+                    ),
+                    _ => self.update(
+                        Message::SmsExportThreadsLoaded(format, scopes, Vec::new(), thread_ids),
+                        ctx,
+                    ),
+                }
+            }
+
+            Message::SmsExportThreadsLoaded(format, scopes, messages, incomplete) => {
+                for message in &messages {
+                    self.archive_message(message);
+                }
+                let incomplete_count = scopes
+                    .iter()
+                    .filter(|(_, ids)| ids.iter().any(|id| incomplete.contains(id)))
+                    .count();
+                let exported: Vec<_> = scopes
+                    .into_iter()
+                    .map(|(addresses, thread_ids)| {
+                        let threads = self
+                            .raw_conversations
+                            .iter()
+                            .filter(|cs| thread_ids.contains(&cs.thread_id))
+                            .cloned()
+                            .collect();
+                        build_conversation(
+                            &addresses,
+                            threads,
+                            self.archive.messages_for(&thread_ids),
+                            &self.contacts,
+                        )
+                    })
+                    .collect();
+
+                match render(&exported, format) {
+                    Ok(contents) => (
+                        cosmic::app::Task::perform(
+                            save_export_async(
+                                fl!("export-messages"),
+                                default_file_name(format),
+                                contents,
+                            ),
+                            move |result| {
+                                cosmic::Action::App(Message::SmsExportComplete(
+                                    result,
+                                    incomplete_count,
+                                ))
+                            },
+                        ),
+                        SmsReply::NoOp,
+                    ),
+                    Err(err) => (
+                        cosmic::app::Task::none(),
+                        SmsReply::Status(fl!("sms-export-failed", error = err)),
+                    ),
+                }
+            }
+
+            Message::SmsExportComplete(result, incomplete) => match result {
+                Ok(Some(path)) if incomplete > 0 => (
+                    cosmic::app::Task::none(),
+                    SmsReply::Status(fl!(
+                        "sms-export-incomplete",
+                        path = path.display().to_string(),
+                        count = incomplete
+                    )),
+                ),
+                Ok(Some(path)) => (
+                    cosmic::app::Task::none(),
+                    SmsReply::Status(fl!(
+                        "sms-export-complete",
+                        path = path.display().to_string()
+                    )),
+                ),
+                // Save dialog cancelled
+                Ok(None) => (cosmic::app::Task::none(), SmsReply::SetStatus(None)),
+                Err(err) => {
+                    tracing::warn!("SMS export failed: {}", err);
+                    (
+                        cosmic::app::Task::none(),
+                        SmsReply::Status(fl!("sms-export-failed", error = err)),
+                    )
+                }
+            },
+
+            Message::SmsSearchInput(query) => {
+                self.search_query = query;
+                self.refresh_search();
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
+            Message::ContactsSearchInput(query) => {
+                self.contacts_query = query;
+                self.refresh_contact_sections();
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
             Message::LoadMoreConversations => {
                 // Show 10 more conversations (up to total available)
                 self.conversations_displayed =
@@ -539,13 +1702,17 @@ impl SmsConversationStore {
                 }
 
                 if self.current_merged_thread_ids.contains(&thread_id) {
-                    // Filter out messages already known (safety net for signal cross-talk)
+                    // Filter out messages already known (safety net for signal
+                    // cross-talk) and failed copies of retried messages
                     let older_msgs: Vec<_> = older_msgs
                         .into_iter()
-                        .filter(|m| !self.known_message_ids.contains(&m.uid))
+                        .filter(|m| {
+                            !self.known_message_ids.contains(&m.uid) && !self.archive.is_retried(m)
+                        })
                         .collect();
                     for m in &older_msgs {
                         self.known_message_ids.insert(m.uid);
+                        self.archive_message(m);
                     }
 
                     if !older_msgs.is_empty() {
@@ -562,6 +1729,7 @@ impl SmsConversationStore {
                         let mut combined = older_msgs;
                         combined.append(&mut self.messages);
                         self.messages = combined;
+                        self.sims_stale = true;
 
                         // Update loaded count
                         self.messages_loaded_count = self.messages.len() as u32;
@@ -573,6 +1741,11 @@ impl SmsConversationStore {
                             None => has_more_heuristic,
                         };
 
+                        // Jumping to a search hit: keep paging until it loads.
+                        if self.search_target_uid.is_some() {
+                            return (self.continue_search_target(ctx), SmsReply::NoOp);
+                        }
+
                         // Calculate scroll adjustment to preserve user's position
                         // When we prepend messages, the content shifts down. We need to
                         // scroll down by the estimated height of the prepended content.
@@ -609,6 +1782,7 @@ impl SmsConversationStore {
                         tracing::info!("No older messages returned for thread {}", thread_id);
                         // No more messages available
                         self.messages_has_more = false;
+                        self.search_target_uid = None;
                         // Clear scroll state
                         self.scroll_offset_before_load = None;
                         self.content_height_before_load = None;
@@ -754,6 +1928,15 @@ impl SmsConversationStore {
                     return (cosmic::app::Task::none(), SmsReply::NoOp);
                 }
 
+                // The phone still holds the failed copy of a retried message
+                if self.archive.is_retried(&message) {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                }
+
+                // The thread is on screen, so anything arriving in it is read.
+                let read_up_to = self.locally_read.entry(thread_id).or_insert(message.date);
+                *read_up_to = (*read_up_to).max(message.date);
+
                 // Reconcile optimistic message: if this incoming sent message
                 // matches our optimistic insert's body within a 5-minute window,
                 // upgrade the optimistic entry in-place instead of inserting a duplicate.
@@ -772,15 +1955,34 @@ impl SmsConversationStore {
                         );
                         self.messages[pos].uid = message.uid;
                         self.messages[pos].date = message.date;
+                        self.messages[pos].raw_type = message.raw_type;
+                        self.archive_message(&message);
                         self.known_message_ids.remove(&OPTIMISTIC_MESSAGE_UID);
                         self.known_message_ids.insert(message.uid);
                         self.sms_sending_body = None;
-                        return (cosmic::app::Task::none(), SmsReply::NoOp);
+                        return (cosmic::app::Task::none(), failure_reply(&message));
                     }
                 }
 
-                // Deduplication: skip if already have this message
+                // Deduplication: skip if already have this message, but pick
+                // up delivery-state changes (outbox → sent / failed), which the
+                // phone reports by re-sending the same uid.
                 if self.known_message_ids.contains(&message.uid) {
+                    if let Some(existing) = self
+                        .messages
+                        .iter_mut()
+                        .find(|m| m.uid == message.uid && m.raw_type != message.raw_type)
+                    {
+                        tracing::info!(
+                            "Message uid={} changed type {} -> {}",
+                            message.uid,
+                            existing.raw_type,
+                            message.raw_type
+                        );
+                        existing.raw_type = message.raw_type;
+                        self.archive_message(&message);
+                        return (cosmic::app::Task::none(), failure_reply(&message));
+                    }
                     tracing::debug!(
                         "Skipping duplicate message uid={} for thread {}",
                         message.uid,
@@ -799,6 +2001,8 @@ impl SmsConversationStore {
                     self.sms_sending_body = None;
                 }
 
+                self.archive_message(&message);
+
                 // Insert message in sorted order by date
                 let insert_pos = self
                     .messages
@@ -806,6 +2010,7 @@ impl SmsConversationStore {
                     .position(|m| m.date > message.date)
                     .unwrap_or(self.messages.len());
                 self.messages.insert(insert_pos, message);
+                self.sims_stale = true;
 
                 tracing::debug!(
                     "Added message to thread {}, now have {} messages",
@@ -875,6 +2080,10 @@ impl SmsConversationStore {
                 self.messages_has_more =
                     self.compute_messages_has_more(total_count, MESSAGES_PER_PAGE as usize);
 
+                if let Some(task) = self.snap_to_search_target() {
+                    return (task, SmsReply::NoOp);
+                }
+
                 // Scroll to bottom to show latest messages
                 if !self.messages.is_empty() {
                     return (
@@ -948,6 +2157,10 @@ impl SmsConversationStore {
                 self.initial_load_complete = true;
                 self.sms_loading_state = SmsLoadingState::Idle;
 
+                if self.search_target_uid.is_some() {
+                    return (self.continue_search_target(ctx), SmsReply::NoOp);
+                }
+
                 if !self.messages.is_empty() {
                     return (
                         scrollable::snap_to(
@@ -963,6 +2176,7 @@ impl SmsConversationStore {
             // === Batch 3: Reply send + attachments + notification ===
             Message::SmsComposeAction(action) => {
                 self.sms_compose_text.perform(action);
+                self.stash_thread_draft();
                 (cosmic::app::Task::none(), SmsReply::NoOp)
             }
             Message::SendSms => {
@@ -981,7 +2195,9 @@ impl SmsConversationStore {
                     self.current_thread_id,
                 ) {
                     let message_text = self.sms_compose_text.text();
-                    if !message_text.trim().is_empty() && !self.sms_sending {
+                    let has_content =
+                        !message_text.trim().is_empty() || !self.pending_attachments.is_empty();
+                    if has_content && !self.sms_sending {
                         self.sms_sending = true;
                         self.sms_sending_body = Some(message_text.clone());
                         // Apply the split-by-case rule: for symmetric merges
@@ -1004,13 +2220,18 @@ impl SmsConversationStore {
                             thread_id,
                             reply_target
                         );
+                        let device_id = device_id.clone();
+                        let sub_id = self.send_sim();
                         return (
                             cosmic::app::Task::perform(
                                 send_sms_async(
                                     conn.clone(),
-                                    device_id.clone(),
+                                    device_id,
                                     reply_target,
+                                    self.current_thread_addresses.clone().unwrap_or_default(),
                                     message_text,
+                                    self.pending_attachments.iter().map(|a| a.url()).collect(),
+                                    sub_id,
                                 ),
                                 cosmic::Action::App,
                             ),
@@ -1034,6 +2255,8 @@ impl SmsConversationStore {
                     Ok(sent_body) => {
                         tracing::info!("SMS sent successfully");
                         self.sms_compose_text = widget::text_editor::Content::new();
+                        self.stash_thread_draft();
+                        self.pending_attachments.clear();
 
                         if let Some(thread_id) = self.current_thread_id {
                             let now_ms = std::time::SystemTime::now()
@@ -1066,10 +2289,11 @@ impl SmsConversationStore {
                                 // same SIM property; sourcing it here closes
                                 // the pre-first-message window where the
                                 // optimistic stamp would have fallen back to -1.
-                                let sub_id = self
-                                    .logical_for(thread_id)
-                                    .map(|lc| lc.subscription_id)
-                                    .unwrap_or(-1);
+                                let sub_id = self.selected_sim.unwrap_or_else(|| {
+                                    self.logical_for(thread_id)
+                                        .map(|lc| lc.subscription_id)
+                                        .unwrap_or(-1)
+                                });
                                 let optimistic = SmsMessage {
                                     body: sent_body,
                                     addresses: self
@@ -1078,6 +2302,8 @@ impl SmsConversationStore {
                                         .unwrap_or_default(),
                                     date: now_ms,
                                     message_type: MessageType::Sent,
+                                    // Outbox until the phone echoes it back
+                                    raw_type: 4,
                                     read: true,
                                     thread_id,
                                     uid: OPTIMISTIC_MESSAGE_UID,
@@ -1085,6 +2311,7 @@ impl SmsConversationStore {
                                     attachments: vec![],
                                 };
                                 self.messages.push(optimistic);
+                                self.sims_stale = true;
                                 self.known_message_ids.insert(OPTIMISTIC_MESSAGE_UID);
                                 self.sms_sending_body = None;
 
@@ -1113,6 +2340,137 @@ impl SmsConversationStore {
                 }
             }
 
+            Message::RetrySms(uid) => {
+                let (Some(conn), Some(device_id), Some(thread_id)) =
+                    (ctx.conn, self.sms_device_id.clone(), self.current_thread_id)
+                else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                // MMS parts live on the phone; only plain text can be resent
+                let Some(failed) = self.messages.iter().find(|m| {
+                    m.uid == uid
+                        && m.delivery_state() == DeliveryState::Failed
+                        && m.attachments.is_empty()
+                }) else {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                };
+                if !self.retrying.insert(uid) {
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                }
+                tracing::info!("Retrying failed SMS uid={}", uid);
+                // Resend on the SIM it failed on; don't touch the compose box
+                let sub_id = sim_override(Some(failed.sub_id), self.thread_sim());
+                (
+                    cosmic::app::Task::perform(
+                        send_sms_async(
+                            conn.clone(),
+                            device_id,
+                            self.reply_target(thread_id),
+                            self.current_thread_addresses.clone().unwrap_or_default(),
+                            failed.body.clone(),
+                            Vec::new(),
+                            sub_id,
+                        ),
+                        move |msg| {
+                            let result = match msg {
+                                Message::SmsSendResult(r) => r.map(|_| ()),
+                                _ => Err("Unexpected send result".to_string()),
+                            };
+                            cosmic::Action::App(Message::RetrySmsResult(uid, result))
+                        },
+                    ),
+                    SmsReply::NoOp,
+                )
+            }
+            Message::RetrySmsResult(uid, result) => {
+                self.retrying.remove(&uid);
+                match result {
+                    Ok(()) => {
+                        // The resend arrives as a new message; drop the
+                        // failed bubble it replaces. The phone keeps its
+                        // failed copy, so the archive remembers it as
+                        // retried to hide it when the thread reloads.
+                        let (retried, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.messages)
+                            .into_iter()
+                            .partition(|m| {
+                                m.uid == uid && m.delivery_state() == DeliveryState::Failed
+                            });
+                        self.messages = kept;
+                        self.sims_stale = true;
+                        for message in &retried {
+                            if self.archive.mark_retried(message) {
+                                self.archive_dirty = true;
+                                self.search_stale = true;
+                            }
+                        }
+                        (cosmic::app::Task::none(), SmsReply::Status(fl!("sms-sent")))
+                    }
+                    Err(err) => {
+                        tracing::error!("SMS retry error: {}", err);
+                        let status = format!("{}: {}", fl!("sms-failed"), err);
+                        (cosmic::app::Task::none(), SmsReply::Status(status))
+                    }
+                }
+            }
+
+            // Outgoing attachments
+            Message::PickSmsAttachments => (
+                cosmic::app::Task::perform(pick_attachments_async(fl!("attach-files")), |picked| {
+                    cosmic::Action::App(Message::SmsAttachmentsPicked(picked))
+                }),
+                SmsReply::NoOp,
+            ),
+            Message::SmsAttachmentsPicked(picked) => {
+                let mut problems = Vec::new();
+                for result in picked {
+                    match result {
+                        Ok(attachment) => {
+                            if self.pending_attachments.len() >= MAX_PENDING_ATTACHMENTS {
+                                problems.push(fl!(
+                                    "attachment-too-many",
+                                    max = MAX_PENDING_ATTACHMENTS
+                                ));
+                                break;
+                            }
+                            if !self
+                                .pending_attachments
+                                .iter()
+                                .any(|a| a.path == attachment.path)
+                            {
+                                self.pending_attachments.push(attachment);
+                            }
+                        }
+                        Err((name, reason)) => problems.push(match reason {
+                            AttachmentRejection::UnsupportedType => {
+                                fl!("attachment-unsupported", name = name)
+                            }
+                            AttachmentRejection::TooLarge => fl!(
+                                "attachment-too-large",
+                                name = name,
+                                max_mb = MAX_ATTACHMENT_BYTES / (1024 * 1024)
+                            ),
+                            AttachmentRejection::Unreadable => {
+                                fl!("attachment-unreadable", name = name)
+                            }
+                        }),
+                    }
+                }
+                if problems.is_empty() {
+                    (cosmic::app::Task::none(), SmsReply::NoOp)
+                } else {
+                    (
+                        cosmic::app::Task::none(),
+                        SmsReply::Status(problems.join("\n")),
+                    )
+                }
+            }
+            Message::RemoveSmsAttachment(index) => {
+                if index < self.pending_attachments.len() {
+                    self.pending_attachments.remove(index);
+                }
+                (cosmic::app::Task::none(), SmsReply::NoOp)
+            }
+
             // Attachment messages
             Message::OpenAttachment {
                 device_id,
@@ -1206,6 +2564,11 @@ impl SmsConversationStore {
                 // Update last seen timestamp for this thread
                 self.last_seen_sms.insert(key, message.date);
 
+                if self.is_muted(&device_id, message.thread_id, &message.addresses) {
+                    tracing::debug!("Conversation muted, skipping SMS notification");
+                    return (cosmic::app::Task::none(), SmsReply::NoOp);
+                }
+
                 // Capture config settings
                 let show_sender = ctx.config.sms_notification_show_sender;
                 let show_content = ctx.config.sms_notification_show_content;
@@ -1282,6 +2645,7 @@ impl SmsConversationStore {
             }
             Message::NewMessageBodyAction(action) => {
                 self.new_message_body.perform(action);
+                self.stash_new_message_draft();
                 (cosmic::app::Task::none(), SmsReply::NoOp)
             }
             Message::AddManualRecipient => {
@@ -1291,6 +2655,7 @@ impl SmsConversationStore {
                     self.new_message_recipients.push((display, input));
                     self.new_message_recipient_input.clear();
                     self.contact_suggestions.clear();
+                    self.stash_new_message_draft();
                     return (
                         widget::text_input::focus(widget::Id::new("new-message-recipient")),
                         SmsReply::NoOp,
@@ -1301,6 +2666,7 @@ impl SmsConversationStore {
             Message::RemoveRecipient(index) => {
                 if index < self.new_message_recipients.len() {
                     self.new_message_recipients.remove(index);
+                    self.stash_new_message_draft();
                 }
                 (cosmic::app::Task::none(), SmsReply::NoOp)
             }
@@ -1310,6 +2676,7 @@ impl SmsConversationStore {
                     self.new_message_recipients.push((display, phone));
                     self.new_message_recipient_input.clear();
                     self.contact_suggestions.clear();
+                    self.stash_new_message_draft();
                     return (
                         widget::text_input::focus(widget::Id::new("new-message-recipient")),
                         SmsReply::NoOp,
@@ -1321,7 +2688,7 @@ impl SmsConversationStore {
                 if let (Some(conn), Some(device_id)) = (ctx.conn, self.sms_device_id.as_ref()) {
                     let body_text = self.new_message_body.text();
                     if !self.new_message_recipients.is_empty()
-                        && !body_text.trim().is_empty()
+                        && (!body_text.trim().is_empty() || !self.pending_attachments.is_empty())
                         && !self.new_message_sending
                     {
                         let recipients: Vec<String> = self
@@ -1331,13 +2698,17 @@ impl SmsConversationStore {
                             .collect();
                         let message = body_text;
                         self.new_message_sending = true;
+                        let device_id = device_id.clone();
+                        let sub_id = self.send_sim();
                         return (
                             cosmic::app::Task::perform(
                                 send_new_sms_async(
                                     conn.clone(),
-                                    device_id.clone(),
+                                    device_id,
                                     recipients,
                                     message,
+                                    self.pending_attachments.iter().map(|a| a.url()).collect(),
+                                    sub_id,
                                 ),
                                 cosmic::Action::App,
                             ),
@@ -1357,6 +2728,8 @@ impl SmsConversationStore {
                         self.new_message_recipients.clear();
                         self.new_message_recipient_input.clear();
                         self.new_message_body = widget::text_editor::Content::new();
+                        self.stash_new_message_draft();
+                        self.pending_attachments.clear();
                         // Enable subscription to catch the new conversation when the phone
                         // syncs back. The subscription listens over a longer window than a
                         // one-shot fetch, giving the phone time to process the send and
@@ -1390,8 +2763,9 @@ impl SmsConversationStore {
     ///
     /// `status_message` is owned by the parent app and threaded through for
     /// the message-thread view's send-confirmation/error banner. `config`
-    /// supplies `merge_reaction_threads` to the conversation-list view so
-    /// the header toggle can show its current state.
+    /// supplies `merge_reaction_threads` to the conversation-list view and
+    /// `show_raw_reactions` to the thread view so the header toggles can
+    /// show their current state.
     pub fn view<'a>(
         &'a self,
         mode: SmsViewMode,
@@ -1404,9 +2778,20 @@ impl SmsConversationStore {
                 conversations: &self.conversations,
                 conversations_displayed: self.conversations_displayed,
                 contacts: &self.contacts,
+                avatars: &self.avatars,
                 loading_state: &self.sms_loading_state,
                 sync_active: self.conversation_sync_active,
                 merge_reaction_threads: config.merge_reaction_threads,
+                export_menu_open: self.export_menu_open,
+                flags: &self.conversation_list_flags,
+                show_archived: self.show_archived,
+                known_sims: &self.known_sims,
+                drafts: self
+                    .sms_device_id
+                    .as_deref()
+                    .and_then(|device_id| self.drafts.threads_for(device_id)),
+                search_query: &self.search_query,
+                search_results: self.search_results.as_deref(),
             }),
             SmsViewMode::MessageThread => {
                 let thread = view_message_thread(MessageThreadParams {
@@ -1415,12 +2800,23 @@ impl SmsConversationStore {
                     thread_addresses: self.current_thread_addresses.as_deref(),
                     messages: &self.messages,
                     contacts: &self.contacts,
+                    avatars: &self.avatars,
                     loading_state: &self.sms_loading_state,
                     sms_compose_text: &self.sms_compose_text,
                     sms_sending: self.sms_sending,
                     sync_active: self.message_sync_active,
                     pressed_bubble_uid: self.pressed_bubble_uid,
                     show_copy_hint: self.show_copy_hint,
+                    search_highlight_uid: self.search_highlight_uid,
+                    export_menu_open: self.export_menu_open,
+                    flags: self.flags_for(self.current_thread_addresses.as_deref().unwrap_or(&[])),
+                    pending_attachments: &self.pending_attachments,
+                    known_sims: &self.known_sims,
+                    selected_sim: self.selected_sim,
+                    schedule: self
+                        .schedule_view_state(|s| s.is_for_thread(&self.current_merged_thread_ids)),
+                    retrying: &self.retrying,
+                    show_raw_reactions: config.show_raw_reactions,
                     status_message,
                 });
                 // popup_container uses Shrink height internally, which sets a
@@ -1435,11 +2831,26 @@ impl SmsConversationStore {
                     .width(cosmic::iced::Length::Fill)
                     .into()
             }
+            SmsViewMode::Contacts => view_contacts(ContactsViewParams {
+                device_name: self.sms_device_name.as_deref(),
+                contacts: &self.contacts,
+                avatars: &self.avatars,
+                sections: &self.contact_sections,
+                threads: &self.direct_threads,
+                query: &self.contacts_query,
+                contacts_syncing: self.contacts_syncing,
+            }),
             SmsViewMode::NewMessage => view_new_message(NewMessageParams {
                 recipients: &self.new_message_recipients,
                 recipient_input: &self.new_message_recipient_input,
                 body: &self.new_message_body,
                 sending: self.new_message_sending,
+                pending_attachments: &self.pending_attachments,
+                known_sims: &self.known_sims,
+                selected_sim: self.selected_sim,
+                schedule: self.schedule_view_state(|s| {
+                    matches!(s.target, ScheduledTarget::NewConversation { .. })
+                }),
                 contact_suggestions: &self.contact_suggestions,
             }),
         }
@@ -1462,6 +2873,52 @@ impl SmsConversationStore {
             }
         }
 
+        // Pick up contacts KDE Connect writes after the initial load
+        if let Some(device_id) = self.sms_device_id.clone() {
+            subs.push(Subscription::run_with(
+                ("contacts_signals", device_id),
+                |(_, device_id)| contacts_signal_subscription(device_id.clone()),
+            ));
+            if !self.contacts_signal_seen {
+                subs.push(
+                    cosmic::iced::time::every(std::time::Duration::from_secs(
+                        CONTACTS_FALLBACK_INTERVAL_SECS,
+                    ))
+                    .map(|_| Message::CheckContactsChanged),
+                );
+            }
+        }
+
+        // Due-check for scheduled messages
+        if !self.scheduled.is_empty() {
+            subs.push(
+                cosmic::iced::time::every(std::time::Duration::from_secs(
+                    SCHEDULED_CHECK_INTERVAL_SECS,
+                ))
+                .map(|_| Message::ScheduledSmsTick),
+            );
+        }
+
+        // Periodic flush of draft changes to disk
+        if !self.dirty_drafts.is_empty() {
+            subs.push(
+                cosmic::iced::time::every(std::time::Duration::from_secs(
+                    DRAFT_FLUSH_INTERVAL_SECS,
+                ))
+                .map(|_| Message::SmsDraftsFlush),
+            );
+        }
+
+        // Periodic flush of archive changes to disk
+        if self.archive_dirty {
+            subs.push(
+                cosmic::iced::time::every(std::time::Duration::from_secs(
+                    ARCHIVE_FLUSH_INTERVAL_SECS,
+                ))
+                .map(|_| Message::SmsArchiveFlush),
+            );
+        }
+
         // Per-thread message subscription (incremental message loading).
         // Fans out one subscription per underlying thread in the open
         // `LogicalConversation` so reactions split into bucket threads load
@@ -2,7 +2,16 @@
 
 use crate::app::{LoadingPhase, Message, SettingKey, SmsLoadingState};
 use crate::fl;
+use crate::sms::attach::PendingAttachment;
+use crate::sms::contacts::ContactSection;
+use crate::sms::export::ExportFormat;
+use crate::sms::flags::{ConversationFlag, ConversationFlags};
 use crate::sms::logical::LogicalConversation;
+use crate::sms::reactions::{collect_reactions, ReactionBadge, ThreadReactions};
+use crate::sms::scheduled::{SchedulePreset, ScheduledMessage};
+use crate::sms::search::{SearchHit, SearchHitKind};
+use crate::sms::sim::sim_slot;
+use crate::ui::widgets::avatar::{avatar, AvatarCache};
 use crate::views::helpers::format_timestamp;
 use base64::Engine;
 use cosmic::applet;
@@ -11,10 +20,12 @@ use cosmic::iced::widget::{column, row};
 use cosmic::iced::{Alignment, ContentFit, Length};
 use cosmic::widget::{self, text};
 use cosmic::Element;
-use kdeconnect_dbus::contacts::ContactLookup;
+use kdeconnect_dbus::contacts::{ContactLookup, PhoneKind};
+use kdeconnect_dbus::phone_key;
 use kdeconnect_dbus::plugins::{
-    is_address_valid, Attachment, MessageType, SmsMessage, OPTIMISTIC_MESSAGE_UID,
+    is_address_valid, Attachment, DeliveryState, MessageType, SmsMessage, OPTIMISTIC_MESSAGE_UID,
 };
+use std::collections::{HashMap, HashSet};
 
 // --- Helper functions for loading state ---
 
@@ -150,6 +161,316 @@ fn view_attachment<'a>(
     .into()
 }
 
+/// Accent pill with a conversation's unread count.
+fn unread_badge<'a>(count: usize) -> Element<'a, Message> {
+    let label = if count > 99 {
+        "99+".to_string()
+    } else {
+        count.to_string()
+    };
+    widget::container(text::caption(label))
+        .padding([0, 6])
+        .class(cosmic::theme::Container::custom(|theme| {
+            let cosmic = theme.cosmic();
+            cosmic::iced::widget::container::Style {
+                background: Some(cosmic::iced::Color::from(cosmic.accent_color()).into()),
+                text_color: Some(cosmic.on_accent_color().into()),
+                border: cosmic::iced::Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+/// Button that opens the file picker for outgoing attachments.
+fn attach_button<'a>() -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name("mail-attachment-symbolic"))
+            .class(cosmic::theme::Button::Link)
+            .on_press(Message::PickSmsAttachments),
+        text::caption(fl!("attach-files")),
+        widget::tooltip::Position::Top,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs)
+    .into()
+}
+
+/// Strip of pending attachments above the compose box: image thumbnails or
+/// a type icon, each with a remove button. `None` when nothing is queued.
+fn view_pending_attachments<'a>(attachments: &[PendingAttachment]) -> Option<Element<'a, Message>> {
+    if attachments.is_empty() {
+        return None;
+    }
+    let sp = cosmic::theme::spacing();
+    let items: Vec<Element<Message>> = attachments
+        .iter()
+        .enumerate()
+        .map(|(index, attachment)| {
+            let preview: Element<Message> = match &attachment.thumbnail {
+                Some(handle) => cosmic::iced::widget::image(handle.clone())
+                    .width(Length::Fixed(56.0))
+                    .height(Length::Fixed(56.0))
+                    .content_fit(ContentFit::Cover)
+                    .into(),
+                None => column![
+                    widget::icon::from_name(attachment_icon(&attachment.mime_type)).size(24),
+                    text::caption(attachment.name.clone())
+                        .wrapping(cosmic::iced::widget::text::Wrapping::None),
+                ]
+                .width(Length::Fixed(56.0))
+                .align_x(Alignment::Center)
+                .into(),
+            };
+            let remove_btn =
+                widget::button::icon(widget::icon::from_name("window-close-symbolic").size(12))
+                    .class(cosmic::theme::Button::Link)
+                    .on_press(Message::RemoveSmsAttachment(index));
+            widget::tooltip(
+                widget::container(
+                    column![remove_btn, preview]
+                        .spacing(sp.space_xxxs)
+                        .align_x(Alignment::End),
+                )
+                .padding(sp.space_xxxs)
+                .class(cosmic::theme::Container::Card),
+                text::caption(attachment.name.clone()),
+                widget::tooltip::Position::Top,
+            )
+            .into()
+        })
+        .collect();
+    Some(
+        widget::container(
+            widget::flex_row(items)
+                .spacing(sp.space_xxs as u16)
+                .width(Length::Fill),
+        )
+        .padding([0, sp.space_xs as u16])
+        .width(Length::Fill)
+        .into(),
+    )
+}
+
+/// "SIM n" for a conversation or message; `None` unless the phone has
+/// more than one SIM.
+fn sim_label(known_sims: &[i64], sub_id: i64) -> Option<String> {
+    if known_sims.len() < 2 {
+        return None;
+    }
+    sim_slot(known_sims, sub_id).map(|slot| fl!("sim-label", slot = slot))
+}
+
+/// "Send from" SIM picker above the compose box; `None` with a single SIM.
+fn view_sim_selector<'a>(
+    known_sims: &[i64],
+    selected: Option<i64>,
+) -> Option<Element<'a, Message>> {
+    if known_sims.len() < 2 {
+        return None;
+    }
+    let sp = cosmic::theme::spacing();
+    let mut sim_row = row![text::caption(fl!("send-from"))]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center);
+    for &sub_id in known_sims {
+        let label = sim_label(known_sims, sub_id).unwrap_or_default();
+        let button = if selected == Some(sub_id) {
+            widget::button::suggested(label)
+        } else {
+            widget::button::standard(label)
+        };
+        sim_row = sim_row.push(
+            button
+                .leading_icon(widget::icon::from_name("network-cellular-symbolic").size(14))
+                .on_press(Message::SelectSim(sub_id)),
+        );
+    }
+    Some(applet::padded_control(sim_row).into())
+}
+
+/// Thread-header toggle for a local conversation flag.
+fn flag_button<'a>(flag: ConversationFlag, active: bool) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    let (icon, tooltip) = match (flag, active) {
+        (ConversationFlag::Pinned, false) => ("view-pin-symbolic", fl!("pin-conversation")),
+        (ConversationFlag::Pinned, true) => ("view-pin-symbolic", fl!("unpin-conversation")),
+        (ConversationFlag::Muted, false) => ("notifications-symbolic", fl!("mute-conversation")),
+        (ConversationFlag::Muted, true) => (
+            "notifications-disabled-symbolic",
+            fl!("unmute-conversation"),
+        ),
+        (ConversationFlag::Archived, false) => ("folder-symbolic", fl!("archive-conversation")),
+        (ConversationFlag::Archived, true) => {
+            ("folder-open-symbolic", fl!("unarchive-conversation"))
+        }
+    };
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name(icon))
+            .class(if active {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Link
+            })
+            .on_press(Message::ToggleConversationFlag(flag)),
+        text::caption(tooltip),
+        widget::tooltip::Position::Bottom,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs)
+    .into()
+}
+
+/// "Send later" state shared by the thread and new-message views.
+pub struct ScheduleViewState<'a> {
+    /// Scheduled messages belonging to this view, soonest first
+    pub scheduled: Vec<&'a ScheduledMessage>,
+    /// Scheduled message being edited, with the draft text
+    pub editing: Option<(i64, &'a str)>,
+    pub picker_open: bool,
+    /// Custom `HH:MM` typed into the picker
+    pub time_input: &'a str,
+}
+
+/// Button that toggles the "Send later" picker; disabled with nothing to send.
+fn schedule_button<'a>(can_schedule: bool) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name("alarm-symbolic"))
+            .class(cosmic::theme::Button::Link)
+            .on_press_maybe(can_schedule.then_some(Message::ToggleSchedulePicker)),
+        text::caption(fl!("send-later")),
+        widget::tooltip::Position::Top,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs)
+    .into()
+}
+
+/// "Send later" picker: two presets plus a custom time of day.
+fn view_schedule_picker<'a>(time_input: &'a str) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    let custom_input = widget::text_input(fl!("schedule-time-placeholder"), time_input)
+        .on_input(Message::ScheduleTimeInput)
+        .on_submit(|_| Message::ScheduleSms(SchedulePreset::Custom))
+        .width(Length::Fixed(72.0));
+    applet::padded_control(
+        widget::flex_row(vec![
+            widget::button::standard(fl!("schedule-in-hour"))
+                .on_press(Message::ScheduleSms(SchedulePreset::InOneHour))
+                .into(),
+            widget::button::standard(fl!("schedule-tomorrow-morning"))
+                .on_press(Message::ScheduleSms(SchedulePreset::TomorrowMorning))
+                .into(),
+            row![
+                custom_input,
+                widget::button::suggested(fl!("schedule")).on_press_maybe(
+                    (!time_input.trim().is_empty())
+                        .then_some(Message::ScheduleSms(SchedulePreset::Custom))
+                ),
+            ]
+            .spacing(sp.space_xxs)
+            .align_y(Alignment::Center)
+            .into(),
+        ])
+        .spacing(sp.space_xxs as u16),
+    )
+    .into()
+}
+
+/// Cards for pending scheduled messages, each with Edit and Cancel, plus
+/// Retry once a message has run out of send attempts. `None` when there
+/// are none.
+fn view_scheduled_list<'a>(state: &ScheduleViewState<'a>) -> Option<Element<'a, Message>> {
+    if state.scheduled.is_empty() {
+        return None;
+    }
+    let sp = cosmic::theme::spacing();
+    let mut list = column![].spacing(sp.space_xxxs);
+    for scheduled in &state.scheduled {
+        let heading = if scheduled.has_failed() {
+            row![
+                widget::icon::from_name("dialog-error-symbolic").size(14),
+                text::caption(fl!("scheduled-not-sent")),
+            ]
+        } else {
+            row![
+                widget::icon::from_name("alarm-symbolic").size(14),
+                text::caption(fl!("scheduled-for", time = scheduled.due_label())),
+            ]
+        }
+        .spacing(sp.space_xxxs)
+        .align_y(Alignment::Center);
+
+        let mut card = column![heading].spacing(sp.space_xxxs);
+        match state.editing {
+            Some((id, draft)) if id == scheduled.id => {
+                card = card.push(
+                    row![
+                        widget::text_input("", draft)
+                            .on_input(Message::ScheduledEditInput)
+                            .on_submit(|_| Message::SaveScheduledEdit)
+                            .width(Length::Fill),
+                        widget::button::suggested(fl!("save")).on_press(Message::SaveScheduledEdit),
+                    ]
+                    .spacing(sp.space_xxs)
+                    .align_y(Alignment::Center),
+                );
+            }
+            _ => {
+                let mut body = scheduled.body.clone();
+                if !scheduled.attachment_urls.is_empty() {
+                    body = format!("📎 {} {}", scheduled.attachment_urls.len(), body);
+                }
+                let mut actions = row![text::body(body)
+                    .wrapping(cosmic::iced::widget::text::Wrapping::Word)
+                    .width(Length::Fill)]
+                .spacing(sp.space_xxs)
+                .align_y(Alignment::Center);
+                if scheduled.has_failed() {
+                    actions = actions.push(
+                        widget::button::text(fl!("retry"))
+                            .on_press(Message::RetryScheduledSms(scheduled.id)),
+                    );
+                }
+                card = card.push(
+                    actions
+                        .push(
+                            widget::button::text(fl!("edit"))
+                                .on_press(Message::EditScheduledSms(scheduled.id)),
+                        )
+                        .push(
+                            widget::button::text(fl!("cancel-scheduled"))
+                                .on_press(Message::CancelScheduledSms(scheduled.id)),
+                        ),
+                );
+            }
+        }
+        if let Some(err) = &scheduled.last_error {
+            card = card.push(
+                text::caption(format!("{}: {}", fl!("sms-failed"), err))
+                    .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+            );
+        }
+        list = list.push(
+            widget::container(card)
+                .padding([sp.space_xxxs, sp.space_xs])
+                .width(Length::Fill)
+                .class(cosmic::theme::Container::Card),
+        );
+    }
+    Some(
+        widget::container(list)
+            .padding([0, sp.space_xs as u16])
+            .width(Length::Fill)
+            .into(),
+    )
+}
+
 // --- View params and functions ---
 
 /// Parameters for the conversation list view.
@@ -158,6 +479,8 @@ pub struct ConversationListParams<'a> {
     pub conversations: &'a [LogicalConversation],
     pub conversations_displayed: usize,
     pub contacts: &'a ContactLookup,
+    /// Decoded contact photos for the row avatars
+    pub avatars: &'a AvatarCache,
     pub loading_state: &'a SmsLoadingState,
     /// Whether background sync is active (syncing conversations from phone)
     pub sync_active: bool,
@@ -166,6 +489,215 @@ pub struct ConversationListParams<'a> {
     /// markers can appear (no markers when merging is off because no
     /// `LogicalConversation` will have `merged_thread_ids.len() > 1`).
     pub merge_reaction_threads: bool,
+    /// Current search field contents.
+    pub search_query: &'a str,
+    /// Search hits for a non-empty query; replaces the conversation list
+    /// while set.
+    pub search_results: Option<&'a [SearchHit]>,
+    /// Whether the export format picker is shown under the header
+    pub export_menu_open: bool,
+    /// Local pin/mute/archive flags, one per entry in `conversations`
+    pub flags: &'a [ConversationFlags],
+    /// Whether the "Archived" section is expanded
+    pub show_archived: bool,
+    /// SIMs seen on this device, in slot order
+    pub known_sims: &'a [i64],
+    /// Unsent reply drafts for this device, keyed by thread ID
+    pub drafts: Option<&'a HashMap<i64, String>>,
+}
+
+/// Header button that toggles the export format picker.
+fn export_button<'a>(tooltip: String) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name("document-save-as-symbolic"))
+            .class(cosmic::theme::Button::Link)
+            .on_press(Message::ToggleSmsExportMenu),
+        text::caption(tooltip),
+        widget::tooltip::Position::Bottom,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs)
+    .into()
+}
+
+/// Row of export format buttons shown while the export picker is open.
+fn view_export_row<'a>() -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    let mut export_row = row![text::caption(fl!("export-as"))]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center);
+    for format in ExportFormat::ALL {
+        export_row = export_row
+            .push(widget::button::standard(format.label()).on_press(Message::ExportSms(format)));
+    }
+    applet::padded_control(export_row).into()
+}
+
+/// Render one search hit: the conversation name plus a snippet with the
+/// matched span drawn in the accent color.
+fn view_search_hit(hit: &SearchHit) -> Element<'_, Message> {
+    let sp = cosmic::theme::spacing();
+    let date_str = format_timestamp(hit.timestamp);
+
+    let snippet: Element<Message> = match hit.kind {
+        SearchHitKind::Participant => text::caption(hit.snippet_before.as_str())
+            .wrapping(cosmic::iced::widget::text::Wrapping::None)
+            .into(),
+        SearchHitKind::Body => row![
+            text::caption(hit.snippet_before.as_str())
+                .wrapping(cosmic::iced::widget::text::Wrapping::None),
+            text::caption(hit.snippet_match.as_str())
+                .class(cosmic::theme::Text::Accent)
+                .wrapping(cosmic::iced::widget::text::Wrapping::None),
+            text::caption(hit.snippet_after.as_str())
+                .wrapping(cosmic::iced::widget::text::Wrapping::None),
+        ]
+        .into(),
+    };
+
+    applet::menu_button(
+        row![
+            widget::container(
+                column![
+                    text::body(hit.display_name.as_str())
+                        .wrapping(cosmic::iced::widget::text::Wrapping::None),
+                    snippet,
+                ]
+                .spacing(2),
+            )
+            .width(Length::Fill)
+            .clip(true),
+            text::caption(date_str),
+            widget::icon::from_name("go-next-symbolic").size(16),
+        ]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center),
+    )
+    .on_press(Message::OpenSearchResult {
+        thread_id: hit.thread_id,
+        uid: hit.message_uid,
+    })
+    .into()
+}
+
+/// One conversation in the list: name, snippet (or draft), date, badges.
+fn view_conversation_row<'a>(
+    params: &ConversationListParams<'a>,
+    conv: &LogicalConversation,
+    flags: ConversationFlags,
+) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    let display_name = params.contacts.get_group_display_name(&conv.addresses, 3);
+    let date_str = format_timestamp(conv.last_message_timestamp);
+
+    let draft = params
+        .drafts
+        .and_then(|drafts| conv.merged_thread_ids.iter().find_map(|id| drafts.get(id)));
+
+    // Build snippet: an unsent draft takes precedence, then show
+    // attachment indicator if needed
+    let snippet_element: Element<Message> = if let Some(draft) = draft {
+        row![
+            text::caption(fl!("draft-prefix")).class(cosmic::theme::Text::Accent),
+            text::caption(normalize_snippet(draft))
+                .wrapping(cosmic::iced::widget::text::Wrapping::None),
+        ]
+        .spacing(sp.space_xxxs)
+        .align_y(Alignment::Center)
+        .into()
+    } else if conv.has_attachments && conv.last_message_preview.is_empty() {
+        // MMS with only attachments (no text body)
+        row![
+            widget::icon::from_name("mail-attachment-symbolic").size(14),
+            text::caption(fl!("attachment")).wrapping(cosmic::iced::widget::text::Wrapping::None),
+        ]
+        .spacing(sp.space_xxxs)
+        .align_y(Alignment::Center)
+        .into()
+    } else if conv.has_attachments {
+        // MMS with both text and attachments
+        let snippet = normalize_snippet(&conv.last_message_preview);
+        row![
+            widget::icon::from_name("mail-attachment-symbolic").size(14),
+            text::caption(snippet).wrapping(cosmic::iced::widget::text::Wrapping::None),
+        ]
+        .spacing(sp.space_xxxs)
+        .align_y(Alignment::Center)
+        .into()
+    } else {
+        let snippet = normalize_snippet(&conv.last_message_preview);
+        text::caption(snippet)
+            .wrapping(cosmic::iced::widget::text::Wrapping::None)
+            .into()
+    };
+
+    let marker_glyph: Option<&str> = if conv.merged_thread_ids.len() > 1 {
+        Some("io.github.nwxnw.cosmic-ext-connected-merged-symbolic")
+    } else if conv.is_split_candidate {
+        Some("io.github.nwxnw.cosmic-ext-connected-split-symbolic")
+    } else {
+        None
+    };
+
+    let snippet_row: Element<Message> = if let Some(glyph) = marker_glyph {
+        let marker_icon =
+            widget::icon::from_name(glyph)
+                .size(14)
+                .icon()
+                .class(cosmic::theme::Svg::custom(|theme| {
+                    cosmic::iced::widget::svg::Style {
+                        color: Some(theme.cosmic().accent_text_color().into()),
+                    }
+                }));
+        row![marker_icon, snippet_element,]
+            .spacing(sp.space_xxxs)
+            .align_y(Alignment::Center)
+            .into()
+    } else {
+        snippet_element
+    };
+
+    // Unread conversations get a bold name and a count badge
+    let mut name_text =
+        text::body(display_name).wrapping(cosmic::iced::widget::text::Wrapping::None);
+    if conv.unread_count > 0 {
+        name_text = name_text.font(cosmic::font::bold());
+    }
+    let mut name_row = row![name_text]
+        .spacing(sp.space_xxxs)
+        .align_y(Alignment::Center);
+    if flags.pinned {
+        name_row = name_row.push(widget::icon::from_name("view-pin-symbolic").size(12));
+    }
+    if flags.muted {
+        name_row =
+            name_row.push(widget::icon::from_name("notifications-disabled-symbolic").size(12));
+    }
+    let mut trailing = column![text::caption(date_str)]
+        .spacing(2)
+        .align_x(Alignment::End);
+    if conv.unread_count > 0 {
+        trailing = trailing.push(unread_badge(conv.unread_count));
+    }
+    if let Some(sim) = sim_label(params.known_sims, conv.subscription_id) {
+        trailing = trailing.push(text::caption(sim));
+    }
+
+    applet::menu_button(
+        row![
+            avatar(params.contacts, params.avatars, &conv.addresses, 36.0),
+            widget::container(column![name_row, snippet_row,].spacing(2))
+                .width(Length::Fill)
+                .clip(true),
+            trailing,
+            widget::icon::from_name("go-next-symbolic").size(16),
+        ]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center),
+    )
+    .on_press(Message::OpenConversation(conv.primary_thread_id))
+    .into()
 }
 
 /// Render the SMS conversation list view.
@@ -227,16 +759,57 @@ pub fn view_conversation_list(params: ConversationListParams<'_>) -> Element<'_,
     .gap(sp.space_xxxs)
     .padding(sp.space_xxs);
 
-    let header = applet::padded_control(
-        header_row
-            .push(widget::space::horizontal())
-            .push(merge_toggle_btn)
-            .push(new_msg_btn),
-    );
+    let contacts_btn = widget::tooltip(
+        widget::button::icon(widget::icon::from_name("x-office-address-book-symbolic"))
+            .class(cosmic::theme::Button::Link)
+            .on_press(Message::OpenContactsView),
+        text::caption(fl!("contacts")),
+        widget::tooltip::Position::Bottom,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs);
 
-    let content: Element<Message> = if is_loading_conversations(params.loading_state)
-        && params.conversations.is_empty()
-    {
+    header_row = header_row
+        .push(widget::space::horizontal())
+        .push(contacts_btn)
+        .push(merge_toggle_btn);
+    if !params.conversations.is_empty() {
+        header_row = header_row.push(export_button(fl!("export-messages")));
+    }
+    let header = applet::padded_control(header_row.push(new_msg_btn));
+
+    let search_row: Option<Element<Message>> = if params.conversations.is_empty() {
+        None
+    } else {
+        Some(
+            applet::padded_control(
+                widget::search_input(fl!("search-messages"), params.search_query)
+                    .on_input(Message::SmsSearchInput)
+                    .on_clear(Message::SmsSearchInput(String::new()))
+                    .width(Length::Fill),
+            )
+            .into(),
+        )
+    };
+
+    let content: Element<Message> = if let Some(hits) = params.search_results {
+        if hits.is_empty() {
+            widget::container(text::caption(fl!("search-no-results")))
+                .padding(sp.space_s)
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into()
+        } else {
+            let rows: Vec<Element<Message>> = hits.iter().map(view_search_hit).collect();
+            widget::scrollable(
+                column(rows)
+                    .spacing(sp.space_xxxs)
+                    .padding([0, sp.space_xxs as u16]),
+            )
+            .width(Length::Fill)
+            .into()
+        }
+    } else if is_loading_conversations(params.loading_state) && params.conversations.is_empty() {
         widget::container(
             column![text::body(conversation_loading_text(params.loading_state)),]
                 .align_x(Alignment::Center),
@@ -256,92 +829,20 @@ pub fn view_conversation_list(params: ConversationListParams<'_>) -> Element<'_,
         .center(Length::Fill)
         .into()
     } else {
-        // Build conversation list (limited to conversations_displayed)
-        let mut conv_column = column![].spacing(sp.space_xxxs);
-        for conv in params
+        // Build conversation list (limited to conversations_displayed);
+        // archived conversations go in their own section at the end
+        let (archived, active): (Vec<_>, Vec<_>) = params
             .conversations
             .iter()
-            .take(params.conversations_displayed)
-        {
-            let display_name = params.contacts.get_group_display_name(&conv.addresses, 3);
-            let date_str = format_timestamp(conv.last_message_timestamp);
-
-            // Build snippet: show attachment indicator if needed
-            let snippet_element: Element<Message> =
-                if conv.has_attachments && conv.last_message_preview.is_empty() {
-                    // MMS with only attachments (no text body)
-                    row![
-                        widget::icon::from_name("mail-attachment-symbolic").size(14),
-                        text::caption(fl!("attachment"))
-                            .wrapping(cosmic::iced::widget::text::Wrapping::None),
-                    ]
-                    .spacing(sp.space_xxxs)
-                    .align_y(Alignment::Center)
-                    .into()
-                } else if conv.has_attachments {
-                    // MMS with both text and attachments
-                    let snippet = normalize_snippet(&conv.last_message_preview);
-                    row![
-                        widget::icon::from_name("mail-attachment-symbolic").size(14),
-                        text::caption(snippet).wrapping(cosmic::iced::widget::text::Wrapping::None),
-                    ]
-                    .spacing(sp.space_xxxs)
-                    .align_y(Alignment::Center)
-                    .into()
-                } else {
-                    let snippet = normalize_snippet(&conv.last_message_preview);
-                    text::caption(snippet)
-                        .wrapping(cosmic::iced::widget::text::Wrapping::None)
-                        .into()
-                };
-
-            let marker_glyph: Option<&str> = if conv.merged_thread_ids.len() > 1 {
-                Some("io.github.nwxnw.cosmic-ext-connected-merged-symbolic")
-            } else if conv.is_split_candidate {
-                Some("io.github.nwxnw.cosmic-ext-connected-split-symbolic")
-            } else {
-                None
-            };
-
-            let snippet_row: Element<Message> = if let Some(glyph) = marker_glyph {
-                let marker_icon = widget::icon::from_name(glyph).size(14).icon().class(
-                    cosmic::theme::Svg::custom(|theme| cosmic::iced::widget::svg::Style {
-                        color: Some(theme.cosmic().accent_text_color().into()),
-                    }),
-                );
-                row![marker_icon, snippet_element,]
-                    .spacing(sp.space_xxxs)
-                    .align_y(Alignment::Center)
-                    .into()
-            } else {
-                snippet_element
-            };
-
-            let conv_row = applet::menu_button(
-                row![
-                    widget::container(
-                        column![
-                            text::body(display_name)
-                                .wrapping(cosmic::iced::widget::text::Wrapping::None),
-                            snippet_row,
-                        ]
-                        .spacing(2),
-                    )
-                    .width(Length::Fill)
-                    .clip(true),
-                    text::caption(date_str),
-                    widget::icon::from_name("go-next-symbolic").size(16),
-                ]
-                .spacing(sp.space_xxs)
-                .align_y(Alignment::Center),
-            )
-            .on_press(Message::OpenConversation(conv.primary_thread_id));
-
-            conv_column = conv_column.push(conv_row);
+            .zip(params.flags.iter().copied())
+            .partition(|(_, flags)| flags.archived);
+        let mut conv_column = column![].spacing(sp.space_xxxs);
+        for &(conv, flags) in active.iter().take(params.conversations_displayed) {
+            conv_column = conv_column.push(view_conversation_row(&params, conv, flags));
         }
 
         // Add "Load More" button if there are more conversations
-        if params.conversations_displayed < params.conversations.len() {
+        if params.conversations_displayed < active.len() {
             let load_more_row = row![
                 widget::icon::from_name("go-down-symbolic").size(16),
                 text::body(fl!("load-more-conversations")),
@@ -359,6 +860,32 @@ pub fn view_conversation_list(params: ConversationListParams<'_>) -> Element<'_,
             conv_column = conv_column.push(load_more_button);
         }
 
+        if !archived.is_empty() {
+            let expander_icon = if params.show_archived {
+                "go-down-symbolic"
+            } else {
+                "go-next-symbolic"
+            };
+            conv_column = conv_column.push(
+                applet::menu_button(
+                    row![
+                        widget::icon::from_name("folder-symbolic").size(16),
+                        text::body(fl!("archived-conversations", count = archived.len()))
+                            .width(Length::Fill),
+                        widget::icon::from_name(expander_icon).size(16),
+                    ]
+                    .spacing(sp.space_xxs)
+                    .align_y(Alignment::Center),
+                )
+                .on_press(Message::ToggleArchivedSection),
+            );
+            if params.show_archived {
+                for &(conv, flags) in &archived {
+                    conv_column = conv_column.push(view_conversation_row(&params, conv, flags));
+                }
+            }
+        }
+
         // Show sync progress indicator at bottom when still syncing
         if params.sync_active {
             conv_column = conv_column.push(
@@ -379,7 +906,168 @@ pub fn view_conversation_list(params: ConversationListParams<'_>) -> Element<'_,
             .into()
     };
 
-    column![header, content,]
+    let mut list_column = column![header].spacing(sp.space_xxs).width(Length::Fill);
+    if params.export_menu_open && !params.conversations.is_empty() {
+        list_column = list_column.push(view_export_row());
+    }
+    if let Some(search_row) = search_row {
+        list_column = list_column.push(search_row);
+    }
+    list_column.push(content).into()
+}
+
+/// Parameters for the contacts browser view.
+pub struct ContactsViewParams<'a> {
+    pub device_name: Option<&'a str>,
+    pub contacts: &'a ContactLookup,
+    /// Decoded contact photos for the contact avatars
+    pub avatars: &'a AvatarCache,
+    /// Contacts matching `query`, grouped by initial
+    pub sections: &'a [ContactSection],
+    /// Existing one-to-one conversations by `phone_key` of the other party
+    pub threads: &'a HashMap<String, i64>,
+    /// Current search field contents
+    pub query: &'a str,
+    /// Whether a contact sync request is in flight
+    pub contacts_syncing: bool,
+}
+
+fn phone_kind_label(kind: PhoneKind) -> String {
+    match kind {
+        PhoneKind::Mobile => fl!("phone-kind-mobile"),
+        PhoneKind::Home => fl!("phone-kind-home"),
+        PhoneKind::Work => fl!("phone-kind-work"),
+        PhoneKind::Other => fl!("phone-kind-other"),
+    }
+}
+
+/// Render the contacts browser: search, then contacts by initial with a
+/// row per number that opens its conversation or starts a new message.
+pub fn view_contacts(params: ContactsViewParams<'_>) -> Element<'_, Message> {
+    let sp = cosmic::theme::spacing();
+    let default_device = fl!("device");
+    let device_name = params.device_name.unwrap_or(&default_device);
+
+    let sync_btn = widget::tooltip(
+        widget::button::icon(widget::icon::from_name("emblem-synchronizing-symbolic"))
+            .class(cosmic::theme::Button::Link)
+            .on_press_maybe((!params.contacts_syncing).then_some(Message::SyncContacts)),
+        text::caption(fl!("sync-contacts")),
+        widget::tooltip::Position::Bottom,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs);
+
+    let header = applet::padded_control(
+        row![
+            widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                .class(cosmic::theme::Button::Link)
+                .on_press(Message::CloseContactsView),
+            text::heading(fl!("contacts-title", device = device_name)),
+            widget::space::horizontal(),
+            sync_btn,
+        ]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center),
+    );
+
+    let search_row = applet::padded_control(
+        widget::search_input(fl!("search-contacts"), params.query)
+            .on_input(Message::ContactsSearchInput)
+            .on_clear(Message::ContactsSearchInput(String::new()))
+            .width(Length::Fill),
+    );
+
+    let content: Element<Message> = if params.sections.is_empty() {
+        let (title, hint) = if params.query.trim().is_empty() {
+            (fl!("no-contacts"), fl!("no-contacts-hint"))
+        } else {
+            (fl!("search-no-contacts"), String::new())
+        };
+        widget::container(
+            column![
+                widget::icon::from_name("x-office-address-book-symbolic").size(48),
+                text::heading(title),
+                text::caption(hint),
+            ]
+            .spacing(sp.space_xs)
+            .align_x(Alignment::Center),
+        )
+        .center(Length::Fill)
+        .into()
+    } else {
+        let mut list = column![].spacing(sp.space_xxxs);
+        let all_contacts = params.contacts.all_contacts();
+        for section in params.sections {
+            list = list.push(
+                widget::container(
+                    text::caption(section.letter.clone()).class(cosmic::theme::Text::Accent),
+                )
+                .padding([sp.space_xxxs, sp.space_xxs]),
+            );
+            for contact in section.contacts.iter().map(|&i| &all_contacts[i]) {
+                let mut name_column = column![text::body(contact.name.clone())];
+                if let Some(org) = &contact.organization {
+                    name_column = name_column.push(text::caption(org.clone()));
+                }
+                let mut card = column![row![
+                    avatar(
+                        params.contacts,
+                        params.avatars,
+                        std::slice::from_ref(&contact.phones[0].number),
+                        32.0,
+                    ),
+                    name_column,
+                ]
+                .spacing(sp.space_xs)
+                .align_y(Alignment::Center)]
+                .spacing(sp.space_xxxs);
+
+                for phone in &contact.phones {
+                    let thread = params.threads.get(&phone_key(&phone.number)).copied();
+                    let (icon, tooltip, message) = match thread {
+                        Some(thread_id) => (
+                            "mail-message-symbolic",
+                            fl!("open-conversation"),
+                            Message::OpenConversation(thread_id),
+                        ),
+                        None => (
+                            "mail-message-new-symbolic",
+                            fl!("new-message"),
+                            Message::MessageContact(contact.name.clone(), phone.number.clone()),
+                        ),
+                    };
+                    card = card.push(
+                        row![
+                            text::caption(phone_kind_label(phone.kind)).width(Length::Fixed(64.0)),
+                            text::body(phone.number.clone()).width(Length::Fill),
+                            widget::tooltip(
+                                widget::button::icon(widget::icon::from_name(icon))
+                                    .class(cosmic::theme::Button::Link)
+                                    .on_press(message),
+                                text::caption(tooltip),
+                                widget::tooltip::Position::Left,
+                            )
+                            .gap(sp.space_xxxs),
+                        ]
+                        .spacing(sp.space_xxs)
+                        .align_y(Alignment::Center)
+                        .padding(cosmic::iced::Padding {
+                            // Line up with the name, past the avatar
+                            left: f32::from(32 + sp.space_xs),
+                            ..cosmic::iced::Padding::ZERO
+                        }),
+                    );
+                }
+                list = list.push(widget::container(card).padding([sp.space_xxxs, sp.space_xxs]));
+            }
+        }
+        widget::scrollable(list.padding([0, sp.space_xxs as u16]))
+            .width(Length::Fill)
+            .into()
+    };
+
+    column![header, search_row, content]
         .spacing(sp.space_xxs)
         .width(Length::Fill)
         .into()
@@ -392,6 +1080,8 @@ pub struct MessageThreadParams<'a> {
     pub thread_addresses: Option<&'a [String]>,
     pub messages: &'a [SmsMessage],
     pub contacts: &'a ContactLookup,
+    /// Decoded contact photos for the header and group sender avatars
+    pub avatars: &'a AvatarCache,
     pub loading_state: &'a SmsLoadingState,
     pub sms_compose_text: &'a widget::text_editor::Content,
     pub sms_sending: bool,
@@ -401,10 +1091,81 @@ pub struct MessageThreadParams<'a> {
     pub pressed_bubble_uid: Option<i32>,
     /// Whether to show the "Hold to copy" hint (500ms elapsed)
     pub show_copy_hint: bool,
+    /// UID of the message jumped to from a conversation search hit
+    pub search_highlight_uid: Option<i32>,
+    /// Whether the export format picker is shown under the header
+    pub export_menu_open: bool,
+    /// Local pin/mute/archive flags of this conversation
+    pub flags: ConversationFlags,
+    /// Files queued to go out with the next reply
+    pub pending_attachments: &'a [PendingAttachment],
+    /// SIMs seen on this device, in slot order
+    pub known_sims: &'a [i64],
+    /// SIM picked in the selector; `None` lets the phone choose
+    pub selected_sim: Option<i64>,
+    /// Scheduled replies in this conversation and the "Send later" picker
+    pub schedule: ScheduleViewState<'a>,
+    /// UIDs of failed messages whose resend is in flight
+    pub retrying: &'a HashSet<i32>,
+    /// Current value of `config.show_raw_reactions`: draw tapbacks as
+    /// bubbles rather than badges on the original
+    pub show_raw_reactions: bool,
     /// Status message to display (e.g. send confirmation or error)
     pub status_message: Option<&'a str>,
 }
 
+/// Header toggle between tapback badges and their raw "Liked “…”" text.
+fn reactions_toggle<'a>(show_raw: bool) -> Element<'a, Message> {
+    let sp = cosmic::theme::spacing();
+    let tooltip = if show_raw {
+        fl!("show-reaction-badges")
+    } else {
+        fl!("show-reaction-text")
+    };
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name("face-smile-symbolic"))
+            .class(if show_raw {
+                cosmic::theme::Button::Link
+            } else {
+                cosmic::theme::Button::Suggested
+            })
+            .on_press(Message::ToggleSetting(SettingKey::ShowRawReactions)),
+        text::caption(tooltip),
+        widget::tooltip::Position::Bottom,
+    )
+    .gap(sp.space_xxxs)
+    .padding(sp.space_xxs)
+    .into()
+}
+
+/// Row of emoji chips for the tapbacks on one message.
+fn reaction_badges(badges: &[ReactionBadge]) -> Element<'static, Message> {
+    let sp = cosmic::theme::spacing();
+    let mut chips = row![].spacing(sp.space_xxxs);
+    for badge in badges {
+        let label = if badge.count > 1 {
+            format!("{} {}", badge.emoji, badge.count)
+        } else {
+            badge.emoji.clone()
+        };
+        chips = chips.push(
+            widget::container(text::caption(label))
+                .padding([0, sp.space_xxs])
+                .class(cosmic::theme::Container::Card),
+        );
+    }
+    chips.into()
+}
+
+/// Icon + caption line under a bubble that isn't plainly delivered.
+fn delivery_caption(icon: &'static str, label: String) -> Element<'static, Message> {
+    let sp = cosmic::theme::spacing();
+    row![widget::icon::from_name(icon).size(12), text::caption(label),]
+        .spacing(sp.space_xxxs)
+        .align_y(Alignment::Center)
+        .into()
+}
+
 /// Enter sends; Shift+Enter falls through to default newline binding
 fn compose_key_binding(
     mut kp: widget::text_editor::KeyPress,
@@ -442,6 +1203,12 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
         widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
             .class(cosmic::theme::Button::Link)
             .on_press(Message::CloseConversation),
+        avatar(
+            params.contacts,
+            params.avatars,
+            params.thread_addresses.unwrap_or_default(),
+            28.0,
+        ),
         text::heading(display_name),
     ]
     .spacing(sp.space_xxs)
@@ -459,7 +1226,18 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
         );
     }
 
-    let header = applet::padded_control(header_row.push(widget::space::horizontal()));
+    let header = applet::padded_control(
+        header_row
+            .push(widget::space::horizontal())
+            .push(flag_button(ConversationFlag::Pinned, params.flags.pinned))
+            .push(flag_button(ConversationFlag::Muted, params.flags.muted))
+            .push(flag_button(
+                ConversationFlag::Archived,
+                params.flags.archived,
+            ))
+            .push(reactions_toggle(params.show_raw_reactions))
+            .push(export_button(fl!("export-conversation"))),
+    );
 
     // Show loading indicator only when loading AND no messages yet
     // Once messages start arriving, show them (scrolled to bottom)
@@ -504,7 +1282,16 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
             msg_column = msg_column.push(loading_indicator);
         }
 
+        let reactions = if params.show_raw_reactions {
+            ThreadReactions::default()
+        } else {
+            collect_reactions(params.messages)
+        };
+
         for msg in params.messages {
+            if reactions.absorbed.contains(&msg.uid) {
+                continue;
+            }
             // MessageType::Inbox (1) = incoming/received, MessageType::Sent (2) = outgoing/sent
             let is_received = msg.message_type == MessageType::Inbox;
             let time_str = format_timestamp(msg.date);
@@ -527,22 +1314,65 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
                 );
             }
 
-            let is_pending = msg.uid == OPTIMISTIC_MESSAGE_UID;
-            if is_pending {
-                bubble_content = bubble_content.push(
-                    row![
-                        widget::icon::from_name("emblem-synchronizing-symbolic").size(12),
-                        text::caption(fl!("sending")),
-                    ]
-                    .spacing(sp.space_xxxs)
-                    .align_y(Alignment::Center),
-                );
+            let meta = match sim_label(params.known_sims, msg.sub_id) {
+                Some(sim) => format!("{} · {}", time_str, sim),
+                None => time_str,
+            };
+            let state = if is_received {
+                DeliveryState::Delivered
+            } else {
+                msg.delivery_state()
+            };
+            if msg.uid == OPTIMISTIC_MESSAGE_UID && state != DeliveryState::Failed {
+                bubble_content = bubble_content.push(delivery_caption(
+                    "emblem-synchronizing-symbolic",
+                    fl!("sending"),
+                ));
             } else {
-                bubble_content = bubble_content.push(text::caption(time_str));
+                match state {
+                    DeliveryState::Delivered => {
+                        bubble_content = bubble_content.push(text::caption(meta));
+                    }
+                    DeliveryState::Draft => {
+                        bubble_content = bubble_content.push(text::caption(format!(
+                            "{} {}",
+                            fl!("draft-prefix"),
+                            meta
+                        )));
+                    }
+                    DeliveryState::Pending => {
+                        bubble_content = bubble_content.push(delivery_caption(
+                            "emblem-synchronizing-symbolic",
+                            fl!("message-queued"),
+                        ));
+                    }
+                    DeliveryState::Failed => {
+                        bubble_content = bubble_content.push(delivery_caption(
+                            "dialog-error-symbolic",
+                            fl!("message-not-delivered"),
+                        ));
+                        // MMS parts live on the phone; only the text can be resent
+                        if msg.attachments.is_empty() && !msg.body.is_empty() {
+                            let retrying = params.retrying.contains(&msg.uid);
+                            let retry = widget::button::text(if retrying {
+                                fl!("sending")
+                            } else {
+                                fl!("retry")
+                            })
+                            .on_press_maybe(
+                                (!retrying && !params.sms_sending)
+                                    .then_some(Message::RetrySms(msg.uid)),
+                            );
+                            bubble_content = bubble_content.push(retry);
+                        }
+                    }
+                }
             }
 
-            // Use highlighted style when pressed for high contrast visual feedback
-            let bubble: Element<Message> = if is_pressed {
+            // Use highlighted style when pressed for high contrast visual feedback.
+            // A search hit gets the same treatment so it stands out after the jump.
+            let is_search_hit = params.search_highlight_uid == Some(msg.uid);
+            let bubble: Element<Message> = if is_pressed || is_search_hit {
                 // Wrap in two containers for a "selected" border effect
                 let inner = widget::container(bubble_content)
                     .padding([sp.space_xxs, sp.space_xs])
@@ -573,13 +1403,15 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
                 .on_release(Message::BubblePressReleased);
 
             // Bubble with optional "Hold to copy" hint (only after 500ms)
-            let bubble_element: Element<Message> = if show_hint {
-                column![bubble_with_press, text::caption(fl!("hold-to-copy")),]
-                    .spacing(2)
-                    .into()
-            } else {
-                bubble_with_press.into()
-            };
+            // and tapback badges underneath
+            let mut bubble_stack = column![bubble_with_press].spacing(2);
+            if show_hint {
+                bubble_stack = bubble_stack.push(text::caption(fl!("hold-to-copy")));
+            }
+            if let Some(badges) = reactions.badges.get(&msg.uid) {
+                bubble_stack = bubble_stack.push(reaction_badges(badges));
+            }
+            let bubble_element: Element<Message> = bubble_stack.into();
 
             // Received messages: align left, show sender name only in group chats
             // Sent messages: align right
@@ -591,9 +1423,19 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
             });
             let msg_row: Element<Message> = if is_received {
                 if is_group {
-                    let sender_name = params.contacts.get_name_or_number(msg.primary_address());
+                    let sender = msg.primary_address();
                     column![
-                        text::caption(sender_name),
+                        row![
+                            avatar(
+                                params.contacts,
+                                params.avatars,
+                                msg.addresses.get(..1).unwrap_or_default(),
+                                16.0,
+                            ),
+                            text::caption(params.contacts.get_name_or_number(sender)),
+                        ]
+                        .spacing(sp.space_xxxs)
+                        .align_y(Alignment::Center),
                         row![bubble_element, widget::space::horizontal(),].width(Length::Fill),
                     ]
                     .spacing(sp.space_xxxs)
@@ -635,7 +1477,9 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
             .leading_icon(widget::icon::from_name("process-working-symbolic").size(16))
             .into()
     } else {
-        let can_send = !params.sms_compose_text.text().trim().is_empty() && !params.sms_sending;
+        let can_send = (!params.sms_compose_text.text().trim().is_empty()
+            || !params.pending_attachments.is_empty())
+            && !params.sms_sending;
         widget::button::suggested(fl!("send"))
             .leading_icon(widget::icon::from_name("mail-send-symbolic").size(16))
             .on_press_maybe(if can_send {
@@ -646,16 +1490,41 @@ pub fn view_message_thread(params: MessageThreadParams<'_>) -> Element<'_, Messa
             .into()
     };
 
+    let can_schedule = (!params.sms_compose_text.text().trim().is_empty()
+        || !params.pending_attachments.is_empty())
+        && !params.sms_sending;
     let compose_row = applet::padded_control(
-        row![compose_input, send_btn,]
-            .spacing(sp.space_xxs)
-            .align_y(Alignment::Center),
+        row![
+            attach_button(),
+            compose_input,
+            schedule_button(can_schedule),
+            send_btn,
+        ]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center),
     );
 
-    let mut thread_column = column![header, content, compose_row,]
+    let mut thread_column = column![header]
         .spacing(sp.space_xxxs)
         .width(Length::Fill)
         .height(Length::Fill);
+    if params.export_menu_open {
+        thread_column = thread_column.push(view_export_row());
+    }
+    thread_column = thread_column.push(content);
+    if let Some(scheduled) = view_scheduled_list(&params.schedule) {
+        thread_column = thread_column.push(scheduled);
+    }
+    if let Some(pending) = view_pending_attachments(params.pending_attachments) {
+        thread_column = thread_column.push(pending);
+    }
+    if params.schedule.picker_open {
+        thread_column = thread_column.push(view_schedule_picker(params.schedule.time_input));
+    }
+    if let Some(selector) = view_sim_selector(params.known_sims, params.selected_sim) {
+        thread_column = thread_column.push(selector);
+    }
+    thread_column = thread_column.push(compose_row);
 
     if let Some(msg) = params.status_message {
         thread_column = thread_column.push(
@@ -677,6 +1546,14 @@ pub struct NewMessageParams<'a> {
     pub recipient_input: &'a str,
     pub body: &'a widget::text_editor::Content,
     pub sending: bool,
+    /// Files queued to go out with the message
+    pub pending_attachments: &'a [PendingAttachment],
+    /// SIMs seen on this device, in slot order
+    pub known_sims: &'a [i64],
+    /// SIM picked in the selector; `None` lets the phone choose
+    pub selected_sim: Option<i64>,
+    /// Scheduled new conversations and the "Send later" picker
+    pub schedule: ScheduleViewState<'a>,
     /// Contact suggestions as (contact_name, phone_number) tuples
     pub contact_suggestions: &'a [(String, String)],
 }
@@ -817,9 +1694,10 @@ pub fn view_new_message(params: NewMessageParams<'_>) -> Element<'_, Message> {
         .padding(sp.space_xs)
         .max_height(120.0);
 
-    // Send button — enabled when at least one recipient and body is non-empty
-    let send_enabled =
-        !params.recipients.is_empty() && !params.body.text().trim().is_empty() && !params.sending;
+    // Send button — enabled when at least one recipient and a body or attachment
+    let send_enabled = !params.recipients.is_empty()
+        && (!params.body.text().trim().is_empty() || !params.pending_attachments.is_empty())
+        && !params.sending;
 
     let send_btn = if params.sending {
         widget::button::standard(fl!("sending"))
@@ -834,18 +1712,35 @@ pub fn view_new_message(params: NewMessageParams<'_>) -> Element<'_, Message> {
     };
 
     let send_row = applet::padded_control(
-        row![widget::space::horizontal(), send_btn,]
-            .spacing(sp.space_xxs)
-            .align_y(Alignment::Center),
+        row![
+            attach_button(),
+            widget::space::horizontal(),
+            schedule_button(send_enabled),
+            send_btn,
+        ]
+        .spacing(sp.space_xxs)
+        .align_y(Alignment::Center),
     );
 
+    let schedule_picker: Element<Message> = if params.schedule.picker_open {
+        view_schedule_picker(params.schedule.time_input)
+    } else {
+        widget::Space::new().into()
+    };
+
     column![
         header,
         recipient_row,
         chips_section,
         suggestions_section,
         applet::padded_control(message_input),
+        view_pending_attachments(params.pending_attachments)
+            .unwrap_or_else(|| widget::Space::new().into()),
+        schedule_picker,
+        view_sim_selector(params.known_sims, params.selected_sim)
+            .unwrap_or_else(|| widget::Space::new().into()),
         send_row,
+        view_scheduled_list(&params.schedule).unwrap_or_else(|| widget::Space::new().into()),
         widget::space::vertical(),
     ]
     .spacing(sp.space_xxxs)
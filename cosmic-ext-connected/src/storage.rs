@@ -0,0 +1,176 @@
+//! JSON files the applet keeps under the XDG data directory.
+//!
+//! Files are loaded leniently: a missing or corrupt file yields the default
+//! value, since everything stored here can be rebuilt or re-entered. COSMIC
+//! may run several applet processes at once, so every write is a
+//! read-modify-write under an exclusive lock ([`update_json`]): each process
+//! applies its own change to what is on disk instead of overwriting the file
+//! with its in-memory copy. The new contents go through a temp file and a
+//! rename so a crash mid-write never leaves a truncated file behind.
+
+use std::fs::OpenOptions;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Distinguishes temp files written by the same process.
+static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+/// `id` made safe to use as a file name. Device IDs are daemon-generated,
+/// but never let one escape the directory.
+pub fn file_name_for(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn parse_or_default<T: DeserializeOwned + Default>(bytes: &[u8], path: &Path, what: &str) -> T {
+    serde_json::from_slice(bytes).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring corrupt {} {}: {}", what, path.display(), e);
+        T::default()
+    })
+}
+
+/// Load `path`. `what` names the file in log messages.
+pub async fn load_json<T: DeserializeOwned + Default>(path: &Path, what: &str) -> T {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => parse_or_default(&bytes, path, what),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+        Err(e) => {
+            tracing::warn!("Failed to read {} {}: {}", what, path.display(), e);
+            T::default()
+        }
+    }
+}
+
+/// Write `bytes` to `path` through a temp file unique to this writer.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension(format!(
+        "json.{}-{}.tmp",
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(e) = std::fs::write(&tmp_path, bytes) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    std::fs::rename(&tmp_path, path)
+}
+
+/// Read-modify-write `path` while holding an exclusive lock on a `.lock` file
+/// next to it. `f` returns whether the value changed and needs writing; the
+/// value is returned either way, so callers can adopt changes made by other
+/// processes.
+///
+/// Blocks on the lock, so run it through [`update_json_async`] from async
+/// code.
+pub fn update_json<T, F>(path: &Path, what: &str, f: F) -> Result<T, String>
+where
+    T: DeserializeOwned + Serialize + Default,
+    F: FnOnce(&mut T) -> bool,
+{
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let lock = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path.with_extension("lock"))
+        .map_err(|e| e.to_string())?;
+
+    let fd = lock.as_raw_fd();
+    // SAFETY: flock is a standard POSIX system call that operates on valid file descriptors
+    unsafe {
+        if libc::flock(fd, libc::LOCK_EX) != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+    }
+
+    let result = (|| {
+        let mut value = match std::fs::read(path) {
+            Ok(bytes) => parse_or_default(&bytes, path, what),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+            Err(e) => return Err(e.to_string()),
+        };
+        if f(&mut value) {
+            let bytes = serde_json::to_vec(&value).map_err(|e| e.to_string())?;
+            write_atomic(path, &bytes).map_err(|e| e.to_string())?;
+        }
+        Ok(value)
+    })();
+
+    // SAFETY: flock is a standard POSIX system call that operates on valid file descriptors
+    unsafe {
+        libc::flock(fd, libc::LOCK_UN);
+    }
+
+    result
+}
+
+/// [`update_json`] on the blocking thread pool.
+pub async fn update_json_async<T, F>(path: PathBuf, what: &'static str, f: F) -> Result<T, String>
+where
+    T: DeserializeOwned + Serialize + Default + Send + 'static,
+    F: FnOnce(&mut T) -> bool + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || update_json(&path, what, f))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let dir = std::env::temp_dir().join(format!("connected-storage-{}", std::process::id()));
+        let path = dir.join("counter.json");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        update_json::<Vec<u32>, _>(&path, "counter", |v| {
+                            v.push(1);
+                            true
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let total: Vec<u32> = update_json(&path, "counter", |_| false).unwrap();
+        assert_eq!(total.len(), 200);
+        // No temp files left behind
+        let leftovers = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn file_names_stay_in_directory() {
+        assert_eq!(file_name_for("abc_123-XYZ"), "abc_123-XYZ");
+        assert_eq!(file_name_for("../etc/passwd"), "___etc_passwd");
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::app::Message;
 use crate::constants::dbus::RETRY_DELAY_SECS;
+use crate::constants::media::MEDIA_SIGNAL_DEBOUNCE_MS;
 use crate::constants::sms::{
     CONVERSATION_RETRY_WAIT_MS, MESSAGE_SUBSCRIPTION_TIMEOUT_SECS, PHONE_RESPONSE_TIMEOUT_MS,
 };
@@ -549,6 +550,7 @@ pub fn call_notification_subscription() -> impl futures_util::Stream<Item = Mess
 
                                                     return Some((
                                                         Message::CallNotification {
+                                                            device_id: device_id.to_string(),
                                                             device_name,
                                                             event,
                                                             phone_number,
@@ -1253,3 +1255,281 @@ pub fn conversation_message_subscription(
         },
     )
 }
+
+/// State for the media player signal subscription.
+#[allow(clippy::large_enum_variant)]
+enum MediaSignalState {
+    Init {
+        device_id: String,
+    },
+    Listening {
+        #[allow(dead_code)]
+        conn: Connection,
+        stream: zbus::MessageStream,
+        device_id: String,
+        path: String,
+    },
+}
+
+/// Create a stream that listens for mprisremote property changes on one device
+/// while the media view is open.
+///
+/// The daemon announces changes with the argument-less `propertiesChanged`
+/// signal, which is emitted in bursts while a track changes; these are
+/// coalesced into a single `MediaRefresh`. The signal carries no values and the
+/// daemon never emits the standard `org.freedesktop.DBus.Properties`
+/// `PropertiesChanged`, so every update is a full re-read (one `GetAll`).
+pub fn media_signal_subscription(device_id: String) -> impl futures_util::Stream<Item = Message> {
+    futures_util::stream::unfold(MediaSignalState::Init { device_id }, |state| async move {
+        match state {
+            MediaSignalState::Init { device_id } => {
+                let conn = match Connection::session().await {
+                    Ok(c) => c,
+                    Err(e) => {
+                        // Polling keeps the view updated in the meantime
+                        tracing::warn!("Failed to connect to D-Bus for media signals: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_secs(RETRY_DELAY_SECS)).await;
+                        return Some((Message::MediaRefresh, MediaSignalState::Init { device_id }));
+                    }
+                };
+
+                let dbus_proxy = match zbus::fdo::DBusProxy::new(&conn).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::warn!("Failed to create DBus proxy for media: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_secs(RETRY_DELAY_SECS)).await;
+                        return Some((Message::MediaRefresh, MediaSignalState::Init { device_id }));
+                    }
+                };
+
+                let path = format!(
+                    "{}/devices/{}/mprisremote",
+                    kdeconnect_dbus::BASE_PATH,
+                    device_id
+                );
+
+                let rule = zbus::MatchRule::builder()
+                    .msg_type(zbus::message::Type::Signal)
+                    .interface("org.kde.kdeconnect.device.mprisremote")
+                    .and_then(|b| b.member("propertiesChanged"))
+                    .and_then(|b| b.path(path.as_str()))
+                    .map(|b| b.build());
+                match rule {
+                    Ok(rule) => {
+                        if let Err(e) = dbus_proxy.add_match_rule(rule).await {
+                            tracing::warn!("Failed to add media match rule: {}", e);
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to build media match rule: {}", e),
+                }
+
+                tracing::debug!("Media signal subscription started for {}", device_id);
+
+                let stream = zbus::MessageStream::from(&conn);
+                // Catch up on anything that changed before the rules were in place
+                Some((
+                    Message::MediaRefresh,
+                    MediaSignalState::Listening {
+                        conn,
+                        stream,
+                        device_id,
+                        path,
+                    },
+                ))
+            }
+            MediaSignalState::Listening {
+                conn,
+                mut stream,
+                device_id,
+                path,
+            } => loop {
+                let msg = match stream.next().await {
+                    Some(Ok(msg)) => msg,
+                    Some(Err(e)) => {
+                        tracing::warn!("D-Bus media stream error: {}", e);
+                        continue;
+                    }
+                    None => {
+                        tracing::warn!("D-Bus media stream ended, reconnecting...");
+                        return Some((Message::MediaRefresh, MediaSignalState::Init { device_id }));
+                    }
+                };
+
+                let header = msg.header();
+                if header.message_type() != zbus::message::Type::Signal
+                    || header.path().map(|p| p.as_str()) != Some(path.as_str())
+                {
+                    continue;
+                }
+                let (Some(interface), Some(member)) = (header.interface(), header.member()) else {
+                    continue;
+                };
+
+                match (interface.as_str(), member.as_str()) {
+                    ("org.kde.kdeconnect.device.mprisremote", "propertiesChanged") => {
+                        // Swallow the rest of the burst before re-reading
+                        let debounce = std::time::Duration::from_millis(MEDIA_SIGNAL_DEBOUNCE_MS);
+                        while let Ok(Some(_)) = tokio::time::timeout(debounce, stream.next()).await
+                        {
+                        }
+                        return Some((
+                            Message::MediaRefresh,
+                            MediaSignalState::Listening {
+                                conn,
+                                stream,
+                                device_id,
+                                path,
+                            },
+                        ));
+                    }
+                    _ => {}
+                }
+            },
+        }
+    })
+}
+
+/// State for the contacts cache signal subscription.
+#[allow(clippy::large_enum_variant)]
+enum ContactsSignalState {
+    Init {
+        device_id: String,
+    },
+    Listening {
+        #[allow(dead_code)]
+        conn: Connection,
+        stream: zbus::MessageStream,
+        device_id: String,
+        path: String,
+    },
+}
+
+/// Create a stream that reports when KDE Connect has written new vCards for a
+/// device, so contacts are re-read only when something changed.
+///
+/// `localCacheSynchronized` fires after each contacts sync, whether the applet
+/// or the phone started it, and is reported as `ContactsCacheSynchronized`.
+/// Until one arrives the store also polls every
+/// `CONTACTS_FALLBACK_INTERVAL_SECS`, in case this daemon never sends it.
+pub fn contacts_signal_subscription(
+    device_id: String,
+) -> impl futures_util::Stream<Item = Message> {
+    futures_util::stream::unfold(
+        ContactsSignalState::Init { device_id },
+        |state| async move {
+            match state {
+                ContactsSignalState::Init { device_id } => {
+                    let conn = match Connection::session().await {
+                        Ok(c) => c,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Failed to connect to D-Bus for contacts signals: {}",
+                                e
+                            );
+                            tokio::time::sleep(std::time::Duration::from_secs(RETRY_DELAY_SECS))
+                                .await;
+                            return Some((
+                                Message::CheckContactsChanged,
+                                ContactsSignalState::Init { device_id },
+                            ));
+                        }
+                    };
+
+                    let dbus_proxy = match zbus::fdo::DBusProxy::new(&conn).await {
+                        Ok(p) => p,
+                        Err(e) => {
+                            tracing::warn!("Failed to create DBus proxy for contacts: {}", e);
+                            tokio::time::sleep(std::time::Duration::from_secs(RETRY_DELAY_SECS))
+                                .await;
+                            return Some((
+                                Message::CheckContactsChanged,
+                                ContactsSignalState::Init { device_id },
+                            ));
+                        }
+                    };
+
+                    let path = format!(
+                        "{}/devices/{}/contacts",
+                        kdeconnect_dbus::BASE_PATH,
+                        device_id
+                    );
+
+                    let rule = zbus::MatchRule::builder()
+                        .msg_type(zbus::message::Type::Signal)
+                        .interface("org.kde.kdeconnect.device.contacts")
+                        .and_then(|b| b.member("localCacheSynchronized"))
+                        .and_then(|b| b.path(path.as_str()))
+                        .map(|b| b.build());
+                    match rule {
+                        Ok(rule) => {
+                            if let Err(e) = dbus_proxy.add_match_rule(rule).await {
+                                tracing::warn!("Failed to add contacts match rule: {}", e);
+                            }
+                        }
+                        Err(e) => tracing::warn!("Failed to build contacts match rule: {}", e),
+                    }
+
+                    tracing::debug!("Contacts signal subscription started for {}", device_id);
+
+                    let stream = zbus::MessageStream::from(&conn);
+                    // Catch up on anything written before the rule was in place
+                    Some((
+                        Message::CheckContactsChanged,
+                        ContactsSignalState::Listening {
+                            conn,
+                            stream,
+                            device_id,
+                            path,
+                        },
+                    ))
+                }
+                ContactsSignalState::Listening {
+                    conn,
+                    mut stream,
+                    device_id,
+                    path,
+                } => loop {
+                    let msg = match stream.next().await {
+                        Some(Ok(msg)) => msg,
+                        Some(Err(e)) => {
+                            tracing::warn!("D-Bus contacts stream error: {}", e);
+                            continue;
+                        }
+                        None => {
+                            tracing::warn!("D-Bus contacts stream ended, reconnecting...");
+                            return Some((
+                                Message::CheckContactsChanged,
+                                ContactsSignalState::Init { device_id },
+                            ));
+                        }
+                    };
+
+                    let header = msg.header();
+                    if header.message_type() != zbus::message::Type::Signal
+                        || header.path().map(|p| p.as_str()) != Some(path.as_str())
+                    {
+                        continue;
+                    }
+                    let (Some(interface), Some(member)) = (header.interface(), header.member())
+                    else {
+                        continue;
+                    };
+
+                    if interface.as_str() == "org.kde.kdeconnect.device.contacts"
+                        && member.as_str() == "localCacheSynchronized"
+                    {
+                        return Some((
+                            Message::ContactsCacheSynchronized,
+                            ContactsSignalState::Listening {
+                                conn,
+                                stream,
+                                device_id,
+                                path,
+                            },
+                        ));
+                    }
+                },
+            }
+        },
+    )
+}
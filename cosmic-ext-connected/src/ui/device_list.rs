@@ -4,6 +4,7 @@ use crate::app::{DeviceInfo, GroupKind, Message};
 use crate::config::Config;
 use crate::device::DeviceClass;
 use crate::fl;
+use crate::views::helpers::share_drop_target;
 use cosmic::applet;
 use cosmic::iced::advanced::widget::text::Style as TextStyle;
 use cosmic::iced::widget::{column, row};
@@ -16,6 +17,7 @@ pub fn view<'a>(
     devices: &'a [DeviceInfo],
     config: &'a Config,
     status_message: Option<&'a str>,
+    call_media_paused: bool,
 ) -> Element<'a, Message> {
     let sp = cosmic::theme::spacing();
 
@@ -59,6 +61,25 @@ pub fn view<'a>(
         );
     }
 
+    // Media paused for a call: the daemon doesn't report an answered call
+    // ending, so the user resumes it.
+    if call_media_paused {
+        content = content.push(
+            widget::container(
+                row![
+                    text::caption(fl!("call-media-paused")),
+                    widget::space::horizontal(),
+                    widget::button::text(fl!("resume-media")).on_press(Message::ResumeCallMedia),
+                ]
+                .spacing(sp.space_xxs)
+                .align_y(Alignment::Center),
+            )
+            .padding([sp.space_xxxs, sp.space_xxs])
+            .width(Length::Fill)
+            .class(cosmic::theme::Container::Card),
+        );
+    }
+
     if groups.is_empty() {
         content = content.push(
             widget::container(text::caption(fl!("no-devices")))
@@ -241,7 +262,13 @@ fn device_row(device: &DeviceInfo) -> Element<'_, Message> {
     row_content = row_content.push(widget::space::horizontal());
     row_content = row_content.push(icon::from_name("go-next-symbolic").size(16));
 
-    applet::menu_button(row_content)
-        .on_press(Message::SelectDevice(device.id.clone()))
-        .into()
+    let button =
+        applet::menu_button(row_content).on_press(Message::SelectDevice(device.id.clone()));
+
+    // Connected devices accept files dragged onto their row
+    if device.is_reachable && device.is_paired {
+        share_drop_target(&device.id, button)
+    } else {
+        button.into()
+    }
 }
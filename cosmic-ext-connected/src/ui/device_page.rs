@@ -67,12 +67,41 @@ pub fn view<'a>(device: &'a DeviceInfo, status_message: Option<&'a str>) -> Elem
 
         if !device.is_reachable {
             // Offline but paired: online-only actions are unavailable, but Unpair
-            // still works.
+            // still works. Archived SMS and the call history stay readable.
+            if class.is_mobile() {
+                let sms_row = row![
+                    icon::from_name("mail-message-new-symbolic").size(24),
+                    text::body(fl!("sms-messages")),
+                    widget::space::horizontal(),
+                    icon::from_name("go-next-symbolic").size(16),
+                ]
+                .spacing(sp.space_xs)
+                .align_y(Alignment::Center);
+                items.push(
+                    applet::menu_button(sms_row)
+                        .on_press(Message::OpenSmsView(device.id.clone()))
+                        .into(),
+                );
+
+                let calls_row = row![
+                    icon::from_name("call-start-symbolic").size(24),
+                    text::body(fl!("call-history")),
+                    widget::space::horizontal(),
+                    icon::from_name("go-next-symbolic").size(16),
+                ]
+                .spacing(sp.space_xs)
+                .align_y(Alignment::Center);
+                items.push(
+                    applet::menu_button(calls_row)
+                        .on_press(Message::OpenCallHistory(device.id.clone()))
+                        .into(),
+                );
+            }
             items.push(text::caption(fl!("device-offline-actions-unavailable")).into());
         } else {
             let device_id_for_media = device.id.clone();
             if class.is_mobile() {
-                // Mobile: SMS → Send-to submenu → Media → Find Phone.
+                // Mobile: SMS → Calls → Send-to submenu → Media → Find Phone.
                 let device_id_for_sms = device.id.clone();
                 let device_id_for_sendto = device.id.clone();
                 let device_type_for_sendto = device.device_type.clone();
@@ -93,6 +122,20 @@ pub fn view<'a>(device: &'a DeviceInfo, status_message: Option<&'a str>) -> Elem
                         .into(),
                 );
 
+                let calls_row = row![
+                    icon::from_name("call-start-symbolic").size(24),
+                    text::body(fl!("call-history")),
+                    widget::space::horizontal(),
+                    icon::from_name("go-next-symbolic").size(16),
+                ]
+                .spacing(sp.space_xs)
+                .align_y(Alignment::Center);
+                items.push(
+                    applet::menu_button(calls_row)
+                        .on_press(Message::OpenCallHistory(device.id.clone()))
+                        .into(),
+                );
+
                 let sendto_row = row![
                     icon::from_name("document-send-symbolic").size(24),
                     text::body(fl!("send-to", device = device_label.as_str())),
@@ -140,6 +183,7 @@ pub fn view<'a>(device: &'a DeviceInfo, status_message: Option<&'a str>) -> Elem
                 // Non-mobile: inline share primitives as direct actions; Share Text
                 // navigates to a focused compose view. Media stays a submenu nav.
                 let device_id_for_file = device.id.clone();
+                let device_id_for_folder = device.id.clone();
                 let device_id_for_clipboard = device.id.clone();
                 let device_id_for_ping = device.id.clone();
                 let device_id_for_text = device.id.clone();
@@ -158,6 +202,19 @@ pub fn view<'a>(device: &'a DeviceInfo, status_message: Option<&'a str>) -> Elem
                         .into(),
                 );
 
+                let share_folder_row = row![
+                    icon::from_name("folder-symbolic").size(24),
+                    text::body(fl!("share-folder")),
+                    widget::space::horizontal(),
+                ]
+                .spacing(sp.space_xs)
+                .align_y(Alignment::Center);
+                items.push(
+                    applet::menu_button(share_folder_row)
+                        .on_press(Message::ShareFolder(device_id_for_folder))
+                        .into(),
+                );
+
                 let clipboard_row = row![
                     icon::from_name("edit-copy-symbolic").size(24),
                     text::body(fl!("share-clipboard")),
@@ -0,0 +1,166 @@
+//! Contact avatars: the contact's vCard photo, or initials on a colored
+//! circle when there is none.
+
+use cosmic::iced::advanced::image::Handle as ImageHandle;
+use cosmic::iced::{Alignment, Color, ContentFit, Length};
+use cosmic::widget::{self, text};
+use cosmic::Element;
+use kdeconnect_dbus::contacts::ContactLookup;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+/// Background colors for initials avatars, picked by a hash of the name so
+/// a contact keeps the same color everywhere.
+const PALETTE: &[(u8, u8, u8)] = &[
+    (0xC0, 0x39, 0x2B),
+    (0xD3, 0x54, 0x00),
+    (0xB7, 0x95, 0x0B),
+    (0x27, 0xAE, 0x60),
+    (0x16, 0xA0, 0x85),
+    (0x29, 0x80, 0xB9),
+    (0x8E, 0x44, 0xAD),
+    (0x7F, 0x8C, 0x8D),
+];
+
+/// Decoded contact photos, built once per contacts load so views don't
+/// re-decode image bytes on every frame.
+#[derive(Debug, Default)]
+pub struct AvatarCache {
+    /// Keyed by the address of the photo's shared buffer in `ContactLookup`.
+    handles: HashMap<usize, ImageHandle>,
+}
+
+fn photo_key(photo: &Arc<[u8]>) -> usize {
+    Arc::as_ptr(photo) as *const u8 as usize
+}
+
+impl AvatarCache {
+    /// Create image handles for every contact photo in `contacts`.
+    pub fn build(contacts: &ContactLookup) -> Self {
+        let handles = contacts
+            .all_contacts()
+            .iter()
+            .filter_map(|contact| contact.photo.as_ref())
+            .map(|photo| (photo_key(photo), ImageHandle::from_bytes(photo.to_vec())))
+            .collect();
+        Self { handles }
+    }
+
+    /// Photo of the contact with phone number `address`, if they have one.
+    pub fn photo_for(&self, contacts: &ContactLookup, address: &str) -> Option<ImageHandle> {
+        let photo = contacts.get_photo(address)?;
+        self.handles.get(&photo_key(photo)).cloned()
+    }
+}
+
+/// Up to two initials: first letters of the first and last words.
+fn initials(name: &str) -> String {
+    let mut words = name
+        .split_whitespace()
+        .filter(|w| w.chars().next().is_some_and(char::is_alphanumeric));
+    let first = words.next().and_then(|w| w.chars().next());
+    let last = words.last().and_then(|w| w.chars().next());
+    first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+fn palette_color(name: &str) -> Color {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let (r, g, b) = PALETTE[(hasher.finish() % PALETTE.len() as u64) as usize];
+    Color::from_rgb8(r, g, b)
+}
+
+/// Circle of `size` px with `content` centered on `color`.
+fn circle<'a, M: 'a>(content: Element<'a, M>, color: Color, size: f32) -> Element<'a, M> {
+    widget::container(content)
+        .width(Length::Fixed(size))
+        .height(Length::Fixed(size))
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center)
+        .class(cosmic::theme::Container::custom(move |_theme| {
+            cosmic::iced::widget::container::Style {
+                background: Some(color.into()),
+                text_color: Some(Color::WHITE),
+                icon_color: Some(Color::WHITE),
+                border: cosmic::iced::Border {
+                    radius: (size / 2.0).into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+/// Avatar for a conversation or sender: the contact photo for a single
+/// known contact, otherwise initials, a person icon for unknown numbers, or
+/// a group icon when `addresses` has more than one participant.
+pub fn avatar<'a, M: 'a>(
+    contacts: &ContactLookup,
+    cache: &AvatarCache,
+    addresses: &[String],
+    size: f32,
+) -> Element<'a, M> {
+    let mut unique: Vec<&String> = addresses.iter().collect();
+    unique.sort();
+    unique.dedup();
+    let icon_size = (size * 0.6) as u16;
+
+    let [address] = unique.as_slice() else {
+        let seed = unique
+            .iter()
+            .map(|a| a.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        return circle(
+            widget::icon::from_name("system-users-symbolic")
+                .size(icon_size)
+                .into(),
+            palette_color(&seed),
+            size,
+        );
+    };
+
+    if let Some(handle) = cache.photo_for(contacts, address) {
+        return cosmic::iced::widget::image(handle)
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size))
+            .content_fit(ContentFit::Cover)
+            .border_radius([size / 2.0; 4])
+            .into();
+    }
+
+    match contacts.get_name(address) {
+        Some(name) if !initials(name).is_empty() => circle(
+            text::body(initials(name)).size(size * 0.4).into(),
+            palette_color(name),
+            size,
+        ),
+        _ => circle(
+            widget::icon::from_name("avatar-default-symbolic")
+                .size(icon_size)
+                .into(),
+            palette_color(address),
+            size,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initials_from_names() {
+        assert_eq!(initials("Ana García"), "AG");
+        assert_eq!(initials("björn af Ström"), "BS");
+        assert_eq!(initials("Mom"), "M");
+        assert_eq!(initials("  (Work) Dana "), "D");
+        assert_eq!(initials(""), "");
+    }
+}
@@ -1,4 +1,3 @@
 //! Reusable UI widgets for the Connected applet.
 
-// Placeholder for custom widgets
-// Examples: battery indicator, connection status badge, etc.
+pub mod avatar;
@@ -0,0 +1,89 @@
+//! Conversions between local paths and the `file://` URLs the daemon and
+//! file managers exchange.
+
+use std::path::{Path, PathBuf};
+
+/// Local path for a URL. Accepts `file://` URLs (percent-encoded) and bare
+/// absolute paths; anything else (`http://`, `content://`) is not reachable
+/// from the desktop and yields `None`.
+pub fn local_path(url: &str) -> Option<PathBuf> {
+    let path = if let Some(rest) = url.strip_prefix("file://") {
+        // Drop an optional host ("file://localhost/...").
+        let rest = &rest[rest.find('/')?..];
+        percent_decode(rest)?
+    } else if url.starts_with('/') {
+        url.to_string()
+    } else {
+        return None;
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Decode `%XX` escapes. `None` on a malformed escape or invalid UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Percent-encode a local path as a `file://` URL.
+pub fn file_url(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    let mut url = String::from("file://");
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_path_from_file_urls() {
+        assert_eq!(
+            local_path("file:///home/me/.cache/kdeconnect/art%20one.jpg"),
+            Some(PathBuf::from("/home/me/.cache/kdeconnect/art one.jpg"))
+        );
+        assert_eq!(
+            local_path("file://localhost/tmp/%C3%A4.png"),
+            Some(PathBuf::from("/tmp/ä.png"))
+        );
+        assert_eq!(
+            local_path("/tmp/cover.png"),
+            Some(PathBuf::from("/tmp/cover.png"))
+        );
+        assert_eq!(local_path("https://example.com/cover.jpg"), None);
+        assert_eq!(local_path("file:///tmp/bad%2"), None);
+    }
+
+    #[test]
+    fn file_url_percent_encodes() {
+        assert_eq!(
+            file_url(Path::new("/home/me/My Photos/ä.jpg")),
+            "file:///home/me/My%20Photos/%C3%A4.jpg"
+        );
+    }
+
+    #[test]
+    fn file_url_round_trips() {
+        let path = Path::new("/tmp/a b/#1 ä.txt");
+        assert_eq!(local_path(&file_url(path)).as_deref(), Some(path));
+    }
+}
@@ -1,5 +1,10 @@
 //! Helper functions and constants for view rendering.
 
+use crate::app::Message;
+use crate::device::DroppedFiles;
+use cosmic::widget::dnd_destination::dnd_destination_for_data;
+use cosmic::Element;
+
 /// Format a Unix timestamp as a human-readable date/time string.
 pub fn format_timestamp(timestamp: i64) -> String {
     use chrono::{Local, TimeZone};
@@ -27,3 +32,16 @@ pub fn format_duration(ms: i64) -> String {
     let seconds = total_seconds % 60;
     format!("{}:{:02}", minutes, seconds)
 }
+
+/// Accept files and folders dragged from a file manager onto `content` and
+/// share them with `device_id`.
+pub fn share_drop_target<'a>(
+    device_id: &str,
+    content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let device_id = device_id.to_string();
+    dnd_destination_for_data(content, move |files: Option<DroppedFiles>, _action| {
+        Message::DropFiles(device_id.clone(), files.map(|f| f.0).unwrap_or_default())
+    })
+    .into()
+}
@@ -2,6 +2,7 @@
 
 use crate::app::Message;
 use crate::fl;
+use crate::views::helpers::share_drop_target;
 use cosmic::applet;
 use cosmic::iced::widget::{column, row};
 use cosmic::iced::{Alignment, Length};
@@ -56,6 +57,7 @@ pub fn view_send_to(params: SendToParams<'_>) -> Element<'_, Message> {
 
     // Action list items (consistent with device page style)
     let device_id_for_file = device_id.clone();
+    let device_id_for_folder = device_id.clone();
     let device_id_for_clipboard = device_id.clone();
     let device_id_for_ping = device_id.clone();
     let device_id_for_text = device_id.clone();
@@ -73,6 +75,18 @@ pub fn view_send_to(params: SendToParams<'_>) -> Element<'_, Message> {
     let share_file_item =
         applet::menu_button(share_file_row).on_press(Message::ShareFile(device_id_for_file));
 
+    // Share folder list item
+    let share_folder_row = row![
+        icon::from_name("folder-symbolic").size(24),
+        text::body(fl!("share-folder")),
+        widget::space::horizontal(),
+    ]
+    .spacing(sp.space_xs)
+    .align_y(Alignment::Center);
+
+    let share_folder_item =
+        applet::menu_button(share_folder_row).on_press(Message::ShareFolder(device_id_for_folder));
+
     // Send clipboard list item
     let send_clipboard_row = row![
         icon::from_name("edit-copy-symbolic").size(24),
@@ -129,22 +143,27 @@ pub fn view_send_to(params: SendToParams<'_>) -> Element<'_, Message> {
 
     let divider = || applet::padded_control(widget::divider::horizontal::default());
 
-    widget::container(
-        column![
-            header,
-            status_bar,
-            share_file_item,
-            send_clipboard_item,
-            send_ping_item,
-            divider(),
-            applet::padded_control(
-                column![share_text_heading, share_text_input, send_text_btn,].spacing(sp.space_xs),
-            ),
-        ]
-        .spacing(sp.space_xxxs)
-        .padding([0, sp.space_s as u16, sp.space_s as u16, sp.space_s as u16]),
+    // Files dragged from a file manager anywhere onto the view are shared
+    share_drop_target(
+        &device_id,
+        widget::container(
+            column![
+                header,
+                status_bar,
+                share_file_item,
+                share_folder_item,
+                send_clipboard_item,
+                send_ping_item,
+                divider(),
+                applet::padded_control(
+                    column![share_text_heading, share_text_input, send_text_btn,]
+                        .spacing(sp.space_xs),
+                ),
+            ]
+            .spacing(sp.space_xxxs)
+            .padding([0, sp.space_s as u16, sp.space_s as u16, sp.space_s as u16]),
+        ),
     )
-    .into()
 }
 
 /// View parameters for the focused Share Text compose view.
@@ -8,6 +8,7 @@ use cosmic::iced::widget::row;
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget::{self, settings, text};
 use cosmic::Element;
+use kdeconnect_dbus::PhoneRegion;
 
 /// Render the main settings view (general settings + nav to notification settings).
 pub fn view_settings(config: &Config) -> Element<'_, Message> {
@@ -65,6 +66,15 @@ pub fn view_settings(config: &Config) -> Element<'_, Message> {
                     }),
             );
     }
+    sms_section = sms_section.add(
+        settings::item::builder(fl!("settings-phone-region"))
+            .description(fl!("settings-phone-region-description"))
+            .control(
+                widget::text_input(PhoneRegion::from_env().code, &config.phone_region)
+                    .on_input(Message::SetPhoneRegion)
+                    .width(Length::Fixed(56.0)),
+            ),
+    );
 
     // Call notifications section
     let mut call_section = settings::section().title(fl!("settings-call-section")).add(
@@ -90,13 +100,53 @@ pub fn view_settings(config: &Config) -> Element<'_, Message> {
             );
     }
 
-    // File notifications section
-    let file_section = settings::section().title(fl!("settings-file-section")).add(
-        settings::item::builder(fl!("settings-file-notifications"))
-            .toggler(config.file_notifications, move |_| {
-                Message::ToggleSetting(SettingKey::FileNotifications)
+    call_section = call_section.add(
+        settings::item::builder(fl!("settings-call-history"))
+            .description(fl!("settings-call-history-description"))
+            .toggler(config.call_history, move |_| {
+                Message::ToggleSetting(SettingKey::CallHistory)
+            }),
+    );
+
+    call_section = call_section.add(
+        settings::item::builder(fl!("settings-call-pause-media"))
+            .description(fl!("settings-call-pause-media-description"))
+            .toggler(config.call_pause_media, move |_| {
+                Message::ToggleSetting(SettingKey::CallPauseMedia)
             }),
     );
+    if config.call_pause_media {
+        call_section = call_section.add(
+            settings::item::builder(fl!("settings-call-lower-volume"))
+                .toggler(config.call_lower_volume, move |_| {
+                    Message::ToggleSetting(SettingKey::CallLowerVolume)
+                }),
+        );
+        call_section = call_section.add(
+            settings::item::builder(fl!("settings-call-resume-after-ring"))
+                .description(fl!("settings-call-resume-after-ring-description"))
+                .toggler(config.call_resume_after_ring, move |_| {
+                    Message::ToggleSetting(SettingKey::CallResumeAfterRing)
+                }),
+        );
+    }
+
+    // File notifications section
+    let file_section = settings::section()
+        .title(fl!("settings-file-section"))
+        .add(
+            settings::item::builder(fl!("settings-file-notifications"))
+                .toggler(config.file_notifications, move |_| {
+                    Message::ToggleSetting(SettingKey::FileNotifications)
+                }),
+        )
+        .add(
+            settings::item::builder(fl!("settings-share-folders-as-zip"))
+                .description(fl!("settings-share-folders-as-zip-description"))
+                .toggler(config.share_folders_as_zip, move |_| {
+                    Message::ToggleSetting(SettingKey::ShareFoldersAsZip)
+                }),
+        );
 
     let sections = settings::view_column(vec![
         sms_section.into(),
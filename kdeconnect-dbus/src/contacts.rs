@@ -3,28 +3,116 @@
 //! KDE Connect syncs contacts as vCard files to ~/.local/share/kpeoplevcard/kdeconnect-{device-id}/
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
 
-/// A contact with name and phone numbers.
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+
+use crate::phone::{has_country_code, phone_key};
+
+/// What a phone number is for, from its vCard TEL type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhoneKind {
+    Mobile,
+    Home,
+    Work,
+    Other,
+}
+
+/// One of a contact's phone numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactPhone {
+    /// The number as written in the vCard.
+    pub number: String,
+    pub kind: PhoneKind,
+}
+
+/// A contact parsed from a vCard.
 #[derive(Debug, Clone)]
 pub struct Contact {
-    /// Display name (from FN field).
+    /// Display name (FN, falling back to N, then ORG).
     pub name: String,
-    /// List of phone numbers associated with this contact.
-    pub phone_numbers: Vec<String>,
+    /// Phone numbers, in file order.
+    pub phones: Vec<ContactPhone>,
+    /// Email addresses, in file order.
+    pub emails: Vec<String>,
+    /// Organization (ORG), components joined with ", ".
+    pub organization: Option<String>,
+    /// Embedded PHOTO image bytes (usually JPEG), if any.
+    pub photo: Option<Arc<[u8]>>,
 }
 
 /// Contact lookup cache mapping normalized phone numbers to contact names.
 #[derive(Debug, Clone, Default)]
 pub struct ContactLookup {
-    /// Map from full normalized phone number (all digits) to contact name.
-    /// Used for exact matching.
+    /// Map from [`phone_key`] (region-aware canonical number) to contact
+    /// name. Used for exact matching.
     phone_to_name: HashMap<String, String>,
-    /// Map from phone suffix (last N digits) to contact name.
-    /// Used for fuzzy matching when exact match fails.
+    /// Map from phone suffix (last N digits) to contact name, for numbers
+    /// stored without a country code. Used for fuzzy matching when exact
+    /// match fails.
     suffix_to_name: HashMap<String, String>,
+    /// Contact photos, keyed like `phone_to_name` / `suffix_to_name`.
+    phone_to_photo: HashMap<String, Arc<[u8]>>,
+    suffix_to_photo: HashMap<String, Arc<[u8]>>,
     /// Full list of contacts for name-based searching.
     contacts: Vec<Contact>,
+    /// Parsed vCard files by path, for incremental reloads.
+    sources: HashMap<PathBuf, Arc<VcardSource>>,
+}
+
+/// Contacts parsed from one vCard file.
+#[derive(Debug)]
+struct VcardSource {
+    /// File modification time when it was read.
+    modified: Option<SystemTime>,
+    contacts: Vec<Contact>,
+}
+
+/// The kpeoplevcard directory KDE Connect syncs a device's contacts into.
+pub fn vcard_dir(device_id: &str) -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| {
+        dir.join("kpeoplevcard")
+            .join(format!("kdeconnect-{}", device_id))
+    })
+}
+
+/// Parse the `.vcf` files in `vcard_dir`, reusing entries of `previous` whose
+/// modification time is unchanged. Empty if the directory can't be read.
+async fn read_sources(
+    vcard_dir: &Path,
+    previous: &HashMap<PathBuf, Arc<VcardSource>>,
+) -> HashMap<PathBuf, Arc<VcardSource>> {
+    let mut sources = HashMap::new();
+
+    // Read all .vcf files asynchronously
+    let mut entries = match tokio::fs::read_dir(vcard_dir).await {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::debug!("Failed to read vCard directory {:?}: {}", vcard_dir, e);
+            return sources;
+        }
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if !path.extension().map(|e| e == "vcf").unwrap_or(false) {
+            continue;
+        }
+        let modified = entry.metadata().await.and_then(|m| m.modified()).ok();
+        let source = match previous.get(&path) {
+            Some(prev) if modified.is_some() && prev.modified == modified => prev.clone(),
+            _ => Arc::new(VcardSource {
+                modified,
+                contacts: parse_vcard_file(&path).await,
+            }),
+        };
+        sources.insert(path, source);
+    }
+    sources
 }
 
 impl ContactLookup {
@@ -36,62 +124,62 @@ impl ContactLookup {
     /// Load contacts asynchronously from the kpeoplevcard directory for a specific device.
     /// Uses tokio::fs for non-blocking file I/O.
     pub async fn load_for_device(device_id: &str) -> Self {
-        let mut lookup = Self::new();
-
-        // Get the kpeoplevcard directory
-        let vcard_dir = match dirs::data_local_dir() {
-            Some(dir) => dir
-                .join("kpeoplevcard")
-                .join(format!("kdeconnect-{}", device_id)),
-            None => {
-                tracing::warn!("Could not find local data directory for contacts");
-                return lookup;
-            }
+        let Some(vcard_dir) = vcard_dir(device_id) else {
+            tracing::warn!("Could not find local data directory for contacts");
+            return Self::new();
         };
+        Self::from_sources(read_sources(&vcard_dir, &HashMap::new()).await)
+    }
 
-        // Check if directory exists using async metadata
-        match tokio::fs::metadata(&vcard_dir).await {
-            Ok(meta) if meta.is_dir() => {}
-            Ok(_) => {
-                tracing::debug!("vCard path is not a directory: {:?}", vcard_dir);
-                return lookup;
-            }
-            Err(_) => {
-                tracing::debug!("vCard directory does not exist: {:?}", vcard_dir);
-                return lookup;
-            }
+    /// Re-read the device's vCard directory and return an updated lookup if
+    /// any file was added, removed or modified since `self` was loaded.
+    ///
+    /// Unchanged files are not re-parsed; their contacts are carried over.
+    /// The returned future owns everything it needs, so it can be spawned
+    /// while `self` stays in use.
+    pub fn reload_changed(
+        &self,
+        device_id: &str,
+    ) -> impl std::future::Future<Output = Option<Self>> + Send + 'static {
+        let previous = self.sources.clone();
+        let vcard_dir = vcard_dir(device_id);
+        async move {
+            let sources = read_sources(&vcard_dir?, &previous).await;
+            let changed = sources.len() != previous.len()
+                || sources
+                    .iter()
+                    .any(|(path, s)| !previous.get(path).is_some_and(|p| Arc::ptr_eq(p, s)));
+            changed.then(|| Self::from_sources(sources))
         }
+    }
 
-        // Read all .vcf files asynchronously
-        let mut entries = match tokio::fs::read_dir(&vcard_dir).await {
-            Ok(e) => e,
-            Err(e) => {
-                tracing::warn!("Failed to read vCard directory: {}", e);
-                return lookup;
-            }
-        };
+    /// Build the lookup maps from parsed vCard files.
+    fn from_sources(sources: HashMap<PathBuf, Arc<VcardSource>>) -> Self {
+        let mut lookup = Self::new();
 
-        while let Ok(Some(entry)) = entries.next_entry().await {
-            let path = entry.path();
-            if path.extension().map(|e| e == "vcf").unwrap_or(false) {
-                if let Some(contact) = parse_vcard_async(&path).await {
-                    for phone in &contact.phone_numbers {
-                        let (full_digits, suffix) = get_phone_keys(phone);
-                        if full_digits.len() >= MIN_PHONE_DIGITS {
-                            // Store in exact match map
-                            lookup
-                                .phone_to_name
-                                .insert(full_digits, contact.name.clone());
-                            // Store in suffix map for fuzzy matching
-                            // (only if suffix differs from full, to avoid duplicates)
-                            if suffix.len() >= MIN_PHONE_DIGITS {
-                                lookup.suffix_to_name.insert(suffix, contact.name.clone());
-                            }
+        // Files in name order so conflicting numbers resolve the same way
+        // on every load
+        let mut paths: Vec<&PathBuf> = sources.keys().collect();
+        paths.sort();
+        for contact in paths.iter().flat_map(|p| &sources[*p].contacts) {
+            for phone in &contact.phones {
+                let (key, suffix) = get_phone_keys(&phone.number);
+                if key.len() >= MIN_PHONE_DIGITS {
+                    if let Some(photo) = &contact.photo {
+                        lookup.phone_to_photo.insert(key.clone(), photo.clone());
+                        if let Some(suffix) = &suffix {
+                            lookup.suffix_to_photo.insert(suffix.clone(), photo.clone());
                         }
                     }
-                    lookup.contacts.push(contact);
+                    // Store in exact match map
+                    lookup.phone_to_name.insert(key, contact.name.clone());
+                    // Store in suffix map for fuzzy matching
+                    if let Some(suffix) = suffix {
+                        lookup.suffix_to_name.insert(suffix, contact.name.clone());
+                    }
                 }
             }
+            lookup.contacts.push(contact.clone());
         }
 
         // Sort contacts alphabetically by name for consistent display
@@ -103,6 +191,7 @@ impl ContactLookup {
             lookup.phone_to_name.len(),
             lookup.suffix_to_name.len()
         );
+        lookup.sources = sources;
         lookup
     }
 
@@ -110,26 +199,12 @@ impl ContactLookup {
     /// Tries exact match first, then falls back to suffix matching.
     /// Returns None if no contact is found.
     pub fn get_name(&self, phone_number: &str) -> Option<&str> {
-        let (full_digits, suffix) = get_phone_keys(phone_number);
-
-        // Skip lookup for numbers that are too short
-        if full_digits.len() < MIN_PHONE_DIGITS {
-            return None;
-        }
-
-        // Try exact match first
-        if let Some(name) = self.phone_to_name.get(&full_digits) {
-            return Some(name.as_str());
-        }
-
-        // Fall back to suffix matching
-        if suffix.len() >= MIN_PHONE_DIGITS {
-            if let Some(name) = self.suffix_to_name.get(&suffix) {
-                return Some(name.as_str());
-            }
-        }
+        lookup_phone(&self.phone_to_name, &self.suffix_to_name, phone_number).map(String::as_str)
+    }
 
-        None
+    /// Look up a contact's photo by phone number, matched like [`Self::get_name`].
+    pub fn get_photo(&self, phone_number: &str) -> Option<&Arc<[u8]>> {
+        lookup_phone(&self.phone_to_photo, &self.suffix_to_photo, phone_number)
     }
 
     /// Look up a contact name by phone number, returning the phone number if not found.
@@ -193,6 +268,27 @@ impl ContactLookup {
     }
 }
 
+/// Exact match on the phone key first, then the digit suffix. The suffix
+/// only links numbers that both lack a country code: `+49 555 1234567` must
+/// not pick up a contact stored as `+1 555-123-4567`.
+fn lookup_phone<'a, V>(
+    exact: &'a HashMap<String, V>,
+    by_suffix: &'a HashMap<String, V>,
+    phone_number: &str,
+) -> Option<&'a V> {
+    let (key, suffix) = get_phone_keys(phone_number);
+
+    // Skip lookup for numbers that are too short
+    if key.len() < MIN_PHONE_DIGITS {
+        return None;
+    }
+
+    // Try exact match first, then fall back to suffix matching
+    exact
+        .get(&key)
+        .or_else(|| suffix.and_then(|suffix| by_suffix.get(&suffix)))
+}
+
 /// Minimum number of digits for a valid phone number match.
 const MIN_PHONE_DIGITS: usize = 7;
 
@@ -218,47 +314,436 @@ pub fn phone_suffix(digits: &str) -> &str {
 }
 
 /// Get all normalized forms of a phone number for storage/lookup.
-/// Returns (full_digits, suffix) where suffix may equal full_digits for short numbers.
-fn get_phone_keys(phone: &str) -> (String, String) {
+/// Returns (key, suffix): the [`phone_key`] in the default region, and the
+/// digit suffix as a fallback for numbers the region rules can't place.
+/// Numbers written with a country code have no suffix; their key already
+/// says which country they belong to.
+fn get_phone_keys(phone: &str) -> (String, Option<String>) {
     let digits = normalize_phone_number(phone);
-    let suffix = phone_suffix(&digits).to_string();
-    (digits, suffix)
+    let suffix = phone_suffix(&digits);
+    let suffix =
+        (suffix.len() >= MIN_PHONE_DIGITS && !has_country_code(phone)).then(|| suffix.to_string());
+    (phone_key(phone), suffix)
 }
 
-/// Parse a vCard file asynchronously and extract the contact information.
-async fn parse_vcard_async(path: &PathBuf) -> Option<Contact> {
-    let content = tokio::fs::read_to_string(path).await.ok()?;
+/// Read a `.vcf` file. A file may hold several vCards (Android "export
+/// all" writes one file); unreadable files yield none.
+async fn parse_vcard_file(path: &PathBuf) -> Vec<Contact> {
+    match tokio::fs::read(path).await {
+        // Lossy: vCard 2.1 files may be in a legacy charset outside
+        // quoted-printable values
+        Ok(bytes) => parse_vcards(&String::from_utf8_lossy(&bytes)),
+        Err(e) => {
+            tracing::debug!("Failed to read vCard {:?}: {}", path, e);
+            Vec::new()
+        }
+    }
+}
 
-    let mut name = String::new();
-    let mut phone_numbers = Vec::new();
+/// One unfolded content line: `[group.]NAME[;PARAM...]:VALUE`.
+#[derive(Debug)]
+struct ContentLine {
+    group: Option<String>,
+    /// Property name, upper-cased.
+    name: String,
+    /// (upper-cased key, value). vCard 2.1 bare parameters (`TEL;CELL:`)
+    /// are stored under `TYPE` or `ENCODING`.
+    params: Vec<(String, String)>,
+    /// Raw value, still escaped / encoded.
+    value: String,
+}
+
+impl ContentLine {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// All `TYPE` values, lower-cased, with comma lists split.
+    fn types(&self) -> Vec<String> {
+        self.params
+            .iter()
+            .filter(|(k, _)| k == "TYPE")
+            .flat_map(|(_, v)| v.split(','))
+            .map(|t| t.trim().trim_matches('"').to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
 
-    for line in content.lines() {
-        // Handle FN (Full Name) field
-        if let Some(fn_value) = line.strip_prefix("FN:") {
-            name = fn_value.trim().to_string();
+    /// The value with quoted-printable and charset decoding applied, but
+    /// backslash escapes left in place.
+    fn decoded(&self) -> String {
+        let quoted_printable = self
+            .param("ENCODING")
+            .is_some_and(|e| e.eq_ignore_ascii_case("QUOTED-PRINTABLE"));
+        if !quoted_printable {
+            return self.value.clone();
         }
-        // Handle TEL (telephone) fields - various formats
-        else if line.starts_with("TEL") {
-            // TEL;CELL:1234567890
-            // TEL;TYPE=CELL:1234567890
-            // TEL:1234567890
-            if let Some(idx) = line.find(':') {
-                let number = line[idx + 1..].trim().to_string();
-                if !number.is_empty() && !number.contains('=') {
-                    // Skip encoded numbers for now
-                    phone_numbers.push(number);
-                }
+        let bytes = decode_quoted_printable(&self.value);
+        match self
+            .param("CHARSET")
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("iso-8859-1" | "latin1" | "windows-1252" | "cp1252") => {
+                bytes.iter().map(|&b| b as char).collect()
+            }
+            _ => String::from_utf8_lossy(&bytes).into_owned(),
+        }
+    }
+
+    /// Decoded, unescaped value.
+    fn text(&self) -> String {
+        unescape(&self.decoded()).trim().to_string()
+    }
+
+    /// Decoded value split into `;`-separated components (N, ORG).
+    fn components(&self) -> Vec<String> {
+        split_unescaped(&self.decoded(), ';')
+            .iter()
+            .map(|c| unescape(c).trim().to_string())
+            .collect()
+    }
+}
+
+/// Whether a (not yet complete) line uses quoted-printable, whose soft line
+/// breaks are a trailing `=` rather than leading whitespace.
+fn is_quoted_printable(line: &str) -> bool {
+    line.split(':')
+        .next()
+        .is_some_and(|head| head.to_ascii_uppercase().contains("QUOTED-PRINTABLE"))
+}
+
+/// Join folded lines: RFC 6350 folding (continuation starts with a space or
+/// tab) and vCard 2.1 quoted-printable soft breaks (line ends with `=`).
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(current) = lines.last_mut() {
+            if raw.starts_with([' ', '\t']) {
+                current.push_str(&raw[1..]);
+                continue;
+            }
+            if current.ends_with('=') && is_quoted_printable(current) {
+                current.pop();
+                current.push_str(raw);
+                continue;
+            }
+        }
+        if !raw.trim().is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Split on `sep`, ignoring separators inside double quotes.
+fn split_unquoted(text: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == sep && !in_quotes {
+            parts.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Split on `sep`, ignoring backslash-escaped separators.
+fn split_unescaped(text: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == sep {
+            parts.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Undo vCard backslash escapes (`\n`, `\,`, `\;`, `\\`).
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn decode_quoted_printable(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
             }
         }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+const ENCODING_KEYWORDS: &[&str] = &["QUOTED-PRINTABLE", "BASE64", "B", "7BIT", "8BIT"];
+
+fn parse_content_line(line: &str) -> Option<ContentLine> {
+    let colon = {
+        let mut in_quotes = false;
+        line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                None
+            }
+            ':' if !in_quotes => Some(i),
+            _ => None,
+        })?
+    };
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = split_unquoted(head, ';').into_iter();
+    let full_name = parts.next()?.trim();
+    let (group, name) = match full_name.rsplit_once('.') {
+        Some((group, name)) => (Some(group.to_string()), name),
+        None => (None, full_name),
+    };
+    let params = parts
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| match p.split_once('=') {
+            Some((key, value)) => (
+                key.trim().to_ascii_uppercase(),
+                value.trim().trim_matches('"').to_string(),
+            ),
+            None if ENCODING_KEYWORDS.contains(&p.trim().to_ascii_uppercase().as_str()) => {
+                ("ENCODING".to_string(), p.trim().to_string())
+            }
+            None => ("TYPE".to_string(), p.trim().to_string()),
+        })
+        .collect();
+    Some(ContentLine {
+        group,
+        name: name.to_ascii_uppercase(),
+        params,
+        value: value.to_string(),
+    })
+}
+
+impl PhoneKind {
+    /// Kind from TEL `TYPE` values or an Apple `X-ABLabel`. A mobile type
+    /// wins over home/work ("work,cell" is a work mobile).
+    fn from_types(types: &[String]) -> Self {
+        let has = |names: &[&str]| types.iter().any(|t| names.contains(&t.as_str()));
+        if has(&["cell", "mobile", "iphone"]) {
+            PhoneKind::Mobile
+        } else if has(&["work"]) {
+            PhoneKind::Work
+        } else if has(&["home"]) {
+            PhoneKind::Home
+        } else {
+            PhoneKind::Other
+        }
     }
+}
+
+/// Number part of a TEL value: strips a `tel:` URI scheme and URI
+/// parameters (`;ext=`).
+fn tel_number(value: &str) -> String {
+    let value = value.trim();
+    let value = match value.get(..4) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("tel:") => &value[4..],
+        _ => value,
+    };
+    value.split(';').next().unwrap_or("").trim().to_string()
+}
+
+/// Base64 as found in vCards: wrapped, and not always padded.
+const LENIENT_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &base64::alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
 
-    if name.is_empty() || phone_numbers.is_empty() {
+/// Image bytes from an inline PHOTO: `ENCODING=b` / `BASE64` (3.0, 2.1) or
+/// a `data:` URI (4.0). Linked (http) photos are not fetched.
+fn decode_photo(line: &ContentLine) -> Option<Arc<[u8]>> {
+    let value = line.value.trim();
+    let payload = if let Some(uri) = value.strip_prefix("data:") {
+        let (header, data) = uri.split_once(',')?;
+        if !header.ends_with(";base64") {
+            return None;
+        }
+        data
+    } else if line
+        .param("ENCODING")
+        .is_some_and(|e| e.eq_ignore_ascii_case("b") || e.eq_ignore_ascii_case("BASE64"))
+    {
+        value
+    } else {
         return None;
+    };
+    let clean: String = payload
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    LENIENT_BASE64
+        .decode(clean)
+        .ok()
+        .filter(|bytes| !bytes.is_empty())
+        .map(Arc::from)
+}
+
+/// "Prefix Given Additional Family Suffix" from an `N` value.
+fn name_from_components(components: &[String]) -> String {
+    let part = |i: usize| components.get(i).map(String::as_str).unwrap_or("");
+    [part(3), part(1), part(2), part(0), part(4)]
+        .iter()
+        .flat_map(|p| p.split(','))
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse every vCard in `content` (versions 2.1, 3.0 and 4.0).
+///
+/// The display name is `FN`, falling back to `N` and then `ORG`. Cards
+/// with no name, or with neither a phone number nor an email, are skipped.
+pub fn parse_vcards(content: &str) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+    let mut card: Option<Vec<ContentLine>> = None;
+    for line in unfold(content) {
+        let Some(line) = parse_content_line(&line) else {
+            continue;
+        };
+        match line.name.as_str() {
+            "BEGIN" if line.value.trim().eq_ignore_ascii_case("VCARD") => {
+                card = Some(Vec::new());
+            }
+            "END" if line.value.trim().eq_ignore_ascii_case("VCARD") => {
+                if let Some(contact) = card.take().and_then(|lines| contact_from_lines(&lines)) {
+                    contacts.push(contact);
+                }
+            }
+            _ => {
+                if let Some(lines) = card.as_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    contacts
+}
+
+fn contact_from_lines(lines: &[ContentLine]) -> Option<Contact> {
+    let mut formatted_name = None;
+    let mut structured_name = None;
+    let mut organization = None;
+    let mut phones = Vec::new();
+    let mut emails: Vec<String> = Vec::new();
+    let mut photo = None;
+
+    // Apple/Android group labels: `item1.X-ABLabel:_$!<Mobile>!$_`
+    let labels: HashMap<&str, String> = lines
+        .iter()
+        .filter(|l| l.name == "X-ABLABEL")
+        .filter_map(|l| {
+            let label = l.text();
+            let label = label
+                .strip_prefix("_$!<")
+                .and_then(|s| s.strip_suffix(">!$_"))
+                .unwrap_or(&label)
+                .to_lowercase();
+            Some((l.group.as_deref()?, label))
+        })
+        .collect();
+
+    for line in lines {
+        match line.name.as_str() {
+            "FN" => {
+                let name = line.text();
+                if !name.is_empty() {
+                    formatted_name = Some(name);
+                }
+            }
+            "N" => {
+                let name = name_from_components(&line.components());
+                if !name.is_empty() {
+                    structured_name = Some(name);
+                }
+            }
+            "ORG" => {
+                let org: Vec<String> = line
+                    .components()
+                    .into_iter()
+                    .filter(|c| !c.is_empty())
+                    .collect();
+                if !org.is_empty() {
+                    organization = Some(org.join(", "));
+                }
+            }
+            "TEL" => {
+                let number = tel_number(&line.decoded());
+                if number.is_empty() {
+                    continue;
+                }
+                let mut types = line.types();
+                if let Some(label) = line.group.as_deref().and_then(|g| labels.get(g)) {
+                    types.push(label.clone());
+                }
+                phones.push(ContactPhone {
+                    number,
+                    kind: PhoneKind::from_types(&types),
+                });
+            }
+            "PHOTO" if photo.is_none() => photo = decode_photo(line),
+            "EMAIL" => {
+                let email = line.text();
+                if !email.is_empty() && !emails.contains(&email) {
+                    emails.push(email);
+                }
+            }
+            _ => {}
+        }
     }
 
+    let name = formatted_name
+        .or(structured_name)
+        .or_else(|| organization.clone())?;
+    if phones.is_empty() && emails.is_empty() {
+        return None;
+    }
     Some(Contact {
         name,
-        phone_numbers,
+        phones,
+        emails,
+        organization,
+        photo,
     })
 }
 
@@ -288,58 +773,69 @@ mod tests {
 
     #[test]
     fn test_get_phone_keys() {
-        // US number with country code
+        // US number with country code (default region is US)
         let (full, suffix) = get_phone_keys("+1-555-123-4567");
-        assert_eq!(full, "15551234567");
-        assert_eq!(suffix, "5551234567");
+        assert_eq!(full, "5551234567");
+        assert_eq!(suffix, None);
 
         // US number without country code
         let (full, suffix) = get_phone_keys("(555) 123-4567");
         assert_eq!(full, "5551234567");
-        assert_eq!(suffix, "5551234567"); // Same since <= 10 digits
+        assert_eq!(suffix.as_deref(), Some("5551234567")); // Same since <= 10 digits
 
-        // International number
+        // International number keeps its country code
         let (full, suffix) = get_phone_keys("+49-555-123-4567");
-        assert_eq!(full, "495551234567");
-        assert_eq!(suffix, "5551234567");
+        assert_eq!(full, "+495551234567");
+        assert_eq!(suffix, None);
     }
 
     #[test]
     fn test_suffix_matching() {
         let mut lookup = ContactLookup::new();
 
-        // Simulate contact stored with country code
-        let contact_phone = "15551234567"; // US format with 1
+        // Simulate a foreign contact stored without its leading +
         lookup
             .phone_to_name
-            .insert(contact_phone.to_string(), "John Doe".to_string());
+            .insert("4915551234567".to_string(), "John Doe".to_string());
         lookup
             .suffix_to_name
             .insert("5551234567".to_string(), "John Doe".to_string());
 
-        // Should match with country code
-        assert_eq!(lookup.get_name("+1-555-123-4567"), Some("John Doe"));
-
         // Should match without country code via suffix
         assert_eq!(lookup.get_name("555-123-4567"), Some("John Doe"));
 
         // Should match formatted differently
         assert_eq!(lookup.get_name("(555) 123-4567"), Some("John Doe"));
+
+        // A number with a country code only matches exactly
+        assert_eq!(lookup.get_name("+1-555-123-4567"), None);
+    }
+
+    #[test]
+    fn test_suffix_ignores_other_countries() {
+        let card = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Alice\r\nTEL:+1 555-123-4567\r\nEND:VCARD\r\n";
+        let mut sources = HashMap::new();
+        sources.insert(
+            PathBuf::from("a.vcf"),
+            Arc::new(VcardSource {
+                modified: None,
+                contacts: parse_vcards(card),
+            }),
+        );
+        let lookup = ContactLookup::from_sources(sources);
+        assert_eq!(lookup.get_name("(555) 123-4567"), Some("Alice"));
+        // Same last ten digits, different country
+        assert_eq!(lookup.get_name("+49 555 1234567"), None);
+        assert_eq!(lookup.get_name("0049 555 1234567"), None);
     }
 
     fn lookup_with_contacts() -> ContactLookup {
         let mut lookup = ContactLookup::new();
         lookup
             .phone_to_name
-            .insert("15551234567".to_string(), "Alice".to_string());
-        lookup
-            .suffix_to_name
             .insert("5551234567".to_string(), "Alice".to_string());
         lookup
             .phone_to_name
-            .insert("15559876543".to_string(), "Bob".to_string());
-        lookup
-            .suffix_to_name
             .insert("5559876543".to_string(), "Bob".to_string());
         lookup
     }
@@ -396,4 +892,159 @@ mod tests {
         ];
         assert_eq!(lookup.get_group_display_name(&addrs, 3), "Alice");
     }
+
+    // Android contacts "Export to .vcf": vCard 2.1, quoted-printable
+    // non-ASCII names split over soft line breaks, base64 photo folded over
+    // indented lines, several cards per file.
+    const ANDROID_EXPORT: &str = "BEGIN:VCARD\r
+VERSION:2.1\r
+N;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:=C3=85str=C3=B6m;Bj=C3=B6rn;;;\r
+FN;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:Bj=C3=B6rn =C3=85str=C3=B6m =\r
+(jobb)\r
+TEL;CELL:070-123 45 67\r
+TEL;WORK;VOICE:+46 8 123 456 78\r
+EMAIL;HOME:bjorn@example.se\r
+ORG:Exempel AB;Utveckling\r
+PHOTO;ENCODING=BASE64;JPEG:/9j/4AAQSkZJRgABAQAAAQABAAD/2wBDAAgGBgcGBQgHBwcJCQgKDBQNDAsLDBkSEw8UHRof\r
+ Hh0aHBwgJC4nICIsIxwcKDcpLDAxNDQ0Hyc5PTgyPC4zNDL/2wBDAQkJCQwLDBgNDRgyIRwh\r
+\r
+END:VCARD\r
+BEGIN:VCARD\r
+VERSION:2.1\r
+N:;;;;\r
+TEL;HOME:555-0100\r
+END:VCARD\r
+BEGIN:VCARD\r
+VERSION:2.1\r
+N:Doe;Jane;Q.;Dr.;\r
+TEL;TYPE=CELL;X-Custom:(555) 123-4567\r
+END:VCARD\r
+";
+
+    #[test]
+    fn parses_android_2_1_export() {
+        let contacts = parse_vcards(ANDROID_EXPORT);
+        // The second card has no name at all and is dropped
+        assert_eq!(contacts.len(), 2);
+
+        let bjorn = &contacts[0];
+        assert_eq!(bjorn.name, "Björn Åström (jobb)");
+        assert_eq!(
+            bjorn.phones,
+            vec![
+                ContactPhone {
+                    number: "070-123 45 67".to_string(),
+                    kind: PhoneKind::Mobile,
+                },
+                ContactPhone {
+                    number: "+46 8 123 456 78".to_string(),
+                    kind: PhoneKind::Work,
+                },
+            ]
+        );
+        assert_eq!(bjorn.emails, vec!["bjorn@example.se".to_string()]);
+        assert_eq!(
+            bjorn.organization.as_deref(),
+            Some("Exempel AB, Utveckling")
+        );
+        // Folded base64 JPEG
+        assert!(bjorn
+            .photo
+            .as_deref()
+            .is_some_and(|p| p.starts_with(&[0xFF, 0xD8, 0xFF])));
+
+        // No FN: name assembled from N
+        assert_eq!(contacts[1].name, "Dr. Jane Q. Doe");
+        assert_eq!(contacts[1].phones[0].kind, PhoneKind::Mobile);
+    }
+
+    #[test]
+    fn parses_3_0_with_folding_escapes_and_groups() {
+        let card = "BEGIN:VCARD
+VERSION:3.0
+FN:O'Brien\\, Siobhán
+N:O'Brien;Siobhán;;;
+item1.TEL;type=pref:+353 1 234 5678
+item1.X-ABLabel:_$!<Mobile>!$_
+TEL;TYPE=HOME,VOICE:01 765 4321
+EMAIL;TYPE=INTERNET;TYPE=WORK:siobhan@exam
+ ple.ie
+NOTE:Line one\\nline two
+END:VCARD
+";
+        let contacts = parse_vcards(card);
+        assert_eq!(contacts.len(), 1);
+        let c = &contacts[0];
+        assert_eq!(c.name, "O'Brien, Siobhán");
+        assert_eq!(c.phones[0].kind, PhoneKind::Mobile);
+        assert_eq!(c.phones[1].kind, PhoneKind::Home);
+        assert_eq!(c.emails, vec!["siobhan@example.ie".to_string()]);
+        assert_eq!(c.organization, None);
+    }
+
+    #[test]
+    fn parses_4_0_uri_numbers_and_quoted_types() {
+        let card = "BEGIN:VCARD\r
+VERSION:4.0\r
+FN;CHARSET=UTF-8:Ana García\r
+TEL;VALUE=uri;TYPE=\"voice,cell\";PREF=1:tel:+34-600-123-456;ext=12\r
+TEL;VALUE=uri;TYPE=work:tel:+34 91 123 45 67\r
+PHOTO:data:image/png;base64,aGkh\r
+END:VCARD\r
+";
+        let c = &parse_vcards(card)[0];
+        assert_eq!(c.name, "Ana García");
+        assert_eq!(c.photo.as_deref(), Some(&b"hi!"[..]));
+        assert_eq!(c.phones[0].number, "+34-600-123-456");
+        assert_eq!(c.phones[0].kind, PhoneKind::Mobile);
+        assert_eq!(c.phones[1].kind, PhoneKind::Work);
+    }
+
+    #[test]
+    fn org_only_and_latin1_quoted_printable() {
+        let card = "BEGIN:VCARD
+VERSION:2.1
+ORG;CHARSET=ISO-8859-1;ENCODING=QUOTED-PRINTABLE:Caf=E9 M=FCller
+TEL:+49 30 1234567
+END:VCARD
+BEGIN:VCARD
+VERSION:3.0
+FN:No Way To Reach
+END:VCARD
+";
+        let contacts = parse_vcards(card);
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].name, "Café Müller");
+        assert_eq!(contacts[0].phones[0].kind, PhoneKind::Other);
+    }
+
+    #[tokio::test]
+    async fn test_reload_reuses_unchanged_files() {
+        let dir = std::env::temp_dir().join(format!("kdeconnect-contacts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let card = |name: &str, tel: &str| {
+            format!("BEGIN:VCARD\r\nVERSION:3.0\r\nFN:{name}\r\nTEL:{tel}\r\nEND:VCARD\r\n")
+        };
+        std::fs::write(dir.join("a.vcf"), card("Alice", "555-123-4567")).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a vCard").unwrap();
+
+        let first = read_sources(&dir, &HashMap::new()).await;
+        assert_eq!(first.len(), 1);
+
+        std::fs::write(dir.join("b.vcf"), card("Bob", "555-987-6543")).unwrap();
+        let second = read_sources(&dir, &first).await;
+        assert_eq!(second.len(), 2);
+        let a = dir.join("a.vcf");
+        assert!(Arc::ptr_eq(&first[&a], &second[&a]));
+
+        let lookup = ContactLookup::from_sources(second);
+        assert_eq!(lookup.get_name("5559876543"), Some("Bob"));
+        assert_eq!(lookup.all_contacts().len(), 2);
+
+        std::fs::remove_file(&a).unwrap();
+        let third = read_sources(&dir, &lookup.sources).await;
+        assert!(!third.contains_key(&a));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -24,11 +24,16 @@
 pub mod contacts;
 pub mod daemon;
 pub mod device;
+pub mod phone;
 pub mod plugins;
 
-pub use contacts::{normalize_phone_number, phone_suffix, Contact, ContactLookup};
+pub use contacts::{
+    normalize_phone_number, parse_vcards, phone_suffix, Contact, ContactLookup, ContactPhone,
+    PhoneKind,
+};
 pub use daemon::DaemonProxy;
 pub use device::DeviceProxy;
+pub use phone::{phone_key, phone_key_in, to_e164, PhoneRegion};
 
 /// KDE Connect D-Bus service name
 pub const SERVICE_NAME: &str = "org.kde.kdeconnect.daemon";
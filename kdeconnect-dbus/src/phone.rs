@@ -0,0 +1,330 @@
+//! Region-aware phone-number normalization.
+//!
+//! Addresses from the phone arrive in whatever form the carrier or the
+//! contact's author used: `+46 70 123 45 67`, `070-123 45 67`,
+//! `0046701234567`. [`phone_key`] folds all of these to one comparison key
+//! using a default region — the user's own country — to interpret numbers
+//! written without a country code.
+//!
+//! This is a dialling-plan table, not full libphonenumber: it knows each
+//! region's calling code, trunk prefix and international access prefix,
+//! which is enough to tell national and international spellings of the same
+//! number apart. The default region is process-wide; the applet sets it at
+//! startup from its config (or the locale) via [`set_default_region`].
+
+use std::sync::RwLock;
+
+/// Numbering rules for one country or region.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PhoneRegion {
+    /// ISO 3166-1 alpha-2 code, upper-case.
+    pub code: &'static str,
+    /// Country calling code, without `+`.
+    pub calling_code: &'static str,
+    /// Digit(s) dialled before a national number, e.g. `0` in most of
+    /// Europe and `1` in North America. Empty where numbers have none.
+    pub trunk_prefix: &'static str,
+    /// Digits dialled before a country code instead of `+`.
+    pub international_prefix: &'static str,
+}
+
+const fn region(
+    code: &'static str,
+    calling_code: &'static str,
+    trunk_prefix: &'static str,
+    international_prefix: &'static str,
+) -> PhoneRegion {
+    PhoneRegion {
+        code,
+        calling_code,
+        trunk_prefix,
+        international_prefix,
+    }
+}
+
+/// Known regions. The first entry is the fallback when neither the config
+/// nor the locale names a known region.
+pub const REGIONS: &[PhoneRegion] = &[
+    // North American Numbering Plan
+    region("US", "1", "1", "011"),
+    region("CA", "1", "1", "011"),
+    // Europe
+    region("AT", "43", "0", "00"),
+    region("BE", "32", "0", "00"),
+    region("BG", "359", "0", "00"),
+    region("CH", "41", "0", "00"),
+    region("CZ", "420", "", "00"),
+    region("DE", "49", "0", "00"),
+    region("DK", "45", "", "00"),
+    region("EE", "372", "", "00"),
+    region("ES", "34", "", "00"),
+    region("FI", "358", "0", "00"),
+    region("FR", "33", "0", "00"),
+    region("GB", "44", "0", "00"),
+    region("GR", "30", "", "00"),
+    region("HR", "385", "0", "00"),
+    region("HU", "36", "06", "00"),
+    region("IE", "353", "0", "00"),
+    region("IS", "354", "", "00"),
+    region("IT", "39", "", "00"),
+    region("LU", "352", "", "00"),
+    region("LV", "371", "", "00"),
+    region("NL", "31", "0", "00"),
+    region("NO", "47", "", "00"),
+    region("PL", "48", "", "00"),
+    region("PT", "351", "", "00"),
+    region("RO", "40", "0", "00"),
+    region("RS", "381", "0", "00"),
+    region("RU", "7", "8", "810"),
+    region("SE", "46", "0", "00"),
+    region("SI", "386", "0", "00"),
+    region("SK", "421", "0", "00"),
+    region("TR", "90", "0", "00"),
+    region("UA", "380", "0", "00"),
+    // Asia and the Middle East
+    region("AE", "971", "0", "00"),
+    region("CN", "86", "0", "00"),
+    region("HK", "852", "", "001"),
+    region("ID", "62", "0", "001"),
+    region("IL", "972", "0", "00"),
+    region("IN", "91", "0", "00"),
+    region("JP", "81", "0", "010"),
+    region("KR", "82", "0", "001"),
+    region("MY", "60", "0", "00"),
+    region("PH", "63", "0", "00"),
+    region("PK", "92", "0", "00"),
+    region("SA", "966", "0", "00"),
+    region("SG", "65", "", "000"),
+    region("TH", "66", "0", "001"),
+    region("TW", "886", "0", "002"),
+    region("VN", "84", "0", "00"),
+    // Oceania
+    region("AU", "61", "0", "0011"),
+    region("NZ", "64", "0", "00"),
+    // Latin America
+    region("AR", "54", "0", "00"),
+    region("BR", "55", "0", "00"),
+    region("CL", "56", "", "00"),
+    region("CO", "57", "", "00"),
+    region("MX", "52", "", "00"),
+    // Africa
+    region("EG", "20", "0", "00"),
+    region("KE", "254", "0", "000"),
+    region("NG", "234", "0", "009"),
+    region("ZA", "27", "0", "00"),
+];
+
+/// Shortest national number treated as a real phone number. Anything
+/// shorter (carrier short codes, `1234`) is compared as plain digits.
+const MIN_NATIONAL_DIGITS: usize = 7;
+
+impl PhoneRegion {
+    /// Look up a region by ISO code (case-insensitive).
+    pub fn from_code(code: &str) -> Option<&'static PhoneRegion> {
+        let code = code.trim();
+        REGIONS.iter().find(|r| r.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Region from a POSIX locale such as `sv_SE.UTF-8` or `de-AT`.
+    pub fn from_locale(locale: &str) -> Option<&'static PhoneRegion> {
+        let name = locale.split(['.', '@']).next()?;
+        let (_, territory) = name.split_once(['_', '-'])?;
+        Self::from_code(territory)
+    }
+
+    /// Region from the environment's locale (`LC_ALL`, `LC_TELEPHONE`,
+    /// `LANG`, in that order), falling back to the first of [`REGIONS`].
+    pub fn from_env() -> &'static PhoneRegion {
+        ["LC_ALL", "LC_TELEPHONE", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|locale| Self::from_locale(&locale))
+            .unwrap_or(&REGIONS[0])
+    }
+
+    fn is_nanp(&self) -> bool {
+        self.calling_code == "1"
+    }
+
+    /// Strip the trunk prefix from a national number, if present.
+    fn national<'a>(&self, digits: &'a str) -> &'a str {
+        if self.is_nanp() {
+            // 1 + 10 digits; a bare 10-digit number may itself start with 1
+            return match digits.strip_prefix('1') {
+                Some(rest) if digits.len() == 11 => rest,
+                _ => digits,
+            };
+        }
+        match digits.strip_prefix(self.trunk_prefix) {
+            Some(rest) if !self.trunk_prefix.is_empty() && rest.len() >= MIN_NATIONAL_DIGITS => {
+                rest
+            }
+            _ => digits,
+        }
+    }
+}
+
+static DEFAULT_REGION: RwLock<&'static PhoneRegion> = RwLock::new(&REGIONS[0]);
+
+/// Set the region used to read numbers without a country code.
+pub fn set_default_region(region: &'static PhoneRegion) {
+    if let Ok(mut current) = DEFAULT_REGION.write() {
+        if *current != region {
+            tracing::info!("Phone number region set to {}", region.code);
+        }
+        *current = region;
+    }
+}
+
+/// The region set by [`set_default_region`] (US until then).
+pub fn default_region() -> &'static PhoneRegion {
+    DEFAULT_REGION.read().map(|r| *r).unwrap_or(&REGIONS[0])
+}
+
+/// Split `phone` into (calling code + national number) if it's written
+/// internationally, i.e. with `+` or the region's international prefix.
+fn international_digits<'a>(phone: &str, digits: &'a str, region: &PhoneRegion) -> Option<&'a str> {
+    if phone.trim_start().starts_with('+') {
+        return Some(digits);
+    }
+    digits
+        .strip_prefix(region.international_prefix)
+        .filter(|rest| !region.international_prefix.is_empty() && rest.len() >= MIN_NATIONAL_DIGITS)
+}
+
+/// Whether `phone` is written with a country code: a leading `+` or the
+/// default region's international prefix.
+pub fn has_country_code(phone: &str) -> bool {
+    let digits = crate::contacts::normalize_phone_number(phone);
+    international_digits(phone, &digits, default_region()).is_some()
+}
+
+/// E.164 form (`+<country code><national number>`) of `phone`, read in
+/// `region` when it has no country code. `None` for short codes and
+/// non-numeric addresses.
+pub fn to_e164(phone: &str, region: &PhoneRegion) -> Option<String> {
+    let digits = crate::contacts::normalize_phone_number(phone);
+    if digits.len() < MIN_NATIONAL_DIGITS {
+        return None;
+    }
+    match international_digits(phone, &digits, region) {
+        Some(intl) => Some(format!("+{}", intl)),
+        None => Some(format!(
+            "+{}{}",
+            region.calling_code,
+            region.national(&digits)
+        )),
+    }
+}
+
+/// Comparison key for `phone` in `region`: the national number (no trunk
+/// prefix) for numbers in the region, full E.164 for foreign numbers, and
+/// the bare digits for short codes. Two spellings of the same number give
+/// the same key.
+///
+/// Home numbers keep the national form so keys stay readable and stable
+/// for data keyed before the region was configurable.
+pub fn phone_key_in(phone: &str, region: &PhoneRegion) -> String {
+    let digits = crate::contacts::normalize_phone_number(phone);
+    if digits.len() < MIN_NATIONAL_DIGITS {
+        return digits;
+    }
+    match international_digits(phone, &digits, region) {
+        Some(intl) => match intl.strip_prefix(region.calling_code) {
+            Some(national) if national.len() >= MIN_NATIONAL_DIGITS => {
+                // Some regions write the trunk prefix after the country code
+                // too (`+44 (0)20 …`); drop it so both spellings agree.
+                region.national(national).to_string()
+            }
+            _ => format!("+{}", intl),
+        },
+        None => region.national(&digits).to_string(),
+    }
+}
+
+/// [`phone_key_in`] for the default region.
+pub fn phone_key(phone: &str) -> String {
+    phone_key_in(phone, default_region())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(code: &str) -> &'static PhoneRegion {
+        PhoneRegion::from_code(code).unwrap()
+    }
+
+    #[test]
+    fn locale_parsing() {
+        assert_eq!(PhoneRegion::from_locale("sv_SE.UTF-8").unwrap().code, "SE");
+        assert_eq!(PhoneRegion::from_locale("de-AT").unwrap().code, "AT");
+        assert_eq!(
+            PhoneRegion::from_locale("cs_CZ.UTF-8@euro").unwrap().code,
+            "CZ"
+        );
+        assert!(PhoneRegion::from_locale("C").is_none());
+        assert!(PhoneRegion::from_locale("en_XX").is_none());
+    }
+
+    #[test]
+    fn north_american_keys_match_legacy_form() {
+        let us = region("US");
+        assert_eq!(phone_key_in("+1 (555) 123-4567", us), "5551234567");
+        assert_eq!(phone_key_in("15551234567", us), "5551234567");
+        assert_eq!(phone_key_in("555.123.4567", us), "5551234567");
+        assert_eq!(phone_key_in("1234567890", us), "1234567890");
+        assert_eq!(phone_key_in("011 46 70 123 45 67", us), "+46701234567");
+        assert_eq!(phone_key_in("1234", us), "1234");
+    }
+
+    #[test]
+    fn national_and_international_spellings_agree() {
+        let se = region("SE");
+        assert_eq!(phone_key_in("070-123 45 67", se), "701234567");
+        assert_eq!(phone_key_in("+46 70 123 45 67", se), "701234567");
+        assert_eq!(phone_key_in("0046701234567", se), "701234567");
+
+        let gb = region("GB");
+        assert_eq!(phone_key_in("+44 (0)20 7946 0958", gb), "2079460958");
+        assert_eq!(phone_key_in("020 7946 0958", gb), "2079460958");
+
+        // Italian numbers keep their leading 0
+        let it = region("IT");
+        assert_eq!(phone_key_in("+39 06 1234 5678", it), "0612345678");
+        assert_eq!(phone_key_in("06 1234 5678", it), "0612345678");
+    }
+
+    #[test]
+    fn foreign_numbers_keep_country_code() {
+        let de = region("DE");
+        assert_eq!(phone_key_in("+1 555 123 4567", de), "+15551234567");
+        // A US number no longer collides with a German one sharing its
+        // last ten digits.
+        assert_ne!(
+            phone_key_in("+1 555 123 4567", de),
+            phone_key_in("+49 555 123 4567", de)
+        );
+    }
+
+    #[test]
+    fn e164_formatting() {
+        assert_eq!(
+            to_e164("0151 2345 6789", region("DE")).as_deref(),
+            Some("+4915123456789")
+        );
+        assert_eq!(
+            to_e164("(555) 123-4567", region("US")).as_deref(),
+            Some("+15551234567")
+        );
+        assert_eq!(
+            to_e164("090-1234-5678", region("JP")).as_deref(),
+            Some("+819012345678")
+        );
+        assert_eq!(
+            to_e164("+44 20 7946 0958", region("SE")).as_deref(),
+            Some("+442079460958")
+        );
+        assert!(to_e164("72345", region("US")).is_none());
+    }
+}
@@ -0,0 +1,22 @@
+//! D-Bus proxy for the contacts plugin.
+//!
+//! Syncs the phone's address book into the local kpeoplevcard cache that
+//! [`ContactLookup`](crate::ContactLookup) reads.
+
+use zbus::proxy;
+
+/// Proxy for the contacts plugin D-Bus interface.
+#[proxy(
+    interface = "org.kde.kdeconnect.device.contacts",
+    default_service = "org.kde.kdeconnect.daemon"
+)]
+pub trait Contacts {
+    /// Ask the phone for its contacts and write new or changed ones to the
+    /// local vCard directory.
+    #[zbus(name = "synchronizeRemoteWithLocal")]
+    fn synchronize_remote_with_local(&self) -> zbus::Result<()>;
+
+    /// Signal emitted once the local vCard cache has been updated.
+    #[zbus(signal, name = "localCacheSynchronized")]
+    fn local_cache_synchronized(&self, uids: Vec<String>) -> zbus::Result<()>;
+}
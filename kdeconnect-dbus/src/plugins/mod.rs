@@ -6,6 +6,7 @@
 
 pub mod battery;
 pub mod clipboard;
+pub mod contacts;
 pub mod findmyphone;
 pub mod mprisremote;
 pub mod notifications;
@@ -16,14 +17,20 @@ pub mod telephony;
 
 pub use battery::BatteryProxy;
 pub use clipboard::ClipboardProxy;
+pub use contacts::ContactsProxy;
 pub use findmyphone::FindMyPhoneProxy;
-pub use mprisremote::MprisRemoteProxy;
+pub use mprisremote::{
+    find_remote_player_service, is_remote_player_identity, parse_mprisremote_property,
+    read_mprisremote_properties, read_remote_player_capabilities, LoopStatus, MprisRemoteProperty,
+    MprisRemoteProxy, RemotePlayerCapabilities, RemotePlayerProxy, RemotePlayerRootProxy,
+    REMOTE_PLAYER_PREFIX,
+};
 pub use notifications::{NotificationInfo, NotificationProxy, NotificationsProxy};
 pub use ping::PingProxy;
 pub use share::ShareProxy;
 pub use sms::{
     is_address_valid, parse_conversations, parse_messages, parse_sms_message, Attachment,
-    ConversationSummary, ConversationsProxy, MessageType, SmsMessage, SmsProxy, MAX_CONVERSATIONS,
-    OPTIMISTIC_MESSAGE_UID,
+    ConversationSummary, ConversationsProxy, DeliveryState, MessageType, SmsMessage, SmsProxy,
+    MAX_CONVERSATIONS, OPTIMISTIC_MESSAGE_UID,
 };
 pub use telephony::TelephonyProxy;
@@ -2,7 +2,13 @@
 //!
 //! Provides control of media players running on the remote device (phone).
 
+use std::collections::HashMap;
+
+use zbus::names::InterfaceName;
 use zbus::proxy;
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{OwnedValue, Value};
+use zbus::Connection;
 
 /// Proxy for the MPRIS Remote plugin D-Bus interface.
 ///
@@ -53,6 +59,11 @@ pub trait MprisRemote {
     #[zbus(property, name = "album")]
     fn album(&self) -> zbus::Result<String>;
 
+    /// Get the current track's album art as a URL (usually `file://` into the
+    /// daemon's cache). Empty when the track has no artwork.
+    #[zbus(property, name = "albumArtUrl")]
+    fn album_art_url(&self) -> zbus::Result<String>;
+
     /// Check if the player can seek.
     #[zbus(property, name = "canSeek")]
     fn can_seek(&self) -> zbus::Result<bool>;
@@ -81,4 +92,421 @@ pub trait MprisRemote {
     /// Valid actions: "Play", "Pause", "PlayPause", "Stop", "Next", "Previous"
     #[zbus(name = "sendAction")]
     fn send_action(&self, action: &str) -> zbus::Result<()>;
+
+    /// Emitted when any player property changes. Carries no values; the
+    /// properties have to be read again.
+    #[zbus(signal, name = "propertiesChanged")]
+    fn properties_changed(&self) -> zbus::Result<()>;
+}
+
+/// Bus name prefix of the MPRIS services the daemon registers for each media
+/// player on a connected device. The capability properties missing from the
+/// mprisremote interface (can play/pause/skip, shuffle, loop) live there.
+pub const REMOTE_PLAYER_PREFIX: &str = "org.mpris.MediaPlayer2.kdeconnect.";
+
+/// Proxy for the root MPRIS interface of a per-player service, used to tell
+/// the services apart.
+#[proxy(
+    interface = "org.mpris.MediaPlayer2",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+pub trait RemotePlayerRoot {
+    /// Player name and device, e.g. "Spotify on Pixel 7" (localized).
+    #[zbus(property, name = "Identity")]
+    fn identity(&self) -> zbus::Result<String>;
+}
+
+/// Proxy for the player interface of a per-player service.
+///
+/// Connect with `.destination()` set to one of the [`REMOTE_PLAYER_PREFIX`]
+/// bus names. Only the capabilities and modes are declared; playback itself
+/// goes through [`MprisRemoteProxy`].
+#[proxy(
+    interface = "org.mpris.MediaPlayer2.Player",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+pub trait RemotePlayer {
+    #[zbus(property, name = "CanPlay")]
+    fn can_play(&self) -> zbus::Result<bool>;
+
+    #[zbus(property, name = "CanPause")]
+    fn can_pause(&self) -> zbus::Result<bool>;
+
+    #[zbus(property, name = "CanGoNext")]
+    fn can_go_next(&self) -> zbus::Result<bool>;
+
+    #[zbus(property, name = "CanGoPrevious")]
+    fn can_go_previous(&self) -> zbus::Result<bool>;
+
+    /// Whether shuffle is on. Fails if the phone's player doesn't report it.
+    #[zbus(property, name = "Shuffle")]
+    fn shuffle(&self) -> zbus::Result<bool>;
+
+    #[zbus(property, name = "Shuffle")]
+    fn set_shuffle(&self, shuffle: bool) -> zbus::Result<()>;
+
+    /// "None", "Track" or "Playlist". Fails if the phone's player doesn't
+    /// report it.
+    #[zbus(property, name = "LoopStatus")]
+    fn loop_status(&self) -> zbus::Result<String>;
+
+    #[zbus(property, name = "LoopStatus")]
+    fn set_loop_status(&self, status: &str) -> zbus::Result<()>;
+}
+
+/// Whether a per-player service's `Identity` belongs to `player` on the
+/// device named `device_name`. The daemon's identity string is translated
+/// ("%1 on %2" in English), so both names have to make up the whole string,
+/// in either order, joined by a single separator word: "Music" doesn't match
+/// "YouTube Music on Pixel 7", nor "Pixel" a device called "Pixel 7".
+pub fn is_remote_player_identity(identity: &str, player: &str, device_name: &str) -> bool {
+    if player.is_empty() || device_name.is_empty() {
+        return false;
+    }
+    let is_separator = |sep: &str| {
+        let word = sep.trim();
+        !word.is_empty() && !word.contains(char::is_whitespace)
+    };
+    let joins = |first: &str, second: &str| {
+        identity
+            .strip_prefix(first)
+            .and_then(|rest| rest.strip_suffix(second))
+            .is_some_and(is_separator)
+    };
+    joins(player, device_name) || joins(device_name, player)
+}
+
+/// Capabilities and modes of a per-player service, read in one `GetAll`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemotePlayerCapabilities {
+    pub can_play: bool,
+    pub can_pause: bool,
+    pub can_go_next: bool,
+    pub can_go_previous: bool,
+    /// `None` when the phone's player doesn't report shuffle.
+    pub shuffle: Option<bool>,
+    /// `None` when the phone's player doesn't report a loop mode.
+    pub loop_status: Option<LoopStatus>,
+}
+
+impl RemotePlayerCapabilities {
+    /// Build from the player interface's properties. Missing `Can*` flags
+    /// count as allowed.
+    pub fn from_properties(properties: &HashMap<String, OwnedValue>) -> Self {
+        let flag = |name: &str| match properties.get(name).map(|v| &**v) {
+            Some(Value::Bool(b)) => Some(*b),
+            _ => None,
+        };
+        Self {
+            can_play: flag("CanPlay").unwrap_or(true),
+            can_pause: flag("CanPause").unwrap_or(true),
+            can_go_next: flag("CanGoNext").unwrap_or(true),
+            can_go_previous: flag("CanGoPrevious").unwrap_or(true),
+            shuffle: flag("Shuffle"),
+            loop_status: match properties.get("LoopStatus").map(|v| &**v) {
+                Some(Value::Str(s)) => LoopStatus::parse(s.as_str()),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Bus name of the per-player service for `player` on the device named
+/// `device_name`, if the daemon has registered one.
+pub async fn find_remote_player_service(
+    conn: &Connection,
+    device_name: &str,
+    player: &str,
+) -> zbus::Result<Option<String>> {
+    let names = zbus::fdo::DBusProxy::new(conn).await?.list_names().await?;
+    for name in names
+        .iter()
+        .map(|n| n.as_str())
+        .filter(|n| n.starts_with(REMOTE_PLAYER_PREFIX))
+    {
+        let Ok(root) = RemotePlayerRootProxy::builder(conn)
+            .destination(name)?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+        else {
+            continue;
+        };
+        let identity = root.identity().await.unwrap_or_default();
+        if is_remote_player_identity(&identity, player, device_name) {
+            return Ok(Some(name.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Read the capabilities of the per-player service `service`. Fails if the
+/// service has gone away.
+pub async fn read_remote_player_capabilities(
+    conn: &Connection,
+    service: &str,
+) -> zbus::Result<RemotePlayerCapabilities> {
+    let properties = zbus::fdo::PropertiesProxy::builder(conn)
+        .destination(service)?
+        .path("/org/mpris/MediaPlayer2")?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?
+        .get_all(InterfaceName::from_static_str_unchecked(
+            "org.mpris.MediaPlayer2.Player",
+        ))
+        .await?;
+    Ok(RemotePlayerCapabilities::from_properties(&properties))
+}
+
+/// MPRIS loop mode of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopStatus {
+    #[default]
+    None,
+    /// Repeat the current track.
+    Track,
+    /// Repeat the whole playlist.
+    Playlist,
+}
+
+impl LoopStatus {
+    /// Parse the MPRIS `LoopStatus` string.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "None" => Some(Self::None),
+            "Track" => Some(Self::Track),
+            "Playlist" => Some(Self::Playlist),
+            _ => None,
+        }
+    }
+
+    /// The MPRIS `LoopStatus` string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Track => "Track",
+            Self::Playlist => "Playlist",
+        }
+    }
+
+    /// Mode the repeat button switches to: off, all, one, off.
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::Playlist,
+            Self::Playlist => Self::Track,
+            Self::Track => Self::None,
+        }
+    }
+}
+
+/// A single property of the mprisremote interface, as read by
+/// [`read_mprisremote_properties`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MprisRemoteProperty {
+    PlayerList(Vec<String>),
+    Player(String),
+    IsPlaying(bool),
+    Volume(i32),
+    Length(i32),
+    Position(i32),
+    Title(String),
+    Artist(String),
+    Album(String),
+    AlbumArtUrl(String),
+    CanSeek(bool),
+}
+
+/// Parse one entry of a `GetAll` dictionary. Returns `None` for properties
+/// we don't track or values of an unexpected type.
+pub fn parse_mprisremote_property(name: &str, value: &Value<'_>) -> Option<MprisRemoteProperty> {
+    let string = || match value {
+        Value::Str(s) => Some(s.as_str().to_string()),
+        _ => None,
+    };
+    let int = || match value {
+        Value::I32(v) => Some(*v),
+        Value::I64(v) => i32::try_from(*v).ok(),
+        Value::U32(v) => i32::try_from(*v).ok(),
+        _ => None,
+    };
+    let boolean = || match value {
+        Value::Bool(b) => Some(*b),
+        _ => None,
+    };
+
+    match name {
+        "playerList" => match value {
+            Value::Array(arr) => Some(MprisRemoteProperty::PlayerList(
+                arr.iter()
+                    .filter_map(|v| match v {
+                        Value::Str(s) => Some(s.as_str().to_string()),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            _ => None,
+        },
+        "player" => string().map(MprisRemoteProperty::Player),
+        "isPlaying" => boolean().map(MprisRemoteProperty::IsPlaying),
+        "volume" => int().map(MprisRemoteProperty::Volume),
+        "length" => int().map(MprisRemoteProperty::Length),
+        "position" => int().map(MprisRemoteProperty::Position),
+        "title" => string().map(MprisRemoteProperty::Title),
+        "artist" => string().map(MprisRemoteProperty::Artist),
+        "album" => string().map(MprisRemoteProperty::Album),
+        "albumArtUrl" => string().map(MprisRemoteProperty::AlbumArtUrl),
+        "canSeek" => boolean().map(MprisRemoteProperty::CanSeek),
+        _ => None,
+    }
+}
+
+/// Read every mprisremote property at `path` in one `GetAll` call.
+///
+/// The daemon only announces changes with the argument-less
+/// `propertiesChanged` signal (Qt doesn't emit the standard
+/// `PropertiesChanged`), so each change means reading the state again; one
+/// round trip keeps that cheap.
+pub async fn read_mprisremote_properties(
+    conn: &Connection,
+    path: &str,
+) -> zbus::Result<Vec<MprisRemoteProperty>> {
+    let properties = zbus::fdo::PropertiesProxy::builder(conn)
+        .destination(crate::SERVICE_NAME)?
+        .path(path)?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?
+        .get_all(InterfaceName::from_static_str_unchecked(
+            "org.kde.kdeconnect.device.mprisremote",
+        ))
+        .await?;
+    Ok(properties
+        .iter()
+        .filter_map(|(name, value)| parse_mprisremote_property(name, value))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_properties() {
+        assert_eq!(
+            parse_mprisremote_property("title", &Value::from("Song")),
+            Some(MprisRemoteProperty::Title("Song".to_string()))
+        );
+        assert_eq!(
+            parse_mprisremote_property("isPlaying", &Value::from(true)),
+            Some(MprisRemoteProperty::IsPlaying(true))
+        );
+        assert_eq!(
+            parse_mprisremote_property("position", &Value::from(61_000i32)),
+            Some(MprisRemoteProperty::Position(61_000))
+        );
+        assert_eq!(
+            parse_mprisremote_property("playerList", &Value::from(vec!["Spotify", "VLC"])),
+            Some(MprisRemoteProperty::PlayerList(vec![
+                "Spotify".to_string(),
+                "VLC".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn loop_status_round_trips_and_cycles() {
+        for status in [LoopStatus::None, LoopStatus::Track, LoopStatus::Playlist] {
+            assert_eq!(LoopStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(LoopStatus::parse("Shuffle"), None);
+        assert_eq!(LoopStatus::None.next(), LoopStatus::Playlist);
+        assert_eq!(LoopStatus::Playlist.next(), LoopStatus::Track);
+        assert_eq!(LoopStatus::Track.next(), LoopStatus::None);
+    }
+
+    #[test]
+    fn matches_remote_player_identity() {
+        assert!(is_remote_player_identity(
+            "Spotify on Pixel 7",
+            "Spotify",
+            "Pixel 7"
+        ));
+        assert!(is_remote_player_identity(
+            "Pixel 7 - Spotify",
+            "Spotify",
+            "Pixel 7"
+        ));
+        assert!(!is_remote_player_identity(
+            "VLC on Pixel 7",
+            "Spotify",
+            "Pixel 7"
+        ));
+        assert!(!is_remote_player_identity(
+            "Spotify on Galaxy",
+            "Spotify",
+            "Pixel 7"
+        ));
+        assert!(!is_remote_player_identity(
+            "Spotify on Pixel 7",
+            "",
+            "Pixel 7"
+        ));
+    }
+
+    #[test]
+    fn identity_match_needs_whole_names() {
+        assert!(!is_remote_player_identity(
+            "YouTube Music on Pixel 7",
+            "Music",
+            "Pixel 7"
+        ));
+        assert!(!is_remote_player_identity(
+            "Spotify on Pixel 7",
+            "Spotify",
+            "Pixel"
+        ));
+        assert!(!is_remote_player_identity(
+            "Music Pro on Pixel 7",
+            "Music",
+            "Pixel 7"
+        ));
+        assert!(is_remote_player_identity(
+            "Pixel 7 上的 Spotify",
+            "Spotify",
+            "Pixel 7"
+        ));
+    }
+
+    #[test]
+    fn capabilities_from_properties() {
+        let mut properties = HashMap::new();
+        properties.insert("CanGoNext".to_string(), OwnedValue::from(false));
+        properties.insert("Shuffle".to_string(), OwnedValue::from(true));
+        properties.insert(
+            "LoopStatus".to_string(),
+            OwnedValue::try_from(Value::from("Track")).unwrap(),
+        );
+        let caps = RemotePlayerCapabilities::from_properties(&properties);
+        assert!(caps.can_play && caps.can_pause && caps.can_go_previous);
+        assert!(!caps.can_go_next);
+        assert_eq!(caps.shuffle, Some(true));
+        assert_eq!(caps.loop_status, Some(LoopStatus::Track));
+
+        let caps = RemotePlayerCapabilities::from_properties(&HashMap::new());
+        assert_eq!(caps.shuffle, None);
+        assert_eq!(caps.loop_status, None);
+    }
+
+    #[test]
+    fn ignores_unknown_names_and_mismatched_types() {
+        assert_eq!(
+            parse_mprisremote_property("shuffle", &Value::from(true)),
+            None
+        );
+        assert_eq!(
+            parse_mprisremote_property("volume", &Value::from("50")),
+            None
+        );
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Provides access to SMS conversations and messages on the remote device.
 
+use serde::{Deserialize, Serialize};
 use zbus::proxy;
 use zbus::zvariant::{OwnedValue, Value};
 
@@ -133,7 +134,7 @@ pub trait Conversations {
 ///
 /// KDE Connect sends attachments as D-Bus type `a(xsss)` (array of structs):
 /// (part_id: i64, mime_type: String, base64_encoded_thumbnail: String, unique_identifier: String)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attachment {
     /// Part ID within the MMS message.
     pub part_id: i64,
@@ -146,7 +147,7 @@ pub struct Attachment {
 }
 
 /// Message type indicating direction (sent vs received).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MessageType {
     /// Message received from the contact (inbox).
     Inbox = 1,
@@ -171,8 +172,36 @@ impl From<i32> for MessageType {
     }
 }
 
+/// Delivery state of a message, from its raw Android type.
+///
+/// [`MessageType`] only says which way a message went; this keeps the
+/// outgoing states it folds into `Sent` apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryState {
+    /// Received, or sent and accepted by the carrier (types 1 and 2, and
+    /// anything unrecognised).
+    Delivered,
+    /// Saved on the phone but never sent (type 3).
+    Draft,
+    /// Waiting to go out (types 4 outbox and 6 queued).
+    Pending,
+    /// The phone gave up sending it (type 5).
+    Failed,
+}
+
+impl From<i32> for DeliveryState {
+    fn from(value: i32) -> Self {
+        match value {
+            3 => DeliveryState::Draft,
+            4 | 6 => DeliveryState::Pending,
+            5 => DeliveryState::Failed,
+            _ => DeliveryState::Delivered,
+        }
+    }
+}
+
 /// A single SMS message.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SmsMessage {
     /// The message text content.
     pub body: String,
@@ -183,6 +212,10 @@ pub struct SmsMessage {
     pub date: i64,
     /// Whether this is a sent or received message.
     pub message_type: MessageType,
+    /// Raw Android message type (1-6), see [`DeliveryState`]. `0` when
+    /// unknown, e.g. for messages archived before this was tracked.
+    #[serde(default)]
+    pub raw_type: i32,
     /// Whether the message has been read.
     pub read: bool,
     /// The conversation thread ID this message belongs to.
@@ -202,6 +235,11 @@ pub struct SmsMessage {
 pub const OPTIMISTIC_MESSAGE_UID: i32 = i32::MIN;
 
 impl SmsMessage {
+    /// Delivery state from the raw Android type.
+    pub fn delivery_state(&self) -> DeliveryState {
+        DeliveryState::from(self.raw_type)
+    }
+
     /// Get the primary address (first participant) for display purposes.
     pub fn primary_address(&self) -> &str {
         self.addresses
@@ -212,7 +250,7 @@ impl SmsMessage {
 }
 
 /// Summary of a conversation for the conversation list.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationSummary {
     /// The conversation thread ID.
     pub thread_id: i64,
@@ -383,6 +421,7 @@ pub fn parse_sms_message(value: &OwnedValue) -> Option<SmsMessage> {
         addresses,
         date,
         message_type: msg_type_parsed,
+        raw_type: msg_type_value,
         read,
         thread_id,
         uid,
@@ -485,3 +524,21 @@ pub fn is_address_valid(address: &str) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivery_state_keeps_outgoing_types_apart() {
+        assert_eq!(DeliveryState::from(1), DeliveryState::Delivered);
+        assert_eq!(DeliveryState::from(2), DeliveryState::Delivered);
+        assert_eq!(DeliveryState::from(3), DeliveryState::Draft);
+        assert_eq!(DeliveryState::from(4), DeliveryState::Pending);
+        assert_eq!(DeliveryState::from(5), DeliveryState::Failed);
+        assert_eq!(DeliveryState::from(6), DeliveryState::Pending);
+        assert_eq!(DeliveryState::from(0), DeliveryState::Delivered);
+        // Every non-inbox type still reads as outgoing.
+        assert_eq!(MessageType::from(5), MessageType::Sent);
+    }
+}
@@ -12,6 +12,9 @@ use zbus::proxy;
 pub trait Telephony {
     /// Signal emitted when a call is received or missed.
     ///
+    /// The daemon handles answered and ended calls internally and emits
+    /// nothing for them.
+    ///
     /// # Arguments
     /// * `event` - "callReceived" for incoming call, "missedCall" for missed call
     /// * `phone_number` - The caller's phone number